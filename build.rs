@@ -0,0 +1,27 @@
+//! Embeds every public key in `keys/`, which signed checksum files are verified against during generation
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=keys");
+    let mut keys = fs::read_dir("keys")
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| matches!(path.extension().and_then(|e| e.to_str()), Some("asc" | "pub")))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    keys.sort();
+
+    let entries = keys
+        .iter()
+        .map(|path| {
+            let name = path.file_name().unwrap().to_string_lossy();
+            let path = fs::canonicalize(path).unwrap();
+            format!("    ({name:?}, include_str!({:?})),\n", path.display().to_string())
+        })
+        .collect::<String>();
+    let output = Path::new(&env::var("OUT_DIR").unwrap()).join("keys.rs");
+    fs::write(output, format!("&[\n{entries}]\n")).unwrap();
+}
@@ -0,0 +1,194 @@
+use crate::checksums::{single_checksum, Bsd, ChecksumFormat, Whitespace};
+use crate::error::{GenerateError, OrGenerateError};
+use crate::store_data::{ArchiveFormat, ChecksumAlgorithm, Config, Distro, Source, WebSource};
+use crate::utils::capture_page;
+use join_futures::join_futures;
+use quickemu::config::GuestOS;
+use regex::Regex;
+
+const PROXMOX_MIRROR: &str = "https://enterprise.proxmox.com/iso/";
+
+pub struct ProxmoxVE;
+impl Distro for ProxmoxVE {
+    const NAME: &'static str = "proxmox-ve";
+    const PRETTY_NAME: &'static str = "Proxmox VE";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.proxmox.com/en/proxmox-virtual-environment/overview");
+    const DESCRIPTION: Option<&'static str> = Some("Complete, open-source server management platform for enterprise virtualization, integrating KVM and LXC containers.");
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(PROXMOX_MIRROR).await.or_network_error(PROXMOX_MIRROR)?;
+        let iso_regex = Regex::new(r#"href="(proxmox-ve_((\d+)\.(\d+))-(\d+)\.iso)""#).unwrap();
+        let mut checksums = Whitespace.fetch(&format!("{PROXMOX_MIRROR}SHA256SUMS")).await;
+
+        let mut isos = iso_regex
+            .captures_iter(&page)
+            .filter_map(|c| {
+                let version = (c[3].parse::<u32>().ok()?, c[4].parse::<u32>().ok()?, c[5].parse::<u32>().ok()?);
+                Some((version, c[1].to_string(), c[2].to_string()))
+            })
+            .collect::<Vec<_>>();
+        isos.sort_unstable();
+        isos.reverse();
+        // Every point release and respin is kept on the mirror, only the newest of each major release is of interest
+        isos.dedup_by_key(|((major, ..), ..)| *major);
+
+        Ok(isos
+            .into_iter()
+            .take(Self::release_limit())
+            .map(|(_, iso, release)| {
+                let checksum = checksums.as_mut().and_then(|cs| cs.remove(&iso));
+                Config {
+                    release,
+                    iso: Some(vec![Source::Web(WebSource::new(
+                        format!("{PROXMOX_MIRROR}{iso}"),
+                        checksum,
+                        None,
+                        None,
+                    ))]),
+                    ..Default::default()
+                }
+            })
+            .collect::<Vec<Config>>())
+    }
+}
+
+const TRUENAS_SCALE_MIRROR: &str = "https://download.truenas.com/";
+
+pub struct TrueNASScale;
+impl Distro for TrueNASScale {
+    const NAME: &'static str = "truenas-scale";
+    const PRETTY_NAME: &'static str = "TrueNAS SCALE";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.truenas.com/truenas-scale/");
+    const DESCRIPTION: Option<&'static str> = Some("Debian-based storage operating system, combining ZFS with containers and virtualization.");
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(TRUENAS_SCALE_MIRROR)
+            .await
+            .or_network_error(TRUENAS_SCALE_MIRROR)?;
+        // Trains are named alphabetically, e.g. TrueNAS-SCALE-Dragonfish followed by TrueNAS-SCALE-ElectricEel
+        let train_regex = Regex::new(r#"href="(TrueNAS-SCALE-[A-Za-z]+)/""#).unwrap();
+        let mut trains = train_regex.captures_iter(&page).map(|c| c[1].to_string()).collect::<Vec<_>>();
+        trains.sort_unstable();
+        trains.dedup();
+
+        let futures = trains.into_iter().rev().take(Self::release_limit()).map(|train| async move {
+            let mirror = format!("{TRUENAS_SCALE_MIRROR}{train}/");
+            let page = capture_page(&mirror).await?;
+            let version_regex = Regex::new(r#"href="(\d+\.\d+(?:\.\d+)*)/""#).unwrap();
+            let release = version_regex
+                .captures_iter(&page)
+                .map(|c| c[1].to_string())
+                .max_by_key(|v| v.split('.').map(|n| n.parse::<u32>().unwrap_or_default()).collect::<Vec<u32>>())?;
+            let url = format!("{mirror}{release}/TrueNAS-SCALE-{release}.iso");
+            let checksum = single_checksum(&format!("{url}.sha256")).await;
+            Some(Config {
+                release,
+                iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                ..Default::default()
+            })
+        });
+
+        Ok(join_futures!(futures, 1))
+    }
+}
+
+const TRUENAS_CORE_MIRROR: &str = "https://download.freenas.org/";
+
+pub struct TrueNASCore;
+impl Distro for TrueNASCore {
+    const NAME: &'static str = "truenas-core";
+    const PRETTY_NAME: &'static str = "TrueNAS CORE";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.truenas.com/truenas-core/");
+    const DESCRIPTION: Option<&'static str> = Some("FreeBSD-based storage operating system built on ZFS, the successor to FreeNAS.");
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(TRUENAS_CORE_MIRROR).await.or_network_error(TRUENAS_CORE_MIRROR)?;
+        let major_regex = Regex::new(r#"href="(1\d\.\d)/""#).unwrap();
+        let mut majors = major_regex.captures_iter(&page).map(|c| c[1].to_string()).collect::<Vec<_>>();
+        majors.sort_unstable();
+        majors.dedup();
+
+        let futures = majors.into_iter().rev().take(Self::release_limit()).map(|major| async move {
+            let mirror = format!("{TRUENAS_CORE_MIRROR}{major}/STABLE/");
+            let page = capture_page(&mirror).await?;
+            // Initial releases are named RELEASE, followed by updates named U1, U1.1, U2 and so on
+            let update_regex = Regex::new(r#"href="(RELEASE|U(\d+)(?:\.(\d+))?)/""#).unwrap();
+            let update = update_regex
+                .captures_iter(&page)
+                .map(|c| {
+                    let number = |i| c.get(i).and_then(|n| n.as_str().parse::<u32>().ok()).unwrap_or_default();
+                    ((number(2), number(3)), c[1].to_string())
+                })
+                .max()
+                .map(|(_, update)| update)?;
+            let release = format!("{major}-{update}");
+            let url = format!("{mirror}{update}/x64/TrueNAS-{release}.iso");
+            let checksum = single_checksum(&format!("{url}.sha256")).await;
+            Some(Config {
+                guest_os: GuestOS::FreeBSD,
+                release,
+                iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                ..Default::default()
+            })
+        });
+
+        Ok(join_futures!(futures, 1))
+    }
+}
+
+const OPNSENSE_MIRROR: &str = "https://pkg.opnsense.org/releases/";
+
+pub struct OPNsense;
+impl Distro for OPNsense {
+    const NAME: &'static str = "opnsense";
+    const PRETTY_NAME: &'static str = "OPNsense";
+    const HOMEPAGE: Option<&'static str> = Some("https://opnsense.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Open source, FreeBSD-based firewall and routing platform.");
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(OPNSENSE_MIRROR).await.or_network_error(OPNSENSE_MIRROR)?;
+        let release_regex = Regex::new(r#"href="(\d+\.\d+)/""#).unwrap();
+        let mut releases = release_regex.captures_iter(&page).map(|c| c[1].to_string()).collect::<Vec<_>>();
+        releases.sort_by_cached_key(|r| r.split('.').map(|n| n.parse::<u32>().unwrap_or_default()).collect::<Vec<u32>>());
+        releases.dedup();
+
+        let futures = releases
+            .into_iter()
+            .rev()
+            .take(Self::release_limit())
+            .map(|release| async move {
+                let mirror = format!("{OPNSENSE_MIRROR}{release}/");
+                let mut checksums = Bsd(ChecksumAlgorithm::Sha256)
+                    .fetch(&format!("{mirror}OPNsense-{release}-checksums-amd64.sha256"))
+                    .await;
+                // The DVD image is an installer, the others are written directly to a disk with either a VGA or serial console
+                ["dvd", "vga", "serial"]
+                    .into_iter()
+                    .map(|edition| {
+                        let image = match edition {
+                            "dvd" => format!("OPNsense-{release}-{edition}-amd64.iso.bz2"),
+                            _ => format!("OPNsense-{release}-{edition}-amd64.img.bz2"),
+                        };
+                        let checksum = checksums.as_mut().and_then(|cs| cs.remove(&image));
+                        let source = vec![Source::Web(WebSource::new(
+                            format!("{mirror}{image}"),
+                            checksum,
+                            Some(ArchiveFormat::Bz2),
+                            None,
+                        ))];
+                        let (iso, img) = if edition == "dvd" { (Some(source), None) } else { (None, Some(source)) };
+                        Config {
+                            guest_os: GuestOS::FreeBSD,
+                            release: release.clone(),
+                            edition: Some(edition.to_string()),
+                            iso,
+                            img,
+                            ..Default::default()
+                        }
+                    })
+                    .collect::<Vec<Config>>()
+            });
+
+        Ok(join_futures!(futures, 1))
+    }
+}
@@ -1,14 +1,63 @@
-use crate::store_data::{ArchiveFormat, ChecksumSeparation, Config, Disk, Distro, Source, WebSource};
-use crate::utils::capture_page;
+use crate::checksums::{Bsd, ChecksumFormat};
+use crate::error::{GenerateError, OrGenerateError};
+use crate::store_data::{ArchiveFormat, ChecksumAlgorithm, ChecksumFileFormat, Config, Disk, Distro, Signature, Source, SourceMetadata, WebSource};
+use crate::utils::{capture_page, list_links_matching, spawn};
 use join_futures::join_futures;
-use quickemu::config::{Arch, GuestOS};
+use quickemu::config::{Arch, DiskFormat, GuestOS};
 use regex::Regex;
-use std::sync::Arc;
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Kinds of installation media, named consistently across the BSDs so they can be grouped without parsing free-form editions
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BsdEdition {
+    /// Installer which fetches the base system over the network
+    Minimal,
+    /// CD image containing the base system
+    Disc1,
+    /// DVD image containing the base system alongside packages
+    Dvd1,
+    /// Installer written to a USB drive
+    Memstick,
+    /// Preinstalled disk image
+    VmImage,
+}
+
+impl BsdEdition {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Minimal => "minimal",
+            Self::Disc1 => "disc1",
+            Self::Dvd1 => "dvd1",
+            Self::Memstick => "memstick",
+            Self::VmImage => "vm-image",
+        }
+    }
+}
+
+impl fmt::Display for BsdEdition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
 const FREEBSD_X86_64_RELEASES: &str = "https://download.freebsd.org/ftp/releases/amd64/amd64/";
 const FREEBSD_AARCH64_RELEASES: &str = "https://download.freebsd.org/ftp/releases/arm64/aarch64/";
 const FREEBSD_RISCV64_RELEASES: &str = "https://download.freebsd.org/ftp/releases/riscv/riscv64/";
-const FREEBSD_EDITIONS: [&str; 2] = ["disc1", "dvd1"];
+const FREEBSD_EDITIONS: [BsdEdition; 4] = [BsdEdition::Minimal, BsdEdition::Disc1, BsdEdition::Dvd1, BsdEdition::Memstick];
+const FREEBSD_SNAPSHOT_EDITIONS: [BsdEdition; 3] = [BsdEdition::Minimal, BsdEdition::Disc1, BsdEdition::Memstick];
+
+static INCLUDE_SNAPSHOTS: AtomicBool = AtomicBool::new(false);
+
+/// Include STABLE and CURRENT snapshot builds, must be called before any FreeBSD configs are generated
+pub fn include_freebsd_snapshots() {
+    INCLUDE_SNAPSHOTS.store(true, Ordering::Relaxed);
+}
 
 pub struct FreeBSD;
 impl Distro for FreeBSD {
@@ -16,8 +65,8 @@ impl Distro for FreeBSD {
     const PRETTY_NAME: &'static str = "FreeBSD";
     const HOMEPAGE: Option<&'static str> = Some("https://www.freebsd.org/");
     const DESCRIPTION: Option<&'static str> = Some("Operating system used to power modern servers, desktops, and embedded platforms.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let freebsd_regex = Arc::new(Regex::new(r#"href="([0-9\.]+)-RELEASE"#).unwrap());
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let freebsd_regex = Arc::new(Regex::new(r"^([0-9.]+)-RELEASE/$").unwrap());
         let futures = [
             (FREEBSD_X86_64_RELEASES, "amd64", Arch::x86_64),
             (FREEBSD_AARCH64_RELEASES, "arm64-aarch64", Arch::aarch64),
@@ -28,9 +77,10 @@ impl Distro for FreeBSD {
             let freebsd_regex = freebsd_regex.clone();
 
             async move {
-                if let Some(page) = capture_page(mirror).await {
-                    let futures = freebsd_regex
-                        .captures_iter(&page)
+                if let Some(links) = list_links_matching(mirror, &freebsd_regex).await {
+                    let mut futures = links
+                        .iter()
+                        .filter_map(|link| freebsd_regex.captures(link))
                         .flat_map(|c| {
                             let release = c[1].to_string();
                             let vm_image_release = release.clone();
@@ -40,32 +90,25 @@ impl Distro for FreeBSD {
                                 format!("https://download.freebsd.org/ftp/releases/VM-IMAGES/{release}-RELEASE/{arch}/Latest/")
                             };
 
-                            let normal_editions = tokio::spawn(async move {
+                            let normal_editions = spawn(async move {
                                 let checksum_url = format!("{mirror}ISO-IMAGES/{release}/CHECKSUM.SHA256-FreeBSD-{release}-RELEASE-{denom}");
-                                let mut checksums = ChecksumSeparation::Sha256Regex.build(&checksum_url).await;
+                                let mut checksums = Bsd(ChecksumAlgorithm::Sha256).fetch(&checksum_url).await;
                                 FREEBSD_EDITIONS
                                     .iter()
                                     .map(|edition| {
-                                        let iso = format!("FreeBSD-{release}-RELEASE-{denom}-{edition}.iso.xz");
-                                        let checksum = checksums.as_mut().and_then(|cs| cs.remove(&iso));
-                                        let url = format!("{mirror}ISO-IMAGES/{release}/{iso}");
-                                        Config {
-                                            guest_os: GuestOS::FreeBSD,
-                                            iso: Some(vec![Source::Web(WebSource::new(url, checksum, Some(ArchiveFormat::Xz), None))]),
-                                            release: release.clone(),
-                                            edition: Some(edition.to_string()),
-                                            arch: arch.clone(),
-                                            ..Default::default()
-                                        }
+                                        let image = format!("FreeBSD-{release}-RELEASE-{denom}-{}.xz", freebsd_image(*edition));
+                                        let checksum = checksums.as_mut().and_then(|cs| cs.remove(&image));
+                                        let url = format!("{mirror}ISO-IMAGES/{release}/{image}");
+                                        freebsd_config(release.clone(), *edition, arch.clone(), url, checksum)
                                     })
                                     .collect::<Vec<Config>>()
                             });
 
-                            let vm_image = tokio::spawn(async move {
+                            let vm_image = spawn(async move {
                                 let iso = format!("FreeBSD-{vm_image_release}-RELEASE-{denom}.qcow2.xz");
                                 let checksum_url = format!("{vm_image_mirror}CHECKSUM.SHA256");
-                                let checksum = ChecksumSeparation::Sha256Regex
-                                    .build(&checksum_url)
+                                let checksum = Bsd(ChecksumAlgorithm::Sha256)
+                                    .fetch(&checksum_url)
                                     .await
                                     .and_then(|mut cs| cs.remove(&iso));
                                 let url = vm_image_mirror + &iso;
@@ -77,7 +120,7 @@ impl Distro for FreeBSD {
                                         ..Default::default()
                                     }]),
                                     release: vm_image_release,
-                                    edition: Some("vm-image".to_string()),
+                                    edition: Some(BsdEdition::VmImage.to_string()),
                                     arch: arch.clone(),
                                     ..Default::default()
                                 }]
@@ -85,17 +128,95 @@ impl Distro for FreeBSD {
                             [normal_editions, vm_image]
                         })
                         .collect::<Vec<_>>();
+                    if INCLUDE_SNAPSHOTS.load(Ordering::Relaxed) {
+                        futures.push(spawn(freebsd_snapshots(mirror, denom, arch.clone())));
+                    }
                     Some(join_futures!(futures))
                 } else {
-                    log::warn!("Failed to fetch FreeBSD {arch} releases");
+                    tracing::warn!("Failed to fetch FreeBSD {arch} releases");
                     None
                 }
             }
         });
-        Some(join_futures!(futures, 4))
+        Ok(join_futures!(futures, 4))
+    }
+}
+
+/// Suffix of an edition's image. Memstick images are written to a USB drive, rather than being booted as an optical disc
+fn freebsd_image(edition: BsdEdition) -> &'static str {
+    match edition {
+        BsdEdition::Minimal => "bootonly.iso",
+        BsdEdition::Disc1 => "disc1.iso",
+        BsdEdition::Dvd1 => "dvd1.iso",
+        BsdEdition::Memstick => "memstick.img",
+        BsdEdition::VmImage => unreachable!("FreeBSD's VM images are published separately"),
+    }
+}
+
+fn freebsd_config(release: String, edition: BsdEdition, arch: Arch, url: String, checksum: Option<String>) -> Config {
+    let source = vec![Source::Web(WebSource::new(url, checksum, Some(ArchiveFormat::Xz), None))];
+    let (iso, img) = if edition == BsdEdition::Memstick { (None, Some(source)) } else { (Some(source), None) };
+    Config {
+        guest_os: GuestOS::FreeBSD,
+        iso,
+        img,
+        release,
+        edition: Some(edition.to_string()),
+        arch,
+        ..Default::default()
     }
 }
 
+/// Snapshots are rebuilt weekly from each STABLE branch and CURRENT, their releases are named after the branch, e.g. 15.0-CURRENT
+async fn freebsd_snapshots(mirror: &'static str, denom: &'static str, arch: Arch) -> Vec<Config> {
+    let mirror = mirror.replace("/releases/", "/snapshots/") + "ISO-IMAGES/";
+    let version_regex = Regex::new(r"^\d+\.\d+/$").unwrap();
+    let Some(versions) = list_links_matching(&mirror, &version_regex).await else {
+        tracing::warn!("Failed to fetch FreeBSD {arch} snapshots");
+        return Vec::new();
+    };
+
+    let futures = versions.into_iter().map(|version| {
+        let mirror = format!("{mirror}{version}");
+        let version = version.trim_end_matches('/').to_string();
+        let arch = arch.clone();
+        async move {
+            // Snapshots are named FreeBSD-15.0-CURRENT-amd64-20241010-ab12cd34ef56-272000-disc1.iso.xz
+            let build_regex = Regex::new(&format!(
+                r"^(FreeBSD-{}-(CURRENT|STABLE)-{}-(\d{{8}})-[0-9a-f]+-\d+)-disc1\.iso\.xz$",
+                regex::escape(&version),
+                regex::escape(denom)
+            ))
+            .unwrap();
+            let links = list_links_matching(&mirror, &build_regex).await?;
+            // Older builds are sometimes still present, only the newest is kept
+            let (build, branch) = links
+                .iter()
+                .filter_map(|link| build_regex.captures(link))
+                .map(|c| c.extract())
+                .max_by_key(|(_, [_, _, date])| *date)
+                .map(|(_, [build, branch, _])| (build.to_string(), branch.to_string()))?;
+
+            let mut checksums = Bsd(ChecksumAlgorithm::Sha256)
+                .fetch(&format!("{mirror}CHECKSUM.SHA256-{build}"))
+                .await;
+            let release = format!("{version}-{branch}");
+            Some(
+                FREEBSD_SNAPSHOT_EDITIONS
+                    .iter()
+                    .map(|edition| {
+                        let image = format!("{build}-{}.xz", freebsd_image(*edition));
+                        let checksum = checksums.as_mut().and_then(|cs| cs.remove(&image));
+                        freebsd_config(release.clone(), *edition, arch.clone(), format!("{mirror}{image}"), checksum)
+                    })
+                    .collect::<Vec<Config>>(),
+            )
+        }
+    });
+
+    join_futures!(futures, 2)
+}
+
 const DRAGONFLYBSD_MIRROR: &str = "https://mirror-master.dragonflybsd.org/iso-images/";
 
 pub struct DragonFlyBSD;
@@ -105,11 +226,13 @@ impl Distro for DragonFlyBSD {
     const HOMEPAGE: Option<&'static str> = Some("https://www.dragonflybsd.org/");
     const DESCRIPTION: Option<&'static str> =
         Some("Provides an opportunity for the BSD base to grow in an entirely different direction from the one taken in the FreeBSD, NetBSD, and OpenBSD series.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let mirror_html = capture_page(DRAGONFLYBSD_MIRROR).await?;
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Md5;
+    const RELEASE_LIMIT: usize = 4;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let mirror_html = capture_page(DRAGONFLYBSD_MIRROR).await.or_network_error(DRAGONFLYBSD_MIRROR)?;
         let iso_regex = Regex::new(r#"href="(dfly-x86_64-([0-9.]+)_REL.iso.bz2)""#).unwrap();
-        let mut checksums = ChecksumSeparation::Md5Regex
-            .build(&(DRAGONFLYBSD_MIRROR.to_string() + "md5.txt"))
+        let mut checksums = Bsd(ChecksumAlgorithm::Md5)
+            .fetch(&(DRAGONFLYBSD_MIRROR.to_string() + "md5.txt"))
             .await;
 
         let mut releases = iso_regex.captures_iter(&mirror_html).collect::<Vec<_>>();
@@ -125,24 +248,40 @@ impl Distro for DragonFlyBSD {
             }
         });
 
-        releases
+        Ok(releases
             .into_iter()
-            .take(4)
-            .map(|c| {
-                let iso = &c[1];
+            .take(Self::release_limit())
+            .flat_map(|c| {
                 let release = c[2].to_string();
-                let checksum = checksums.as_mut().and_then(|cs| cs.remove(iso));
-                let url = DRAGONFLYBSD_MIRROR.to_string() + iso;
-
-                Config {
-                    guest_os: GuestOS::DragonFlyBSD,
-                    iso: Some(vec![Source::Web(WebSource::new(url, checksum, Some(ArchiveFormat::Bz2), None))]),
-                    release,
-                    ..Default::default()
-                }
+                // USB images are published alongside most ISOs, under the same name
+                let img = c[1].replace(".iso.", ".img.");
+                let img = mirror_html
+                    .contains(&format!("href=\"{img}\""))
+                    .then_some((BsdEdition::Memstick, img));
+                [Some((BsdEdition::Disc1, c[1].to_string())), img]
+                    .into_iter()
+                    .flatten()
+                    .map(|(edition, image)| {
+                        let checksum = checksums.as_mut().and_then(|cs| cs.remove(&image));
+                        let source = vec![Source::Web(WebSource::new(
+                            DRAGONFLYBSD_MIRROR.to_string() + &image,
+                            checksum,
+                            Some(ArchiveFormat::Bz2),
+                            None,
+                        ))];
+                        let (iso, img) = if edition == BsdEdition::Memstick { (None, Some(source)) } else { (Some(source), None) };
+                        Config {
+                            guest_os: GuestOS::DragonFlyBSD,
+                            iso,
+                            img,
+                            release: release.clone(),
+                            edition: Some(edition.to_string()),
+                            ..Default::default()
+                        }
+                    })
+                    .collect::<Vec<Config>>()
             })
-            .collect::<Vec<Config>>()
-            .into()
+            .collect::<Vec<Config>>())
     }
 }
 
@@ -154,8 +293,9 @@ impl Distro for GhostBSD {
     const PRETTY_NAME: &'static str = "GhostBSD";
     const HOMEPAGE: Option<&'static str> = Some("https://www.ghostbsd.org/");
     const DESCRIPTION: Option<&'static str> = Some("Simple, elegant desktop BSD Operating System.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let release_html = capture_page(GHOSTBSD_MIRROR).await?;
+    const RELEASE_LIMIT: usize = 4;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let release_html = capture_page(GHOSTBSD_MIRROR).await.or_network_error(GHOSTBSD_MIRROR)?;
         let release_regex = Regex::new(r#"href="(latest|[\d\.]+)\/""#).unwrap();
         let iso_regex = Arc::new(Regex::new(r#"href="(GhostBSD-[\d\.]+(-[\w]+)?.iso)""#).unwrap());
 
@@ -165,7 +305,7 @@ impl Distro for GhostBSD {
             .collect::<Vec<_>>();
         releases.reverse();
 
-        let futures = releases.into_iter().take(4).map(|(release, mirror)| {
+        let futures = releases.into_iter().take(Self::release_limit()).map(|(release, mirror)| {
             let iso_regex = iso_regex.clone();
 
             async move {
@@ -174,6 +314,7 @@ impl Distro for GhostBSD {
                     .captures_iter(&iso_html)
                     .map(|c| {
                         let release = release.clone();
+                        // GhostBSD's ISOs are live DVDs published for each desktop, which remain the edition so existing configs keep their names
                         let edition = match c.get(2) {
                             Some(edition) => edition.as_str()[1..].to_string(),
                             None => "MATE".to_string(),
@@ -202,6 +343,158 @@ impl Distro for GhostBSD {
             }
         });
 
-        Some(join_futures!(futures, 2))
+        Ok(join_futures!(futures, 2))
+    }
+}
+
+const OPENBSD_MIRROR: &str = "https://cdn.openbsd.org/pub/OpenBSD/";
+
+pub struct OpenBSD;
+impl Distro for OpenBSD {
+    const NAME: &'static str = "openbsd";
+    const PRETTY_NAME: &'static str = "OpenBSD";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.openbsd.org/");
+    const DESCRIPTION: Option<&'static str> =
+        Some("Free, multi-platform 4.4BSD-based UNIX-like operating system. Emphasizes portability, standardization, correctness, proactive security and integrated cryptography.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let release_html = capture_page(OPENBSD_MIRROR).await.or_network_error(OPENBSD_MIRROR)?;
+        let release_regex = Regex::new(r#"href="(\d+\.\d+)/""#).unwrap();
+
+        let mut releases = release_regex
+            .captures_iter(&release_html)
+            .map(|c| c[1].to_string())
+            .collect::<Vec<_>>();
+        releases.sort_by_key(|r| r.split('.').map(|n| n.parse::<u32>().unwrap_or_default()).collect::<Vec<u32>>());
+        releases.reverse();
+
+        let futures = releases.into_iter().take(Self::release_limit()).flat_map(|release| {
+            [(Arch::x86_64, "amd64"), (Arch::aarch64, "arm64"), (Arch::riscv64, "riscv64")]
+                .into_iter()
+                .map(move |(arch, arch_str)| {
+                    let mirror = format!("{OPENBSD_MIRROR}{release}/{arch_str}/");
+                    let release = release.clone();
+                    async move {
+                        let checksum_url = format!("{mirror}SHA256");
+                        let mut checksums = Bsd(ChecksumAlgorithm::Sha256).fetch(&checksum_url).await?;
+                        let short_release = release.replace('.', "");
+                        // SHA256.sig holds the same checksums, signed with the base key of the release
+                        let signature = Signature::signify(
+                            format!("{mirror}SHA256.sig"),
+                            ChecksumFileFormat::Bsd,
+                            format!("openbsd-{short_release}-base"),
+                        );
+                        // Only x86_64 has an install ISO, other architectures boot an installer disk image
+                        let image = match arch {
+                            Arch::x86_64 => format!("install{short_release}.iso"),
+                            _ => format!("install{short_release}.img"),
+                        };
+                        let checksum = checksums.remove(&image);
+                        let url = mirror + &image;
+                        SourceMetadata::attach(&url, |m| m.signature = Some(signature));
+                        let source = Source::Web(WebSource::new(url, checksum, None, None));
+                        let mut config = Config {
+                            guest_os: GuestOS::GenericBSD,
+                            release,
+                            arch,
+                            ..Default::default()
+                        };
+                        match config.arch {
+                            Arch::x86_64 => config.iso = Some(vec![source]),
+                            _ => config.img = Some(vec![source]),
+                        }
+                        Some(config)
+                    }
+                })
+                .collect::<Vec<_>>()
+        });
+
+        Ok(join_futures!(futures, 1))
+    }
+}
+
+const NETBSD_MIRROR: &str = "https://cdn.netbsd.org/pub/NetBSD/";
+
+pub struct NetBSD;
+impl Distro for NetBSD {
+    const NAME: &'static str = "netbsd";
+    const PRETTY_NAME: &'static str = "NetBSD";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.netbsd.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Free, fast, secure, and highly portable Unix-like Open Source operating system. It is available for a wide range of platforms.");
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Sha512;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let iso_mirror = format!("{NETBSD_MIRROR}iso/");
+        let release_html = capture_page(&iso_mirror).await.or_network_error(&iso_mirror)?;
+        let release_regex = Regex::new(r#"href="(\d+\.\d+)/""#).unwrap();
+
+        let mut releases = release_regex
+            .captures_iter(&release_html)
+            .map(|c| c[1].to_string())
+            .collect::<Vec<_>>();
+        releases.sort_by_key(|r| r.split('.').map(|n| n.parse::<u32>().unwrap_or_default()).collect::<Vec<u32>>());
+        releases.reverse();
+
+        let futures = releases.into_iter().take(Self::release_limit()).flat_map(|release| {
+            let iso_release = release.clone();
+            let iso_config = spawn(async move {
+                let mirror = format!("{NETBSD_MIRROR}iso/{iso_release}/");
+                let iso = format!("NetBSD-{iso_release}-amd64.iso");
+                let checksum = Bsd(ChecksumAlgorithm::Sha512)
+                    .fetch(&format!("{mirror}SHA512"))
+                    .await
+                    .and_then(|mut cs| cs.remove(&iso));
+                Some(Config {
+                    guest_os: GuestOS::GenericBSD,
+                    release: iso_release,
+                    edition: Some(BsdEdition::Disc1.to_string()),
+                    iso: Some(vec![Source::Web(WebSource::new(mirror + &iso, checksum, None, None))]),
+                    ..Default::default()
+                })
+            });
+
+            // The boot-only CD installs NetBSD's sets, the tarballs making up the system, from a mirror rather than from the disc
+            let minimal_release = release.clone();
+            let minimal_config = spawn(async move {
+                let mirror = format!("{NETBSD_MIRROR}NetBSD-{minimal_release}/amd64/installation/cdrom/");
+                let iso = "boot.iso";
+                let checksum = Bsd(ChecksumAlgorithm::Sha512)
+                    .fetch(&format!("{mirror}SHA512"))
+                    .await
+                    .and_then(|mut cs| cs.remove(iso));
+                // Every release's boot-only CD has the same name
+                let file_name = format!("NetBSD-{minimal_release}-amd64-boot.iso");
+                Some(Config {
+                    guest_os: GuestOS::GenericBSD,
+                    release: minimal_release,
+                    edition: Some(BsdEdition::Minimal.to_string()),
+                    iso: Some(vec![Source::Web(WebSource::new(mirror + iso, checksum, None, Some(file_name)))]),
+                    ..Default::default()
+                })
+            });
+
+            // aarch64 is only published as a preinstalled disk image
+            let disk_image_config = spawn(async move {
+                let mirror = format!("{NETBSD_MIRROR}NetBSD-{release}/evbarm-aarch64/binary/gzimg/");
+                let image = "arm64.img.gz";
+                let checksum = Bsd(ChecksumAlgorithm::Sha512)
+                    .fetch(&format!("{mirror}SHA512"))
+                    .await
+                    .and_then(|mut cs| cs.remove(image));
+                Some(Config {
+                    guest_os: GuestOS::GenericBSD,
+                    release,
+                    edition: Some(BsdEdition::VmImage.to_string()),
+                    arch: Arch::aarch64,
+                    disk_images: Some(vec![Disk {
+                        source: Source::Web(WebSource::new(mirror + image, checksum, Some(ArchiveFormat::Gz), None)),
+                        format: DiskFormat::Raw,
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                })
+            });
+            [iso_config, minimal_config, disk_image_config]
+        });
+
+        Ok(join_futures!(futures, 2))
     }
 }
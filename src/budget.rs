@@ -0,0 +1,121 @@
+use crate::edition_filter::glob_matches;
+use quickget_ci::store_data::{insert_metadata, Config, ConfigMetadata, OS};
+use serde::{Deserialize, Serialize};
+use std::{fs::File, path::Path};
+
+/// Limits on the size of the published dataset, and how it's brought within them.
+/// The newest stable release of each edition and architecture is never removed
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Budget {
+    /// Number of distinct releases kept for each OS, newest first
+    max_releases: Option<usize>,
+    /// Maximum size of the minified output, in bytes
+    max_bytes: Option<usize>,
+    /// Glob patterns of editions to remove while the output exceeds `max_bytes`, applied one at a time in order
+    prunable_editions: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct PrunedConfig {
+    os: String,
+    release: String,
+    edition: Option<String>,
+    arch: String,
+    rule: &'static str,
+}
+
+/// Reads the budget from a JSON file. Without one, nothing is pruned
+pub fn load(path: Option<&Path>) -> Option<Budget> {
+    let Some(path) = path else {
+        return Some(Budget::default());
+    };
+    let data = std::fs::read_to_string(path)
+        .inspect_err(|e| tracing::error!("Unable to read {}: {e}", path.display()))
+        .ok()?;
+    serde_json::from_str(&data)
+        .inspect_err(|e| tracing::error!("Unable to parse {}: {e}", path.display()))
+        .ok()
+}
+
+/// Prunes configs until the dataset is within budget, then writes a summary of every removed config to `summary`.
+/// Distros must already be sorted, so the same input always prunes the same configs
+pub fn apply(distros: &mut [OS], budget: &Budget, summary: &str) {
+    let mut pruned = Vec::new();
+    if let Some(max_releases) = budget.max_releases {
+        for os in distros.iter_mut() {
+            let mut kept = Vec::new();
+            prune(os, &mut pruned, "max_releases", |config| {
+                if kept.contains(&config.release) {
+                    return false;
+                }
+                if kept.len() < max_releases {
+                    kept.push(config.release.clone());
+                    return false;
+                }
+                true
+            });
+        }
+    }
+    if let Some(max_bytes) = budget.max_bytes {
+        for pattern in &budget.prunable_editions {
+            let size = output_size(distros);
+            if size <= max_bytes {
+                break;
+            }
+            tracing::info!("Output is {size} bytes, exceeding the budget of {max_bytes}. Removing editions matching {pattern}");
+            for os in distros.iter_mut() {
+                // An OS is never emptied, its remaining configs are kept even if they match
+                let remaining = os
+                    .releases
+                    .iter()
+                    .filter(|config| !config.edition.as_deref().is_some_and(|edition| glob_matches(pattern, edition)))
+                    .count();
+                if remaining > 0 {
+                    prune(os, &mut pruned, "prunable_editions", |config| {
+                        config.edition.as_deref().is_some_and(|edition| glob_matches(pattern, edition))
+                    });
+                }
+            }
+        }
+        let size = output_size(distros);
+        if size > max_bytes {
+            tracing::warn!("Output is {size} bytes after pruning, exceeding the budget of {max_bytes}");
+        }
+    }
+
+    if pruned.is_empty() {
+        return;
+    }
+    tracing::info!("Pruned {} configs to stay within budget", pruned.len());
+    let result = File::create(summary)
+        .map_err(|e| e.to_string())
+        .and_then(|file| serde_json::to_writer_pretty(file, &pruned).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        tracing::error!("Failed to write {summary}: {e}");
+    }
+}
+
+/// Removes configs for which `remove` returns true, except the newest stable release of each edition and architecture
+fn prune(os: &mut OS, pruned: &mut Vec<PrunedConfig>, rule: &'static str, mut remove: impl FnMut(&Config) -> bool) {
+    let name = &os.name;
+    os.releases.retain(|config| {
+        if !remove(config) || ConfigMetadata::is_latest_stable(config) {
+            return true;
+        }
+        pruned.push(PrunedConfig {
+            os: name.clone(),
+            release: config.release.clone(),
+            edition: config.edition.clone(),
+            arch: config.arch.to_string(),
+            rule,
+        });
+        false
+    });
+}
+
+fn output_size(distros: &[OS]) -> usize {
+    let mut output = serde_json::to_value(distros).unwrap();
+    insert_metadata(&mut output);
+    serde_json::to_string(&output).unwrap().len()
+}
@@ -0,0 +1,202 @@
+use crate::store_data::{Config, ConfigMetadata, SourceMetadata};
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    future::Future,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+static CACHE: OnceCell<Cache> = OnceCell::new();
+/// Pages which no distro has read for this long are dropped when the cache is saved
+const PAGE_MAX_AGE: u64 = 30 * 24 * 60 * 60;
+
+/// Data kept from previous runs. Pages are requested conditionally, allowing mirrors to skip sending pages which haven't changed,
+/// and a distro whose scraper would only read unchanged pages reuses its previous configs. Their URLs are still validated on every run
+pub struct Cache {
+    directory: PathBuf,
+    /// SHA-256 of the options configs are generated with, which entries generated under other options don't match
+    options: String,
+    pages: Mutex<HashMap<String, CachedPage>>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CachedPage {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub body: String,
+    /// UNIX timestamp of the last run which read the page
+    #[serde(default)]
+    pub used_at: u64,
+}
+
+/// A distro's configs as its scraper returned them, alongside the pages it read to produce them
+#[derive(Serialize, Deserialize)]
+struct CachedDistro {
+    /// SHA-256 of the options the configs were generated with
+    #[serde(default)]
+    options: String,
+    /// SHA-256 of each page's contents, keyed by its URL
+    pages: BTreeMap<String, String>,
+    configs: Vec<Config>,
+    config_metadata: HashMap<String, ConfigMetadata>,
+    source_metadata: HashMap<String, SourceMetadata>,
+}
+
+/// The pages read while generating a distro. Anything which can't be compared against a later run, such as a failed request,
+/// makes the scrape opaque, and its configs aren't reused
+#[derive(Default)]
+pub struct PagesRead {
+    pages: BTreeMap<String, String>,
+    opaque: bool,
+}
+
+tokio::task_local! {
+    static PAGES_READ: Arc<Mutex<PagesRead>>;
+}
+
+/// Configs are only reused by runs whose `options` serialize identically to those of the run which generated them
+pub fn init(directory: PathBuf, options: &impl Serialize) {
+    if let Err(e) = std::fs::create_dir_all(directory.join("distros")) {
+        tracing::error!("Failed to create cache directory {}: {e}", directory.display());
+        return;
+    }
+    let pages = File::open(directory.join("pages.json"))
+        .ok()
+        .and_then(|file| serde_json::from_reader(file).ok())
+        .unwrap_or_default();
+    let options = content_hash(&serde_json::to_string(options).unwrap_or_default());
+    let _ = CACHE.set(Cache {
+        directory,
+        options,
+        pages: Mutex::new(pages),
+    });
+}
+
+pub fn get() -> Option<&'static Cache> {
+    CACHE.get()
+}
+
+impl Cache {
+    pub fn page(&self, url: &str) -> Option<CachedPage> {
+        let mut pages = self.pages.lock().unwrap();
+        let page = pages.get_mut(url)?;
+        page.used_at = now();
+        Some(page.clone())
+    }
+    pub fn store_page(&self, url: &str, mut page: CachedPage) {
+        page.used_at = now();
+        self.pages.lock().unwrap().insert(url.to_string(), page);
+    }
+    /// Returns the configs the distro's scraper produced in a previous run, as long as it was made with the same options and every page it read is unchanged.
+    /// The metadata attached to them is restored alongside them
+    pub async fn previous_configs(&self, name: &str) -> Option<Vec<Config>> {
+        let file = File::open(self.distro_path(name)).ok()?;
+        let cached: CachedDistro = serde_json::from_reader(file).ok()?;
+        if cached.options != self.options {
+            tracing::debug!("Cached configs for {name} were generated with different options");
+            return None;
+        }
+        // Pages with an ETag or Last-Modified header are requested conditionally, the rest have to be downloaded to compare them
+        for (url, hash) in &cached.pages {
+            let page = crate::utils::capture_large_page(url).await?;
+            if content_hash(&page) != *hash {
+                tracing::debug!("{url} has changed since the previous run");
+                return None;
+            }
+        }
+        crate::store_data::restore_metadata(cached.config_metadata, cached.source_metadata);
+        Some(cached.configs)
+    }
+    pub fn store_configs(&self, name: &str, pages: PagesRead, configs: &[Config]) {
+        let path = self.distro_path(name);
+        if pages.opaque || pages.pages.is_empty() {
+            // An outdated entry would otherwise be reused once the scraper's pages are back to how they were
+            let _ = std::fs::remove_file(path);
+            return;
+        }
+        let (config_metadata, source_metadata) = crate::store_data::metadata_of(configs);
+        let cached = CachedDistro {
+            options: self.options.clone(),
+            pages: pages.pages,
+            configs: configs.to_vec(),
+            config_metadata,
+            source_metadata,
+        };
+        let result = File::create(path)
+            .map_err(|e| e.to_string())
+            .and_then(|file| serde_json::to_writer(file, &cached).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            tracing::warn!("Failed to write cache entry for {name}: {e}");
+        }
+    }
+    /// Pages are only dropped once they're old, as a run which only generates some distros still leaves the rest's pages in place
+    pub fn save(&self) {
+        let mut pages = self.pages.lock().unwrap();
+        let now = now();
+        pages.retain(|_, page| now.saturating_sub(page.used_at) < PAGE_MAX_AGE);
+        match File::create(self.directory.join("pages.json")) {
+            Ok(file) => {
+                if let Err(e) = serde_json::to_writer(file, &*pages) {
+                    tracing::error!("Failed to save page cache: {e}");
+                }
+            }
+            Err(e) => tracing::error!("Failed to save page cache: {e}"),
+        }
+    }
+    fn distro_path(&self, name: &str) -> PathBuf {
+        self.directory.join("distros").join(format!("{name}.json"))
+    }
+}
+
+/// Runs a distro's scraper, returning its output alongside every page it read
+pub async fn track_pages<F: Future>(future: F) -> (F::Output, PagesRead) {
+    let pages = Arc::new(Mutex::new(PagesRead::default()));
+    let output = PAGES_READ.scope(pages.clone(), future).await;
+    let pages = std::mem::take(&mut *pages.lock().unwrap());
+    (output, pages)
+}
+
+/// Wraps a future which is about to be spawned, so pages it reads are still attributed to the distro being generated
+pub(crate) fn inherit_pages<F: Future>(future: F) -> impl Future<Output = F::Output> {
+    let pages = PAGES_READ.try_with(Arc::clone).ok();
+    async move {
+        match pages {
+            Some(pages) => PAGES_READ.scope(pages, future).await,
+            None => future.await,
+        }
+    }
+}
+
+/// Records a page read by the distro currently being generated. A page which couldn't be read makes the scrape opaque
+pub(crate) fn record_page(url: &str, body: Option<&str>) {
+    let _ = PAGES_READ.try_with(|pages| {
+        let mut pages = pages.lock().unwrap();
+        match body {
+            Some(body) => {
+                pages.pages.insert(url.to_string(), content_hash(body));
+            }
+            None => pages.opaque = true,
+        }
+    });
+}
+
+/// Records a request whose response can't be compared against a later run, such as a POST or a request with custom headers
+pub(crate) fn record_opaque_request() {
+    let _ = PAGES_READ.try_with(|pages| pages.lock().unwrap().opaque = true);
+}
+
+fn content_hash(body: &str) -> String {
+    format!("{:x}", Sha256::digest(body.as_bytes()))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
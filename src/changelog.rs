@@ -0,0 +1,52 @@
+use crate::diff::{self, Change};
+use serde_json::Value;
+use std::fmt::Write;
+
+/// Compares the fresh output against the currently published dataset, writing the differences as markdown for release announcements.
+/// Nothing is written if the published dataset can't be parsed, as every OS would otherwise be listed as new
+pub fn write_changelog(published: &str, output: &Value, filename: &str) {
+    let published = serde_json::from_str::<Value>(published)
+        .ok()
+        .and_then(|p| diff::releases_by_os(&p));
+    let (Some(published), Some(output)) = (published, diff::releases_by_os(output)) else {
+        tracing::warn!("Unable to parse the published dataset, no changelog will be written");
+        return;
+    };
+
+    let (mut new_os, mut new_releases, mut removed_releases, mut checksum_changes) = (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    for change in diff::compare(&published, &output) {
+        match change {
+            Change::AddedOS { os, releases } => new_os.push(format!("- {os} ({releases} releases)")),
+            Change::AddedRelease { os, release } => new_releases.push(format!("- {os}: {release}")),
+            Change::RemovedOS { os, releases } => removed_releases.push(format!("- {os}: every release ({releases})")),
+            Change::RemovedRelease { os, release } => removed_releases.push(format!("- {os}: {release}")),
+            Change::ChecksumChanged { os, release, url, old, new } => checksum_changes.push(format!(
+                "- {os} {release}: `{url}` changed from `{}` to `{}`",
+                old.as_deref().unwrap_or("none"),
+                new.as_deref().unwrap_or("none")
+            )),
+            // URLs move between mirrors regularly, which isn't worth announcing
+            Change::AddedUrl { .. } | Change::RemovedUrl { .. } => {}
+        }
+    }
+
+    let mut changelog = String::from("# Changes in this run\n");
+    let sections = [
+        ("New operating systems", new_os),
+        ("New releases", new_releases),
+        ("Removed releases", removed_releases),
+        ("Checksum changes", checksum_changes),
+    ];
+    if sections.iter().all(|(_, lines)| lines.is_empty()) {
+        changelog.push_str("\nNothing changed since the published data.\n");
+    }
+    for (heading, lines) in sections {
+        if lines.is_empty() {
+            continue;
+        }
+        let _ = write!(changelog, "\n## {heading}\n\n{}\n", lines.join("\n"));
+    }
+    if let Err(e) = std::fs::write(filename, changelog) {
+        tracing::error!("Failed to write {filename}: {e}");
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{
+    store_data::{ChecksumAlgorithm, ChecksumFileFormat},
+    utils::capture_page,
+};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::{collections::HashMap, sync::Arc};
+
+#[allow(async_fn_in_trait)]
+pub trait ChecksumFormat {
+    /// Reads every checksum within the document, keyed by the name of the file it belongs to
+    fn parse(&self, data: &str) -> HashMap<String, String>;
+    async fn fetch(&self, url: &str) -> Option<HashMap<String, String>>
+    where
+        Self: Sized,
+    {
+        let data = capture_page(url).await?;
+        Some(self.parse(&data))
+    }
+}
+
+/// One checksum per line, followed by the file name, as written by sha256sum and similar tools
+pub struct Whitespace;
+
+impl ChecksumFormat for Whitespace {
+    fn parse(&self, data: &str) -> HashMap<String, String> {
+        data.lines()
+            .filter_map(|l| l.split_once(' '))
+            // An asterisk before the file name denotes it was read in binary mode
+            .map(|(hash, file)| (file.trim().trim_start_matches('*').to_string(), hash.trim().to_string()))
+            .collect()
+    }
+}
+
+static BSD_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^(MD5|SHA256|SHA512) \(([^)]+)\) = ([0-9a-fA-F]+)").unwrap());
+
+/// Lines formatted as `SHA256 (file) = checksum`, as written by BSD tools. Files may list several algorithms, only the given one is read
+pub struct Bsd(pub ChecksumAlgorithm);
+
+impl ChecksumFormat for Bsd {
+    fn parse(&self, data: &str) -> HashMap<String, String> {
+        let tag = match self.0 {
+            ChecksumAlgorithm::Md5 => "MD5",
+            ChecksumAlgorithm::Sha256 => "SHA256",
+            ChecksumAlgorithm::Sha512 => "SHA512",
+        };
+        BSD_REGEX
+            .captures_iter(data)
+            .filter(|c| &c[1] == tag)
+            .map(|c| (c[2].to_string(), c[3].to_string()))
+            .collect()
+    }
+}
+
+/// A regex capturing the file name and checksum in the given groups, for formats which don't fit any other
+#[derive(Clone)]
+pub struct CustomRegex(pub Arc<Regex>, pub usize, pub usize);
+
+impl ChecksumFormat for CustomRegex {
+    fn parse(&self, data: &str) -> HashMap<String, String> {
+        let Self(regex, file_group, checksum_group) = self;
+        regex
+            .captures_iter(data)
+            .map(|c| (c[*file_group].to_string(), c[*checksum_group].to_string()))
+            .collect()
+    }
+}
+
+/// A file published alongside a single source, such as `image.iso.sha256`. It holds only the checksum,
+/// optionally followed by the file name, which is used as the key when present
+pub struct Single;
+
+impl ChecksumFormat for Single {
+    fn parse(&self, data: &str) -> HashMap<String, String> {
+        let mut fields = data.split_whitespace();
+        let Some(checksum) = fields.next() else {
+            return HashMap::new();
+        };
+        let file = fields.next().map(|f| f.trim_start_matches('*')).unwrap_or_default();
+        HashMap::from([(file.to_string(), checksum.to_string())])
+    }
+}
+
+/// Fetches the checksum of a single source from a file such as `image.iso.sha256`
+pub async fn single_checksum(url: &str) -> Option<String> {
+    Single.fetch(url).await?.into_values().next()
+}
+
+/// A YAML list of mappings, each naming a file alongside its checksum, such as Alpine's latest-releases.yaml
+pub struct YamlList {
+    pub file_key: &'static str,
+    pub checksum_key: &'static str,
+}
+
+impl ChecksumFormat for YamlList {
+    fn parse(&self, data: &str) -> HashMap<String, String> {
+        let entries: Vec<HashMap<String, serde_yaml::Value>> = match serde_yaml::from_str(data) {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::warn!("Unable to parse YAML checksums: {e}");
+                return HashMap::new();
+            }
+        };
+        entries
+            .iter()
+            .filter_map(|entry| {
+                let file = entry.get(self.file_key)?.as_str()?;
+                let checksum = entry.get(self.checksum_key)?.as_str()?;
+                Some((file.to_string(), checksum.to_string()))
+            })
+            .collect()
+    }
+}
+
+const ALGORITHMS: [ChecksumAlgorithm; 3] = [ChecksumAlgorithm::Md5, ChecksumAlgorithm::Sha256, ChecksumAlgorithm::Sha512];
+
+/// Reads every checksum within a signed checksum file, whichever algorithm produced it, as pairs of file name and checksum
+pub fn signed_checksums(format: ChecksumFileFormat, data: &str) -> Vec<(String, String)> {
+    match format {
+        ChecksumFileFormat::Whitespace => Whitespace.parse(data).into_iter().collect(),
+        ChecksumFileFormat::Bsd => ALGORITHMS.iter().flat_map(|a| Bsd(*a).parse(data)).collect(),
+        ChecksumFileFormat::Yaml => ALGORITHMS
+            .iter()
+            .flat_map(|a| {
+                let checksum_key = match a {
+                    ChecksumAlgorithm::Md5 => "md5",
+                    ChecksumAlgorithm::Sha256 => "sha256",
+                    ChecksumAlgorithm::Sha512 => "sha512",
+                };
+                YamlList { file_key: "file", checksum_key }.parse(data)
+            })
+            .collect(),
+    }
+}
+
+/// Release notes and READMEs, often written in markdown, which list checksums among other text.
+/// Only lines containing a digest of the given algorithm alongside a file name are read
+pub struct ReleaseNotes(pub ChecksumAlgorithm);
+
+impl ChecksumFormat for ReleaseNotes {
+    fn parse(&self, data: &str) -> HashMap<String, String> {
+        let is_digest = |s: &str| s.len() == self.0.digest_length() && s.chars().all(|c| c.is_ascii_hexdigit());
+        data.lines()
+            .filter_map(|line| {
+                let fields = line
+                    .split(|c: char| c.is_whitespace() || c == '|')
+                    .map(|f| f.trim_matches(['`', '*', '_', ':', '(', ')', '[', ']']))
+                    .filter(|f| !f.is_empty())
+                    .collect::<Vec<&str>>();
+                let checksum = *fields.iter().find(|f| is_digest(f))?;
+                let file = fields.iter().find(|f| !is_digest(f) && f.contains('.') && !f.ends_with('.'))?;
+                let file = file.rsplit('/').next().unwrap_or(file);
+                Some((file.to_string(), checksum.to_string()))
+            })
+            .collect()
+    }
+}
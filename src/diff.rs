@@ -0,0 +1,218 @@
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+const SOURCE_FIELDS: [&str; 4] = ["iso", "img", "fixed_iso", "floppy"];
+
+/// Each release's web sources, mapped from URL to checksum
+pub(crate) type Releases = BTreeMap<String, BTreeMap<String, Option<String>>>;
+
+/// A difference between two generated datasets
+pub(crate) enum Change {
+    AddedOS {
+        os: String,
+        releases: usize,
+    },
+    RemovedOS {
+        os: String,
+        releases: usize,
+    },
+    AddedRelease {
+        os: String,
+        release: String,
+    },
+    RemovedRelease {
+        os: String,
+        release: String,
+    },
+    AddedUrl {
+        os: String,
+        release: String,
+        url: String,
+    },
+    RemovedUrl {
+        os: String,
+        release: String,
+        url: String,
+    },
+    ChecksumChanged {
+        os: String,
+        release: String,
+        url: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+}
+
+/// Lists every difference between two datasets, removed operating systems first, then the changes within each OS
+pub(crate) fn compare(old: &BTreeMap<String, Releases>, new: &BTreeMap<String, Releases>) -> Vec<Change> {
+    let mut changes = old
+        .iter()
+        .filter(|(os, _)| !new.contains_key(*os))
+        .map(|(os, releases)| Change::RemovedOS {
+            os: os.clone(),
+            releases: releases.len(),
+        })
+        .collect::<Vec<Change>>();
+    for (os, new_releases) in new {
+        let Some(old_releases) = old.get(os) else {
+            changes.push(Change::AddedOS {
+                os: os.clone(),
+                releases: new_releases.len(),
+            });
+            continue;
+        };
+        for release in old_releases.keys().filter(|r| !new_releases.contains_key(*r)) {
+            changes.push(Change::RemovedRelease {
+                os: os.clone(),
+                release: release.clone(),
+            });
+        }
+        for (release, new_sources) in new_releases {
+            let Some(old_sources) = old_releases.get(release) else {
+                changes.push(Change::AddedRelease {
+                    os: os.clone(),
+                    release: release.clone(),
+                });
+                continue;
+            };
+            for url in old_sources.keys().filter(|u| !new_sources.contains_key(*u)) {
+                changes.push(Change::RemovedUrl {
+                    os: os.clone(),
+                    release: release.clone(),
+                    url: url.clone(),
+                });
+            }
+            for (url, checksum) in new_sources {
+                match old_sources.get(url) {
+                    None => changes.push(Change::AddedUrl {
+                        os: os.clone(),
+                        release: release.clone(),
+                        url: url.clone(),
+                    }),
+                    Some(old_checksum) if old_checksum != checksum => changes.push(Change::ChecksumChanged {
+                        os: os.clone(),
+                        release: release.clone(),
+                        url: url.clone(),
+                        old: old_checksum.clone(),
+                        new: checksum.clone(),
+                    }),
+                    _ => {}
+                }
+            }
+        }
+    }
+    changes
+}
+
+/// Prints the differences between two generated datasets.
+/// Returns false if more than `max_removed_percent` of the previous releases are missing, which usually means a mistake rather than genuine removals.
+pub fn run(old: &Path, new: &Path, max_removed_percent: f64) -> bool {
+    let (Some(old), Some(new)) = (load(old), load(new)) else {
+        return false;
+    };
+    let (mut added, mut removed) = (0, 0);
+    let mut changed = BTreeSet::new();
+
+    for change in compare(&old, &new) {
+        match change {
+            Change::RemovedOS { os, releases } => {
+                println!("Removed OS: {os}");
+                removed += releases;
+            }
+            Change::AddedOS { os, releases } => {
+                println!("Added OS: {os}");
+                added += releases;
+            }
+            Change::RemovedRelease { os, release } => {
+                println!("{os}: removed {release}");
+                removed += 1;
+            }
+            Change::AddedRelease { os, release } => {
+                println!("{os}: added {release}");
+                added += 1;
+            }
+            Change::RemovedUrl { os, release, url } => {
+                println!("{os} {release}: removed URL {url}");
+                changed.insert((os, release));
+            }
+            Change::AddedUrl { os, release, url } => {
+                println!("{os} {release}: added URL {url}");
+                changed.insert((os, release));
+            }
+            Change::ChecksumChanged { os, release, url, old, new } => {
+                println!(
+                    "{os} {release}: checksum of {url} changed from {} to {}",
+                    old.as_deref().unwrap_or("none"),
+                    new.as_deref().unwrap_or("none")
+                );
+                changed.insert((os, release));
+            }
+        }
+    }
+
+    println!("{added} releases added, {removed} removed, {} changed", changed.len());
+    let previous = old.values().map(BTreeMap::len).sum::<usize>();
+    let removed_percent = if previous == 0 { 0.0 } else { removed as f64 * 100.0 / previous as f64 };
+    if removed_percent > max_removed_percent {
+        tracing::error!("{removed_percent:.1}% of releases were removed, exceeding the limit of {max_removed_percent}%");
+        return false;
+    }
+    true
+}
+
+pub(crate) fn load(path: &Path) -> Option<BTreeMap<String, Releases>> {
+    let data = std::fs::read_to_string(path)
+        .inspect_err(|e| tracing::error!("Unable to read {}: {e}", path.display()))
+        .ok()?;
+    let value: Value = serde_json::from_str(&data)
+        .inspect_err(|e| tracing::error!("Unable to parse {}: {e}", path.display()))
+        .ok()?;
+    releases_by_os(&value)
+}
+
+/// Maps each OS of a dataset to its releases
+pub(crate) fn releases_by_os(data: &Value) -> Option<BTreeMap<String, Releases>> {
+    // Data from before schema versioning is a bare array
+    let distros = data.get("distros").unwrap_or(data);
+
+    distros
+        .as_array()?
+        .iter()
+        .map(|os| {
+            let name = os["os"].as_str()?.to_string();
+            let releases = os["releases"]
+                .as_array()?
+                .iter()
+                .map(|r| (release_key(r), web_sources(r)))
+                .collect();
+            Some((name, releases))
+        })
+        .collect()
+}
+
+fn release_key(release: &Value) -> String {
+    let field = |name: &str| release[name].as_str();
+    let mut key = field("release").unwrap_or("latest").to_string();
+    if let Some(edition) = field("edition") {
+        key = key + " " + edition;
+    }
+    key + " " + field("arch").unwrap_or("x86_64")
+}
+
+fn web_sources(release: &Value) -> BTreeMap<String, Option<String>> {
+    let sources = SOURCE_FIELDS.iter().filter_map(|field| release[field].as_array()).flatten();
+    let disk_sources = release["disk_images"].as_array().into_iter().flatten().map(|d| &d["source"]);
+    sources
+        .chain(disk_sources)
+        .filter_map(|source| {
+            let web = &source["web"];
+            Some((
+                web["url"].as_str()?.to_string(),
+                web["checksum"].as_str().map(ToString::to_string),
+            ))
+        })
+        .collect()
+}
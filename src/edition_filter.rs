@@ -0,0 +1,94 @@
+use quickget_ci::store_data::OS;
+use serde::Deserialize;
+use std::{collections::HashMap, path::Path};
+
+/// Glob patterns selecting which editions of a distro are kept
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EditionFilter {
+    /// Only keep editions matching one of these patterns, unless empty
+    allow: Vec<String>,
+    /// Remove editions matching any of these patterns, even if they're allowed
+    deny: Vec<String>,
+}
+
+impl EditionFilter {
+    fn keeps(&self, edition: &str) -> bool {
+        (self.allow.is_empty() || self.allow.iter().any(|p| glob_matches(p, edition))) && !self.deny.iter().any(|p| glob_matches(p, edition))
+    }
+}
+
+/// Reads filters from a JSON file mapping distro names to their allow and deny lists, then adds denied editions passed on the command line
+pub fn load(path: Option<&Path>, denied: &[(String, String)]) -> Option<HashMap<String, EditionFilter>> {
+    let mut filters: HashMap<String, EditionFilter> = match path {
+        Some(path) => {
+            let data = std::fs::read_to_string(path)
+                .inspect_err(|e| tracing::error!("Unable to read {}: {e}", path.display()))
+                .ok()?;
+            serde_json::from_str(&data)
+                .inspect_err(|e| tracing::error!("Unable to parse {}: {e}", path.display()))
+                .ok()?
+        }
+        None => HashMap::new(),
+    };
+    for (distro, pattern) in denied {
+        filters.entry(distro.clone()).or_default().deny.push(pattern.clone());
+    }
+    Some(filters)
+}
+
+/// Removes releases whose editions are filtered out. Releases without an edition are always kept.
+/// Distros left without any releases are removed entirely
+pub fn apply(distros: &mut Vec<OS>, filters: &HashMap<String, EditionFilter>) {
+    if filters.is_empty() {
+        return;
+    }
+    for os in distros.iter_mut() {
+        let Some(filter) = filters.get(&os.name) else {
+            continue;
+        };
+        let before = os.releases.len();
+        os.releases
+            .retain(|r| r.edition.as_deref().is_none_or(|edition| filter.keeps(edition)));
+        let removed = before - os.releases.len();
+        if removed > 0 {
+            tracing::info!("Filtered out {removed} releases of {} by edition", os.name);
+        }
+    }
+    distros.retain(|os| {
+        let empty = os.releases.is_empty();
+        if empty {
+            tracing::warn!("Every release of {} was filtered out by edition", os.name);
+        }
+        !empty
+    });
+}
+
+/// Matches `text` against a glob pattern, where `*` matches any run of characters and `?` matches a single one
+pub fn glob_matches(pattern: &str, text: &str) -> bool {
+    let (pattern, text): (Vec<char>, Vec<char>) = (pattern.chars().collect(), text.chars().collect());
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text it was matched against, to backtrack to when a later character doesn't match
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
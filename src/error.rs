@@ -0,0 +1,70 @@
+use std::fmt;
+
+/// Why a distro's configs couldn't be generated
+#[derive(Debug)]
+pub enum GenerateError {
+    /// A page, API or file the scraper depends on couldn't be fetched
+    NetworkError { url: String },
+    /// Fetched data didn't contain what the scraper expected, usually because a page's layout changed
+    ParseError { context: String },
+    /// The scraper ran, but found no releases
+    NoReleasesFound,
+}
+
+impl GenerateError {
+    /// Short name of the error, which failures are grouped by in the generation report
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::NetworkError { .. } => "network",
+            Self::ParseError { .. } => "parse",
+            Self::NoReleasesFound => "no_releases",
+        }
+    }
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NetworkError { url } => write!(f, "Unable to fetch {url}"),
+            Self::ParseError { context } => write!(f, "Unable to parse {context}"),
+            Self::NoReleasesFound => write!(f, "No releases found"),
+        }
+    }
+}
+
+impl std::error::Error for GenerateError {}
+
+impl From<serde_json::Error> for GenerateError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::ParseError { context: format!("JSON: {e}") }
+    }
+}
+
+impl From<serde_yaml::Error> for GenerateError {
+    fn from(e: serde_yaml::Error) -> Self {
+        Self::ParseError { context: format!("YAML: {e}") }
+    }
+}
+
+impl From<quick_xml::DeError> for GenerateError {
+    fn from(e: quick_xml::DeError) -> Self {
+        Self::ParseError { context: format!("XML: {e}") }
+    }
+}
+
+/// Turns missing values within a scraper into errors, so the reason it failed reaches the report
+pub trait OrGenerateError<T> {
+    /// The value is the response from `url`, which couldn't be fetched
+    fn or_network_error(self, url: &str) -> Result<T, GenerateError>;
+    /// The value was expected within fetched data, `context` describes what was being looked for
+    fn or_parse_error(self, context: &str) -> Result<T, GenerateError>;
+}
+
+impl<T> OrGenerateError<T> for Option<T> {
+    fn or_network_error(self, url: &str) -> Result<T, GenerateError> {
+        self.ok_or_else(|| GenerateError::NetworkError { url: url.to_string() })
+    }
+    fn or_parse_error(self, context: &str) -> Result<T, GenerateError> {
+        self.ok_or_else(|| GenerateError::ParseError { context: context.to_string() })
+    }
+}
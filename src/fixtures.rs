@@ -0,0 +1,89 @@
+use once_cell::sync::OnceCell;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+static MODE: OnceCell<FixtureMode> = OnceCell::new();
+
+/// Allows runs to be recorded to disk, and later replayed without any network access
+pub enum FixtureMode {
+    Record(PathBuf),
+    Replay(PathBuf),
+}
+
+pub fn init(mode: FixtureMode) {
+    if let FixtureMode::Record(directory) = &mode {
+        if let Err(e) = std::fs::create_dir_all(directory) {
+            tracing::error!("Failed to create fixture directory {}: {e}", directory.display());
+            return;
+        }
+    }
+    let _ = MODE.set(mode);
+}
+
+pub fn replaying() -> bool {
+    matches!(MODE.get(), Some(FixtureMode::Replay(_)))
+}
+
+/// Saves a response, if fixtures are being recorded
+pub fn record(key: &str, data: &str) {
+    if let Some(FixtureMode::Record(directory)) = MODE.get() {
+        if let Err(e) = std::fs::write(fixture_path(directory, key), data) {
+            tracing::warn!("Failed to record fixture for {key}: {e}");
+        }
+    }
+}
+
+/// Loads a previously recorded response, if fixtures are being replayed
+pub fn replay(key: &str) -> Option<String> {
+    let Some(FixtureMode::Replay(directory)) = MODE.get() else {
+        return None;
+    };
+    let data = std::fs::read_to_string(fixture_path(directory, key));
+    if data.is_err() {
+        tracing::warn!("No fixture recorded for {key}");
+    }
+    data.ok()
+}
+
+/// Writes a response into a fixture directory outside of a recorded run, such as fixtures kept alongside the tests in a readable form
+pub fn import(directory: &Path, key: &str, data: &str) -> std::io::Result<()> {
+    std::fs::create_dir_all(directory)?;
+    std::fs::write(fixture_path(directory, key), data)
+}
+
+/// Generates a value, such as a random session ID, which must stay the same for a replayed run to match its fixtures
+pub fn recorded_value(key: &str, generate: impl FnOnce() -> String) -> String {
+    if let Some(value) = replay(key) {
+        return value;
+    }
+    let value = generate();
+    record(key, &value);
+    value
+}
+
+pub fn record_headers(key: &str, headers: &HeaderMap) {
+    let data = headers
+        .iter()
+        .filter_map(|(name, value)| Some(format!("{name}: {}", value.to_str().ok()?)))
+        .collect::<Vec<_>>()
+        .join("\n");
+    record(key, &data);
+}
+
+pub fn replay_headers(key: &str) -> Option<HeaderMap> {
+    replay(key)?
+        .lines()
+        .map(|line| {
+            let (name, value) = line.split_once(": ")?;
+            Some((
+                HeaderName::from_bytes(name.as_bytes()).ok()?,
+                HeaderValue::from_str(value).ok()?,
+            ))
+        })
+        .collect()
+}
+
+fn fixture_path(directory: &Path, key: &str) -> PathBuf {
+    directory.join(format!("{:x}", Sha256::digest(key.as_bytes())))
+}
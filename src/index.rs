@@ -0,0 +1,47 @@
+use serde_json::{json, Value};
+use std::{fs::File, io::Write};
+
+const SOURCE_FIELDS: [&str; 5] = ["iso", "img", "fixed_iso", "floppy", "disk_images"];
+
+/// Writes one JSON object per line for every downloadable web source, so tooling can consume them without walking the nested structure
+pub fn write_index(distros: &Value, filename: &str) {
+    let mut file = match File::create(filename) {
+        Ok(file) => file,
+        Err(e) => {
+            tracing::error!("Failed to create {filename}: {e}");
+            return;
+        }
+    };
+    let releases = distros
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|os| os["releases"].as_array().into_iter().flatten().map(move |r| (&os["os"], r)));
+    for (os, release) in releases {
+        for field in SOURCE_FIELDS {
+            for source in release[field].as_array().into_iter().flatten() {
+                // Disk images wrap their source alongside the image's format
+                let web = match field {
+                    "disk_images" => &source["source"]["web"],
+                    _ => &source["web"],
+                };
+                let Some(url) = web["url"].as_str() else {
+                    continue;
+                };
+                let entry = json!({
+                    "os": os,
+                    "release": release["release"],
+                    "edition": release["edition"],
+                    "arch": release["arch"].as_str().unwrap_or("x86_64"),
+                    "type": field,
+                    "url": url,
+                    "checksum": web["checksum"],
+                });
+                if let Err(e) = writeln!(file, "{entry}") {
+                    tracing::error!("Failed to write {filename}: {e}");
+                    return;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! Generates the configuration data used by quickget to download operating systems.
+//! Global state such as the cache, fixtures and connection limits must be initialised before generating anything.
+
+pub mod appliances;
+pub mod bsd;
+pub mod cache;
+pub mod checksums;
+pub mod error;
+pub mod fixtures;
+pub mod lifecycle;
+pub mod linux;
+pub mod liveness;
+pub mod lockfile;
+pub mod macos;
+pub mod metrics;
+pub mod other;
+pub mod progress;
+pub mod report;
+pub mod schema;
+pub mod solaris;
+pub mod store_data;
+pub mod utils;
+pub mod verification;
+pub mod windows;
+
+use join_futures::join_futures;
+use std::{collections::HashSet, time::Duration};
+use store_data::{ToOS, OS};
+use tokio::spawn;
+
+/// Which distros are generated, and how long each is given
+pub struct Selection {
+    /// Only generate these distros, unless empty
+    pub only: Vec<String>,
+    pub exclude: Vec<String>,
+    pub timeout: Duration,
+}
+
+impl Default for Selection {
+    fn default() -> Self {
+        Self {
+            only: Vec::new(),
+            exclude: Vec::new(),
+            timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+impl Selection {
+    fn should_generate(&self, name: &str) -> bool {
+        if self.only.is_empty() {
+            !self.exclude.iter().any(|d| d == name)
+        } else {
+            self.only.iter().any(|d| d == name)
+        }
+    }
+}
+
+/// Generates every supported distro.
+/// Data which quickget_core's schema has no place for is merged into the serialized output by `store_data::insert_metadata`
+pub async fn generate_all() -> Vec<OS> {
+    generate(&Selection::default()).await
+}
+
+/// Generates a single distro by its name, as used in quickget's data
+pub async fn generate_one(name: &str) -> Option<OS> {
+    let selection = Selection {
+        only: vec![name.to_string()],
+        ..Default::default()
+    };
+    generate(&selection).await.into_iter().next()
+}
+
+pub async fn generate(selection: &Selection) -> Vec<OS> {
+    let futures = spawn_distros!(selection;
+        bsd::FreeBSD,
+        linux::Ubuntu,
+        linux::UbuntuServer,
+        linux::UbuntuUnity,
+        linux::Lubuntu,
+        linux::Kubuntu,
+        linux::UbuntuMATE,
+        linux::UbuntuBudgie,
+        linux::UbuntuStudio,
+        linux::UbuntuKylin,
+        linux::Edubuntu,
+        linux::Xubuntu,
+        linux::UbuntuCinnamon,
+        linux::NixOS,
+        other::NineFront,
+        linux::Alma,
+        linux::Alpine,
+        linux::AltLinux,
+        linux::AmazonLinux,
+        linux::AnduinOS,
+        linux::Antix,
+        linux::Archboot,
+        linux::Archcraft,
+        linux::Elementary,
+        linux::ArchLinux,
+        linux::ArcoLinux,
+        linux::Armbian,
+        linux::ArtixLinux,
+        linux::AstraLinux,
+        linux::AthenaOS,
+        linux::Aurora,
+        linux::BackBox,
+        linux::Batocera,
+        linux::Bazzite,
+        linux::BigLinux,
+        linux::BlendOS,
+        linux::Bluefin,
+        linux::Bodhi,
+        linux::BunsenLabs,
+        linux::CachyOS,
+        linux::CentOSStream,
+        linux::ChimeraLinux,
+        linux::ClearLinux,
+        linux::CrunchbangPlusPlus,
+        linux::Debian,
+        linux::DebianEdu,
+        linux::Deepin,
+        linux::Devuan,
+        bsd::DragonFlyBSD,
+        linux::EasyOS,
+        linux::Elive,
+        linux::EndeavourOS,
+        linux::EndlessOS,
+        linux::Fedora,
+        linux::Flatcar,
+        other::FreeDOS,
+        linux::Garuda,
+        linux::Gentoo,
+        bsd::GhostBSD,
+        bsd::NetBSD,
+        bsd::OpenBSD,
+        linux::GnomeOS,
+        linux::Guix,
+        other::Haiku,
+        linux::Kali,
+        linux::KaOS,
+        linux::KDENeon,
+        linux::LinuxLite,
+        macos::MacOS,
+        linux::Manjaro,
+        other::MenuetOS,
+        linux::MXLinux,
+        linux::NuTyX,
+        solaris::OmniOS,
+        linux::OpenEuler,
+        linux::OpenKylin,
+        solaris::OpenIndiana,
+        linux::OpenSUSEAeon,
+        linux::OpenSUSEKalpa,
+        linux::OpenSUSELeap,
+        linux::OpenSUSEMicroOS,
+        linux::OpenSUSETumbleweed,
+        appliances::OPNsense,
+        linux::OracleLinux,
+        linux::Parabola,
+        linux::Parrot,
+        linux::PCLinuxOS,
+        linux::Peppermint,
+        linux::PhotonOS,
+        linux::PopOS,
+        linux::PuppyLinux,
+        linux::PureOS,
+        appliances::ProxmoxVE,
+        linux::Q4OS,
+        linux::Qubes,
+        linux::RaspberryPiOS,
+        other::ReactOS,
+        other::RedoxOS,
+        linux::Rocky,
+        linux::RosaFresh,
+        linux::Salix,
+        linux::Slackware,
+        linux::SliTaz,
+        linux::Solus,
+        linux::SparkyLinux,
+        linux::SteamOS,
+        linux::Tails,
+        linux::TinyCore,
+        solaris::Tribblix,
+        linux::Trisquel,
+        appliances::TrueNASCore,
+        appliances::TrueNASScale,
+        linux::UnionTechOS,
+        linux::VoidLinux,
+        linux::Whonix,
+        linux::Zorin,
+        windows::Windows,
+    );
+
+    let generated = join_futures!(futures, 2, Vec<store_data::Candidates>);
+    store_data::validate(generated).await.remove_duplicates().distro_sort()
+}
+
+pub trait DistroSort {
+    /// Removes operating systems and releases which appear more than once
+    fn remove_duplicates(self) -> Self;
+    fn distro_sort(self) -> Self;
+}
+
+impl DistroSort for Vec<OS> {
+    fn remove_duplicates(mut self) -> Self {
+        // Guards against a distro being listed twice, or a scraper finding the same file through multiple pages
+        let (mut names, mut pretty_names) = (HashSet::new(), HashSet::new());
+        self.retain(|os| {
+            let unique = names.insert(os.name.clone()) & pretty_names.insert(os.pretty_name.clone());
+            if !unique {
+                tracing::warn!("Removing duplicate OS {} ({})", os.name, os.pretty_name);
+            }
+            unique
+        });
+        self.iter_mut().for_each(|os| {
+            let mut releases = HashSet::new();
+            os.releases.retain(|r| {
+                let unique = releases.insert((r.release.clone(), r.edition.clone(), r.arch.to_string()));
+                if !unique {
+                    tracing::warn!(
+                        "Removing duplicate release of {}: {} {} {}",
+                        os.name,
+                        r.release,
+                        r.edition.as_deref().unwrap_or_default(),
+                        r.arch
+                    );
+                }
+                unique
+            });
+        });
+        self
+    }
+    fn distro_sort(mut self) -> Self {
+        self.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        self.iter_mut().for_each(|d| {
+            d.releases.sort_unstable_by(|a, b| {
+                let (release_a, release_b) = (a.release.trim_start_matches('v'), b.release.trim_start_matches('v'));
+                let (mut split_a, mut split_b) = (release_a.split('.'), release_b.split('.'));
+                while let (Some(a), Some(b)) = (split_a.next(), split_b.next()) {
+                    if let (Ok(a), Ok(b)) = (a.parse::<u64>(), b.parse::<u64>()) {
+                        let comparison = b.cmp(&a);
+                        if comparison != std::cmp::Ordering::Equal {
+                            return comparison;
+                        }
+                    } else {
+                        break;
+                    }
+                }
+                b.release.cmp(&a.release).then(a.edition.cmp(&b.edition))
+            })
+        });
+        self
+    }
+}
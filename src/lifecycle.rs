@@ -0,0 +1,89 @@
+use crate::{store_data::SupportStatus, utils::capture_page};
+use serde::Deserialize;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const ENDOFLIFE_API: &str = "https://endoflife.date/api/";
+
+/// A release cycle of a product on endoflife.date, such as Ubuntu 24.04 or Debian 12
+#[derive(Deserialize)]
+pub(crate) struct Cycle {
+    cycle: StringOrNumber,
+    /// Date security updates end, or whether they already have when no date is published
+    eol: DateOrBool,
+    /// Date active support ends, after which only security updates are published
+    #[serde(default)]
+    support: Option<DateOrBool>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrNumber {
+    String(String),
+    Number(serde_json::Number),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DateOrBool {
+    Date(String),
+    Bool(bool),
+}
+
+impl DateOrBool {
+    /// Whether the date has passed, dates are formatted as YYYY-MM-DD so they're compared as text
+    fn passed(&self, today: &str) -> bool {
+        match self {
+            Self::Date(date) => date.as_str() <= today,
+            Self::Bool(passed) => *passed,
+        }
+    }
+}
+
+impl Cycle {
+    pub(crate) fn name(&self) -> String {
+        match &self.cycle {
+            StringOrNumber::String(cycle) => cycle.clone(),
+            StringOrNumber::Number(cycle) => cycle.to_string(),
+        }
+    }
+    pub(crate) fn eol_date(&self) -> Option<&str> {
+        match &self.eol {
+            DateOrBool::Date(date) => Some(date),
+            DateOrBool::Bool(_) => None,
+        }
+    }
+    pub(crate) fn status(&self, today: &str) -> SupportStatus {
+        if self.eol.passed(today) {
+            SupportStatus::Eol
+        } else if self.support.as_ref().is_some_and(|s| s.passed(today)) {
+            SupportStatus::Security
+        } else {
+            SupportStatus::Supported
+        }
+    }
+}
+
+/// Fetches the support schedule of a product, named as on endoflife.date
+pub(crate) async fn schedule(product: &str) -> Option<Vec<Cycle>> {
+    let url = format!("{ENDOFLIFE_API}{product}.json");
+    let data = capture_page(&url).await?;
+    serde_json::from_str(&data)
+        .inspect_err(|e| tracing::warn!("Unable to parse the support schedule at {url}: {e}"))
+        .ok()
+}
+
+/// Today's UTC date, formatted as YYYY-MM-DD
+pub(crate) fn today() -> String {
+    let days = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64 / 86400;
+    // Converts days since the epoch to a civil date, from Howard Hinnant's chrono-compatible date algorithms
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
@@ -1,11 +1,32 @@
 mod arch;
+mod arm;
 mod debian;
 mod fedora_redhat;
 mod independent;
+mod privacy;
+mod security;
+mod slackware;
+mod suse;
 mod ubuntu;
 
-pub(crate) use arch::{manjaro::BigLinux, ArchLinux, Archcraft, ArcoLinux, ArtixLinux, AthenaOS, BlendOS, CachyOS, EndeavourOS, Garuda};
-pub(crate) use debian::{Antix, BunsenLabs, CrunchbangPlusPlus, Debian, Devuan, EasyOS, EndlessOS};
-pub(crate) use fedora_redhat::{Alma, Bazzite, CentOSStream, Fedora};
-pub(crate) use independent::{Alpine, Batocera, ChimeraLinux, Gentoo, GnomeOS, NixOS};
-pub(crate) use ubuntu::{Bodhi, Edubuntu, Elementary, Kubuntu, Lubuntu, Ubuntu, UbuntuBudgie, UbuntuCinnamon, UbuntuKylin, UbuntuMATE, UbuntuServer, UbuntuStudio, UbuntuUnity, Xubuntu};
+pub use arch::{
+    manjaro::{BigLinux, Manjaro},
+    ArchLinux, Archboot, Archcraft, ArcoLinux, ArtixLinux, AthenaOS, BlendOS, CachyOS, EndeavourOS, Garuda, SteamOS,
+};
+pub use arm::{Armbian, RaspberryPiOS};
+pub use debian::{
+    Antix, AstraLinux, BunsenLabs, CrunchbangPlusPlus, Debian, DebianEdu, Deepin, Devuan, EasyOS, Elive, EndlessOS, MXLinux, Peppermint, PuppyLinux, PureOS, SparkyLinux, UnionTechOS, Q4OS,
+};
+pub use fedora_redhat::{include_fedora_server_editions, Alma, AmazonLinux, Aurora, Bazzite, Bluefin, CentOSStream, Fedora, OracleLinux, Rocky};
+pub use independent::{
+    Alpine, AltLinux, Batocera, ChimeraLinux, ClearLinux, Flatcar, Gentoo, GnomeOS, Guix, KaOS, NixOS, NuTyX, OpenEuler, OpenKylin, PCLinuxOS, Parabola, PhotonOS, RosaFresh, SliTaz, Solus, TinyCore,
+    VoidLinux,
+};
+pub use privacy::{Qubes, Tails, Whonix};
+pub use security::{BackBox, Kali, Parrot};
+pub use slackware::{Salix, Slackware};
+pub use suse::{OpenSUSEAeon, OpenSUSEKalpa, OpenSUSELeap, OpenSUSEMicroOS, OpenSUSETumbleweed};
+pub use ubuntu::{
+    include_devel_releases, AnduinOS, Bodhi, Edubuntu, Elementary, KDENeon, Kubuntu, LinuxLite, Lubuntu, PopOS, Trisquel, Ubuntu, UbuntuBudgie, UbuntuCinnamon, UbuntuKylin, UbuntuMATE, UbuntuServer,
+    UbuntuStudio, UbuntuUnity, Xubuntu, Zorin,
+};
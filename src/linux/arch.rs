@@ -1,15 +1,19 @@
 pub mod manjaro;
 
 use crate::{
-    store_data::{ChecksumSeparation, Config, Distro, Source, WebSource},
-    utils::{capture_page, GatherData, GithubAPI},
+    checksums::{single_checksum, ChecksumFormat, CustomRegex, Whitespace},
+    error::{GenerateError, OrGenerateError},
+    store_data::{add_latest_alias, mirrored_url, Arch, ArchiveFormat, ChecksumAlgorithm, ChecksumSource, Config, Disk, Distro, Region, Signature, Source, SourceMetadata, WebSource},
+    utils::{capture_page, list_links, list_links_matching, GatherData, GithubAPI, SourceForgeAPI},
 };
 use join_futures::join_futures;
+use quickemu::config::DiskFormat;
+use quickget_core::data_structures::BootType;
 use regex::Regex;
 use serde::Deserialize;
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
-const ARCHCRAFT_MIRROR: &str = "https://sourceforge.net/projects/archcraft/files/";
+const ARCHCRAFT_PROJECT: &str = "archcraft";
 
 pub struct Archcraft;
 impl Distro for Archcraft {
@@ -17,35 +21,49 @@ impl Distro for Archcraft {
     const PRETTY_NAME: &'static str = "Archcraft";
     const HOMEPAGE: Option<&'static str> = Some("https://archcraft.io/");
     const DESCRIPTION: Option<&'static str> = Some("Yet another minimal Linux distribution, based on Arch Linux.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let releases = capture_page(ARCHCRAFT_MIRROR).await?;
-        let releases_regex = Regex::new(r#""name":"v([^"]+)""#).unwrap();
-        let url_regex = Arc::new(Regex::new(r#""name":"archcraft-.*?-x86_64.iso".*?"download_url":"([^"]+)".*?"name":"archcraft-.*?-x86_64.iso.sha256sum".*?"download_url":"([^"]+)""#).unwrap());
-        let futures = releases_regex.captures_iter(&releases).take(3).map(|c| {
-            let release = c[1].to_string();
-            let mirror = format!("{ARCHCRAFT_MIRROR}v{release}/");
-            let url_regex = url_regex.clone();
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let files_url = SourceForgeAPI::files_url(ARCHCRAFT_PROJECT, "/");
+        let files = SourceForgeAPI::gather_data(&files_url).await.or_network_error(&files_url)?;
+        let iso_regex = Regex::new(r"^/v([^/]+)/archcraft-.*-x86_64\.iso$").unwrap();
+
+        // The feed is newest first, so the first ISO of each release directory is its latest build
+        let mut releases = HashSet::new();
+        let isos = files
+            .iter()
+            .filter_map(|f| Some((iso_regex.captures(&f.path)?[1].to_string(), f)))
+            .filter(|(release, _)| releases.insert(release.clone()))
+            .take(Self::release_limit())
+            .collect::<Vec<_>>();
+
+        let futures = isos.into_iter().map(|(release, iso)| {
+            let checksum_url = iso.sibling(&files, ".sha256sum").map(|f| f.download_url.clone());
+            let download_url = iso.download_url.clone();
+            let file_name = iso.name().to_string();
             async move {
-                let page = capture_page(&mirror).await?;
-                let urls = url_regex.captures(&page)?;
-                let (_, [download_url, checksum_url]) = urls.extract();
-                let checksum = capture_page(checksum_url)
-                    .await
-                    .and_then(|c| c.split_whitespace().next().map(ToString::to_string));
-                Some(Config {
+                let checksum = match checksum_url {
+                    Some(url) => single_checksum(&url).await,
+                    None => None,
+                };
+                Config {
                     release,
                     edition: None,
-                    iso: Some(vec![Source::Web(WebSource::new(download_url.into(), checksum, None, None))]),
+                    iso: Some(vec![Source::Web(WebSource::new(download_url, checksum, None, Some(file_name)))]),
                     ..Default::default()
-                })
+                }
             }
         });
-        Some(join_futures!(futures, 1))
+        Ok(join_futures!(futures))
     }
 }
 
 const ARCHLINUX_API: &str = "https://archlinux.org/releng/releases/json/";
-const ARCHLINUX_MIRROR: &str = "https://mirror.rackspace.com/archlinux";
+const ARCHLINUX_MIRRORS: [(&str, Region); 3] = [
+    ("https://mirror.rackspace.com/archlinux", Region::Us),
+    ("https://geo.mirror.pkgbuild.com", Region::Global),
+    ("https://mirrors.kernel.org/archlinux", Region::Us),
+];
+// Pierre Schmitz <pierre@archlinux.org>
+const ARCHLINUX_SIGNING_KEY: &str = "3E80CA1A8B89F69CBA57D98A76A5EF9054449A5C";
 
 pub struct ArchLinux;
 impl Distro for ArchLinux {
@@ -53,33 +71,35 @@ impl Distro for ArchLinux {
     const PRETTY_NAME: &'static str = "Arch Linux";
     const HOMEPAGE: Option<&'static str> = Some("https://archlinux.org/");
     const DESCRIPTION: Option<&'static str> = Some("Lightweight and flexible Linux® distribution that tries to Keep It Simple.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let data = capture_page(ARCHLINUX_API).await?;
-        let api_data: ArchAPI = serde_json::from_str(&data).unwrap();
-        api_data
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let data = capture_page(ARCHLINUX_API).await.or_network_error(ARCHLINUX_API)?;
+        let api_data: ArchAPI = serde_json::from_str(&data)?;
+        let mut configs = api_data
             .releases
             .into_iter()
-            .take(3)
+            .take(Self::release_limit())
             .map(|r| {
-                let download_url = format!("{ARCHLINUX_MIRROR}{}", r.iso_url);
+                let download_url = mirrored_url(&ARCHLINUX_MIRRORS, &r.iso_url);
                 let checksum = r.sha256_sum;
-                let release = if r.version == api_data.latest_version { "latest".to_string() } else { r.version };
+                SourceMetadata::attach(&download_url, |m| {
+                    m.signature = Some(Signature::detached(download_url.clone() + ".sig", ARCHLINUX_SIGNING_KEY))
+                });
                 Config {
-                    release,
+                    release: r.version,
                     edition: None,
                     iso: Some(vec![Source::Web(WebSource::new(download_url, checksum, None, None))]),
                     ..Default::default()
                 }
             })
-            .collect::<Vec<Config>>()
-            .into()
+            .collect::<Vec<Config>>();
+        add_latest_alias(&mut configs);
+        Ok(configs)
     }
 }
 
 #[derive(Deserialize)]
 struct ArchAPI {
     releases: Vec<ArchRelease>,
-    latest_version: String,
 }
 
 #[derive(Deserialize)]
@@ -89,6 +109,39 @@ struct ArchRelease {
     iso_url: String,
 }
 
+const ARCHBOOT_AARCH64_MIRROR: &str = "https://release.archboot.com/aarch64/latest/iso/";
+
+pub struct Archboot;
+impl Distro for Archboot {
+    const NAME: &'static str = "archboot";
+    const PRETTY_NAME: &'static str = "Archboot";
+    const HOMEPAGE: Option<&'static str> = Some("https://archboot.com/");
+    const DESCRIPTION: Option<&'static str> = Some("Installation media for Arch Linux, which on aarch64 installs Arch Linux ARM.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        // Arch Linux ARM only publishes root filesystem tarballs, and Arch Linux's releng images are x86_64 only.
+        // Archboot's aarch64 ISOs are the only bootable installers, the "latest" and "local" editions fetch packages at boot time
+        let page = capture_page(ARCHBOOT_AARCH64_MIRROR)
+            .await
+            .or_network_error(ARCHBOOT_AARCH64_MIRROR)?;
+        let iso_regex = Regex::new(r#"href="(archboot-(\d{4}\.\d{2}\.\d{2})-[^"]*?(?:-(latest|local))?-aarch64\.iso)""#).unwrap();
+        let checksums = ChecksumSource::new().aggregate(format!("{ARCHBOOT_AARCH64_MIRROR}sha256sum.txt"), Whitespace);
+
+        let mut configs = Vec::new();
+        for c in iso_regex.captures_iter(&page) {
+            let url = format!("{ARCHBOOT_AARCH64_MIRROR}{}", &c[1]);
+            let checksum = checksums.find(&url).await;
+            configs.push(Config {
+                release: c[2].to_string(),
+                edition: Some(c.get(3).map_or("full", |e| e.as_str()).to_string()),
+                arch: Arch::aarch64,
+                iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                ..Default::default()
+            });
+        }
+        Ok(configs)
+    }
+}
+
 const ARCOLINUX_MIRROR: &str = "https://mirror.accum.se/mirror/arcolinux.info/iso/";
 
 pub struct ArcoLinux;
@@ -97,8 +150,8 @@ impl Distro for ArcoLinux {
     const PRETTY_NAME: &'static str = "ArcoLinux";
     const HOMEPAGE: Option<&'static str> = Some("https://arcolinux.com/");
     const DESCRIPTION: Option<&'static str> = Some("It's all about becoming an expert in Linux.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let releases = capture_page(ARCOLINUX_MIRROR).await?;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let releases = capture_page(ARCOLINUX_MIRROR).await.or_network_error(ARCOLINUX_MIRROR)?;
         let release_regex = Regex::new(r#">(v[0-9.]+)/</a"#).unwrap();
         let iso_regex = Arc::new(Regex::new(r#">(arco([^-]+)-[v0-9.]+-x86_64.iso)</a>"#).unwrap());
         let checksum_regex = Arc::new(Regex::new(r#">(arco([^-]+)-[v0-9.]+-x86_64.iso.sha256)</a>"#).unwrap());
@@ -107,15 +160,15 @@ impl Distro for ArcoLinux {
         releases.reverse();
         let futures = releases
             .into_iter()
-            .take(3)
+            .take(Self::release_limit())
             .map(|c| {
                 let release = c[1].to_string();
                 let mirror = format!("{ARCOLINUX_MIRROR}{release}/");
                 let iso_regex = iso_regex.clone();
-                let checksums = ChecksumSeparation::CustomRegex(checksum_regex.clone(), 2, 1);
+                let checksums = CustomRegex(checksum_regex.clone(), 2, 1);
                 async move {
                     let page = capture_page(&mirror).await?;
-                    let checksums = checksums.build_with_data(&page);
+                    let checksums = checksums.parse(&page);
 
                     let futures = iso_regex
                         .captures_iter(&page)
@@ -124,16 +177,11 @@ impl Distro for ArcoLinux {
                             let iso = i[1].to_string();
                             let edition = i[2].to_string();
                             let download_url = format!("{mirror}{iso}");
+                            SourceMetadata::attach(&download_url, |m| m.region = Some(Region::Eu));
                             let checksum_url = checksums.get(edition.as_str()).map(|c| format!("{mirror}{c}"));
                             let release = release.clone();
                             async move {
-                                let checksum = if let Some(checksum_url) = checksum_url {
-                                    capture_page(&checksum_url)
-                                        .await
-                                        .and_then(|c| c.split_whitespace().next().map(ToString::to_string))
-                                } else {
-                                    None
-                                };
+                                let checksum = if let Some(checksum_url) = checksum_url { single_checksum(&checksum_url).await } else { None };
                                 Config {
                                     release,
                                     edition: Some(edition),
@@ -147,11 +195,15 @@ impl Distro for ArcoLinux {
                 }
             })
             .collect::<Vec<_>>();
-        Some(join_futures!(futures, 2))
+        Ok(join_futures!(futures, 2))
     }
 }
 
-const ARTIX_MIRROR: &str = "https://mirrors.ocf.berkeley.edu/artix-iso/";
+const ARTIX_MIRRORS: [(&str, Region); 3] = [
+    ("https://mirrors.ocf.berkeley.edu/artix-iso/", Region::Us),
+    ("https://iso.artixlinux.org/iso/", Region::Global),
+    ("https://mirror1.artixlinux.org/iso/", Region::Eu),
+];
 
 pub struct ArtixLinux;
 impl Distro for ArtixLinux {
@@ -159,19 +211,19 @@ impl Distro for ArtixLinux {
     const PRETTY_NAME: &'static str = "Artix Linux";
     const HOMEPAGE: Option<&'static str> = Some("https://artixlinux.org/");
     const DESCRIPTION: Option<&'static str> = Some("The Art of Linux. Simple. Fast. Systemd-free.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let page = capture_page(ARTIX_MIRROR).await?;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(ARTIX_MIRRORS[0].0).await.or_network_error(ARTIX_MIRRORS[0].0)?;
         let iso_regex = Regex::new(r#"href="(artix-(.*?)-([^-]+-[0-9]+)-x86_64.iso)""#).unwrap();
 
-        let checksums = ChecksumSeparation::Whitespace.build(&format!("{ARTIX_MIRROR}sha256sums")).await;
+        let checksums = Whitespace.fetch(&format!("{}sha256sums", ARTIX_MIRRORS[0].0)).await;
 
-        iso_regex
+        Ok(iso_regex
             .captures_iter(&page)
             .map(|c| {
                 let iso = c[1].to_string();
                 let edition = c[2].to_string();
                 let release = c[3].to_string();
-                let download_url = format!("{ARTIX_MIRROR}{iso}");
+                let download_url = mirrored_url(&ARTIX_MIRRORS, &iso);
                 let checksum = checksums.as_ref().and_then(|cs| cs.get(&iso)).map(ToString::to_string);
                 Config {
                     release,
@@ -180,8 +232,7 @@ impl Distro for ArtixLinux {
                     ..Default::default()
                 }
             })
-            .collect::<Vec<Config>>()
-            .into()
+            .collect::<Vec<Config>>())
     }
 }
 
@@ -193,10 +244,11 @@ impl Distro for AthenaOS {
     const PRETTY_NAME: &'static str = "Athena OS";
     const HOMEPAGE: Option<&'static str> = Some("https://athenaos.org/");
     const DESCRIPTION: Option<&'static str> = Some("Offer a different experience than the most used pentesting distributions by providing only tools that fit with the user needs and improving the access to hacking resources and learning materials.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let api_data = GithubAPI::gather_data(ATHENA_API).await?;
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let api_data = GithubAPI::gather_data(ATHENA_API).await.or_network_error(ATHENA_API)?;
 
-        let futures = api_data.into_iter().take(2).map(|mut d| async move {
+        let futures = api_data.into_iter().take(Self::release_limit()).map(|mut d| async move {
             if d.assets.is_empty() {
                 return None;
             }
@@ -210,9 +262,7 @@ impl Distro for AthenaOS {
             let checksum = {
                 let checksum_asset = d.assets.iter().find(|a| a.name == checksum_name);
                 match checksum_asset {
-                    Some(c) => capture_page(&c.browser_download_url)
-                        .await
-                        .and_then(|c| c.split_whitespace().next().map(ToString::to_string)),
+                    Some(c) => single_checksum(&c.browser_download_url).await,
                     None => None,
                 }
             };
@@ -225,7 +275,7 @@ impl Distro for AthenaOS {
             })
         });
 
-        Some(join_futures!(futures, 1))
+        Ok(join_futures!(futures, 1))
     }
 }
 
@@ -237,8 +287,8 @@ impl Distro for BlendOS {
     const DESCRIPTION: Option<&'static str> = Some(
         "A seamless blend of all Linux distributions. Allows you to have an immutable, atomic and declarative Arch Linux system, with application support from several Linux distributions & Android.",
     );
-    async fn generate_configs() -> Option<Vec<Config>> {
-        Some(vec![Config {
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        Ok(vec![Config {
             iso: Some(vec![Source::Web(WebSource::url_only(
                 "https://kc1.mirrors.199693.xyz/blend/isos/testing/blendOS.iso",
             ))]),
@@ -255,44 +305,44 @@ impl Distro for CachyOS {
     const PRETTY_NAME: &'static str = "CachyOS";
     const HOMEPAGE: Option<&'static str> = Some("https://cachyos.org/");
     const DESCRIPTION: Option<&'static str> = Some("Designed to deliver lightning-fast speeds and stability, ensuring a smooth and enjoyable computing experience every time you use it.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let edition_data = capture_page(CACHYOS_MIRROR).await?;
-        let edition_regex = Regex::new(r#"href="(\w+)\/"#).unwrap();
-        let release_regex = Regex::new(r#"href="([0-9]+)/""#).unwrap();
-        let iso_regex = Regex::new(r#"href="(cachyos-([^-]+)-linux-[0-9]+.iso)""#).unwrap();
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let edition_regex = Regex::new(r"^\w+/$").unwrap();
+        let release_regex = Regex::new(r"^[0-9]+/$").unwrap();
+        let iso_regex = Regex::new(r"^cachyos-([^-]+)-linux-[0-9]+\.iso$").unwrap();
 
-        let edition_mirrors = edition_regex
-            .captures_iter(&edition_data)
-            .map(|c| format!("{CACHYOS_MIRROR}{}/", &c[1]));
+        let editions = list_links_matching(CACHYOS_MIRROR, &edition_regex)
+            .await
+            .or_network_error(CACHYOS_MIRROR)?;
 
-        let futures = edition_mirrors.map(|edition_mirror| {
+        let futures = editions.into_iter().map(|edition| {
+            let edition_mirror = format!("{CACHYOS_MIRROR}{edition}");
             let iso_regex = iso_regex.clone();
             let release_regex = release_regex.clone();
             async move {
-                let edition_page = capture_page(&edition_mirror).await?;
-                let futures = release_regex.captures_iter(&edition_page).map(|c| {
-                    let release = c[1].to_string();
-                    let mirror = format!("{edition_mirror}{release}/");
+                let releases = list_links_matching(&edition_mirror, &release_regex).await?;
+                let futures = releases.into_iter().map(|release| {
+                    let mirror = format!("{edition_mirror}{release}");
+                    let release = release.trim_end_matches('/').to_string();
                     let iso_regex = iso_regex.clone();
 
                     async move {
-                        let page = capture_page(&mirror).await?;
-                        let futures = iso_regex.captures_iter(&page).map(|c| {
-                            let edition = c[2].to_string();
-                            let url = format!("{mirror}{}", &c[1]);
-                            let checksum_url = url.clone() + ".sha256";
+                        let isos = list_links_matching(&mirror, &iso_regex).await?;
+                        // Older releases were published with a .sha256sum file instead
+                        let checksums = Arc::new(ChecksumSource::new().suffix(".sha256").suffix(".sha256sum"));
+                        let futures = isos.into_iter().filter_map(|iso| {
+                            let edition = iso_regex.captures(&iso)?[1].to_string();
+                            let url = format!("{mirror}{iso}");
                             let release = release.clone();
-                            async move {
-                                let checksum = capture_page(&checksum_url)
-                                    .await
-                                    .and_then(|c| c.split_whitespace().next().map(ToString::to_string));
+                            let checksums = checksums.clone();
+                            Some(async move {
+                                let checksum = checksums.find(&url).await;
                                 Config {
                                     release,
                                     edition: Some(edition),
                                     iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
                                     ..Default::default()
                                 }
-                            }
+                            })
                         });
 
                         Some(join_futures!(futures))
@@ -302,7 +352,7 @@ impl Distro for CachyOS {
             }
         });
 
-        Some(join_futures!(futures, 2))
+        Ok(join_futures!(futures, 2))
     }
 }
 
@@ -314,16 +364,16 @@ impl Distro for EndeavourOS {
     const PRETTY_NAME: &'static str = "EndeavourOS";
     const HOMEPAGE: Option<&'static str> = Some("https://endeavouros.com/");
     const DESCRIPTION: Option<&'static str> = Some("Provides an Arch experience without the hassle of installing it manually for both x86_64 and ARM systems.");
-    async fn generate_configs() -> Option<Vec<Config>> {
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Sha512;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
         let iso_regex = Regex::new(r#"href="(EndeavourOS_[^\d]+(\d{4}.\d{2}.\d{2}).iso)""#).unwrap();
-        let page = capture_page(ENDEAVOUROS_MIRROR).await?;
+        let page = capture_page(ENDEAVOUROS_MIRROR).await.or_network_error(ENDEAVOUROS_MIRROR)?;
         let futures = iso_regex.captures_iter(&page).map(|c| c.extract()).map(|(_, [iso, release])| {
             let url = ENDEAVOUROS_MIRROR.to_string() + iso;
+            SourceMetadata::attach(&url, |m| m.region = Some(Region::Eu));
             let checksum_url = url.clone() + ".sha512sum";
             async move {
-                let checksum = capture_page(&checksum_url)
-                    .await
-                    .and_then(|c| c.split_whitespace().next().map(ToString::to_string));
+                let checksum = single_checksum(&checksum_url).await;
                 Config {
                     release: release.to_string(),
                     iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
@@ -331,7 +381,7 @@ impl Distro for EndeavourOS {
                 }
             }
         });
-        Some(join_futures!(futures))
+        Ok(join_futures!(futures))
     }
 }
 
@@ -343,8 +393,8 @@ impl Distro for Garuda {
     const PRETTY_NAME: &'static str = "Garuda Linux";
     const HOMEPAGE: Option<&'static str> = Some("https://garudalinux.org/");
     const DESCRIPTION: Option<&'static str> = Some("Feature rich and easy to use Linux distribution.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let edition_html = capture_page(GARUDA_MIRROR).await?;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let edition_html = capture_page(GARUDA_MIRROR).await.or_network_error(GARUDA_MIRROR)?;
         let edition_regex = Regex::new(r#"href="([^.]+)\/""#).unwrap();
         let iso_regex = Arc::new(Regex::new(r#"href="([^"]+.iso)""#).unwrap());
 
@@ -357,12 +407,13 @@ impl Distro for Garuda {
                 let page = capture_page(&mirror).await?;
                 let iso = &iso_regex.captures(&page)?[1];
                 let url = format!("{mirror}{iso}");
-                let checksum = {
-                    let checksum_url = url.clone() + ".sha256";
-                    capture_page(&checksum_url)
-                        .await
-                        .and_then(|c| c.split_whitespace().next().map(ToString::to_string))
-                };
+                // Mirrors don't always sync every checksum file
+                let checksum = ChecksumSource::new()
+                    .suffix(".sha256")
+                    .suffix(".sha512")
+                    .algorithm(ChecksumAlgorithm::Sha512)
+                    .find(&url)
+                    .await;
 
                 Some(Config {
                     release: "latest".to_string(),
@@ -373,6 +424,62 @@ impl Distro for Garuda {
             }
         });
 
-        Some(join_futures!(futures, 1))
+        Ok(join_futures!(futures, 1))
+    }
+}
+
+const STEAMOS_RECOVERY_MIRROR: &str = "https://steamdeck-images.steamos.cloud/recovery/";
+const STEAMOS_RECOVERY_LATEST: &str = "https://steamdeck-images.steamos.cloud/recovery/steamdeck-repair-latest.img.bz2";
+
+pub struct SteamOS;
+impl Distro for SteamOS {
+    const NAME: &'static str = "steamos";
+    const PRETTY_NAME: &'static str = "SteamOS";
+    const HOMEPAGE: Option<&'static str> = Some("https://store.steampowered.com/steamos");
+    const DESCRIPTION: Option<&'static str> =
+        Some("Valve's Arch Linux based operating system of the Steam Deck. Only its recovery image is published, which can reinstall SteamOS onto a virtual disk.");
+    const IMMUTABLE: bool = true;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let recovery_regex = Regex::new(r"^steamdeck-(?:repair|recovery)-(\d{8}\.\d+)-(\d+(?:\.\d+)+)\.img\.bz2$").unwrap();
+        // The latest image is replaced in place, versioned ones are preferred so the release is known
+        let links = list_links(STEAMOS_RECOVERY_MIRROR).await.unwrap_or_default();
+        let newest = links
+            .iter()
+            .filter_map(|link| {
+                let c = recovery_regex.captures(link)?;
+                Some((link, c[2].to_string(), c[1].to_string()))
+            })
+            .max_by(|(_, a, a_build), (_, b, b_build)| version_key(a).cmp(&version_key(b)).then(a_build.cmp(b_build)));
+        let (release, url, checksum_url) = match newest {
+            Some((image, version, _)) => {
+                let checksum_url = links
+                    .iter()
+                    .find(|l| **l == format!("{image}.sha256"))
+                    .map(|l| format!("{STEAMOS_RECOVERY_MIRROR}{l}"));
+                (version, format!("{STEAMOS_RECOVERY_MIRROR}{image}"), checksum_url)
+            }
+            None => ("latest".to_string(), STEAMOS_RECOVERY_LATEST.to_string(), None),
+        };
+        let checksum = match checksum_url {
+            Some(checksum_url) => single_checksum(&checksum_url).await,
+            None => None,
+        };
+
+        Ok(vec![Config {
+            release,
+            edition: Some("recovery".to_string()),
+            // The Steam Deck only boots through UEFI
+            boot_type: Some(BootType::Efi { secure_boot: false }),
+            disk_images: Some(vec![Disk {
+                source: Source::Web(WebSource::new(url, checksum, Some(ArchiveFormat::Bz2), None)),
+                format: DiskFormat::Raw,
+                ..Default::default()
+            }]),
+            ..Default::default()
+        }])
     }
 }
+
+fn version_key(version: &str) -> Vec<u32> {
+    version.split('.').map(|n| n.parse().unwrap_or_default()).collect()
+}
@@ -1,9 +1,13 @@
 use crate::{
-    store_data::{Config, Distro, Source, WebSource},
+    checksums::single_checksum,
+    error::{GenerateError, OrGenerateError},
+    store_data::{ChecksumAlgorithm, Config, Distro, Signature, Source, SourceMetadata, WebSource},
     utils::capture_page,
 };
 use join_futures::join_futures;
 use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
 
 const BIGLINUX_MIRROR: &str = "https://iso.biglinux.com.br/";
 
@@ -15,8 +19,9 @@ impl Distro for BigLinux {
     const DESCRIPTION: Option<&'static str> = Some(
         "It's the right choice if you want to have an easy and enriching experience with Linux. It has been perfected over more than 19 years, following our motto: 'In search of the perfect system'",
     );
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let data = capture_page(BIGLINUX_MIRROR).await?;
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Md5;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let data = capture_page(BIGLINUX_MIRROR).await.or_network_error(BIGLINUX_MIRROR)?;
         let biglinux_regex = Regex::new(r#"<a href="(biglinux_([0-9]{4}(?:-[0-9]{2}){2})_(.*?).iso)""#).unwrap();
 
         let mut data = biglinux_regex.captures_iter(&data).collect::<Vec<_>>();
@@ -27,9 +32,7 @@ impl Distro for BigLinux {
             let url = BIGLINUX_MIRROR.to_string() + iso;
             let checksum_url = url.clone() + ".md5";
             async move {
-                let checksum = capture_page(&checksum_url)
-                    .await
-                    .and_then(|s| s.split_whitespace().next().map(ToString::to_string));
+                let checksum = single_checksum(&checksum_url).await;
                 Config {
                     release: release.to_string(),
                     edition: Some(edition.to_string()),
@@ -39,6 +42,79 @@ impl Distro for BigLinux {
             }
         });
 
-        Some(join_futures!(futures))
+        Ok(join_futures!(futures))
     }
 }
+
+const MANJARO_FILE_INFO: &str = "https://gitlab.manjaro.org/web/iso-info/-/raw/master/file-info.json";
+// Manjaro Build Server <build@manjaro.org>
+const MANJARO_SIGNING_KEY: &str = "3B794DE6D4320FCE594F4171279E7CF5D8D56EC8";
+
+pub struct Manjaro;
+impl Distro for Manjaro {
+    const NAME: &'static str = "manjaro";
+    const PRETTY_NAME: &'static str = "Manjaro";
+    const HOMEPAGE: Option<&'static str> = Some("https://manjaro.org/");
+    const DESCRIPTION: Option<&'static str> =
+        Some("Versatile, free, and open-source Linux operating system designed with a strong focus on safeguarding user privacy and offering extensive control over hardware.");
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Sha512;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        // The same metadata the download page is built from, listing the current image of each edition
+        let data = capture_page(MANJARO_FILE_INFO).await.or_network_error(MANJARO_FILE_INFO)?;
+        let file_info: ManjaroFileInfo = serde_json::from_str(&data)?;
+        let version_regex = Regex::new(r"-(\d+(?:\.\d+)+)-").unwrap();
+
+        let futures = file_info
+            .official
+            .into_iter()
+            .chain(file_info.community)
+            .flat_map(|(edition, image)| {
+                let minimal = image.minimal.map(|minimal| (format!("{edition}-minimal"), minimal));
+                [(edition, image.files)].into_iter().chain(minimal)
+            })
+            .map(|(edition, files)| {
+                let release = version_regex
+                    .captures(&files.image)
+                    .map_or("latest".to_string(), |c| c[1].to_string());
+                async move {
+                    let checksum = match &files.checksum {
+                        Some(url) => single_checksum(url).await,
+                        None => None,
+                    };
+                    SourceMetadata::attach(&files.image, |m| {
+                        m.signature = files.signature.map(|url| Signature::detached(url, MANJARO_SIGNING_KEY));
+                        m.torrent = files.torrent;
+                    });
+                    Config {
+                        release,
+                        edition: Some(edition),
+                        iso: Some(vec![Source::Web(WebSource::new(files.image, checksum, None, None))]),
+                        ..Default::default()
+                    }
+                }
+            });
+
+        Ok(join_futures!(futures))
+    }
+}
+
+#[derive(Deserialize)]
+struct ManjaroFileInfo {
+    official: HashMap<String, ManjaroImage>,
+    community: HashMap<String, ManjaroImage>,
+}
+
+#[derive(Deserialize)]
+struct ManjaroImage {
+    #[serde(flatten)]
+    files: ManjaroFiles,
+    minimal: Option<ManjaroFiles>,
+}
+
+#[derive(Deserialize)]
+struct ManjaroFiles {
+    image: String,
+    signature: Option<String>,
+    checksum: Option<String>,
+    torrent: Option<String>,
+}
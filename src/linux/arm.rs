@@ -0,0 +1,119 @@
+use crate::{
+    checksums::single_checksum,
+    error::{GenerateError, OrGenerateError},
+    store_data::{ArchiveFormat, Config, Disk, Distro, Source, WebSource},
+    utils::{capture_large_page, list_links_matching},
+};
+use join_futures::join_futures;
+use quickemu::config::{Arch, DiskFormat};
+use regex::Regex;
+use serde::Deserialize;
+use std::sync::Arc;
+
+const ARMBIAN_IMAGES: &str = "https://github.armbian.com/all-images.json";
+/// Boards whose images are included. Armbian publishes images for hundreds of boards, most of which QEMU can't emulate
+const ARMBIAN_BOARDS: [&str; 4] = ["uefi-arm64", "rpi4b", "orangepi5", "rock-5b"];
+
+pub struct Armbian;
+impl Distro for Armbian {
+    const NAME: &'static str = "armbian";
+    const PRETTY_NAME: &'static str = "Armbian";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.armbian.com/");
+    const DESCRIPTION: Option<&'static str> = Some("Lightweight Debian and Ubuntu based distribution specialising in ARM single board computers.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let data = capture_large_page(ARMBIAN_IMAGES).await.or_network_error(ARMBIAN_IMAGES)?;
+        let images: ArmbianImages = serde_json::from_str(&data)?;
+
+        let futures = images
+            .assets
+            .into_iter()
+            // Images with preinstalled applications are built on top of the plain ones, which are all that's needed
+            .filter(|image| ARMBIAN_BOARDS.contains(&image.board_slug.as_str()) && image.preinstalled_application.is_empty())
+            .filter(|image| image.file_url.ends_with(".img.xz") || image.file_url.ends_with(".qcow2.xz"))
+            .map(|image| async move {
+                let checksum = single_checksum(&format!("{}.sha", image.file_url)).await;
+                let format = if image.file_url.ends_with(".qcow2.xz") { DiskFormat::Qcow2 } else { DiskFormat::Raw };
+                Config {
+                    release: image.armbian_version,
+                    edition: Some(format!(
+                        "{}-{}-{}-{}",
+                        image.board_slug, image.distro_release, image.kernel_branch, image.image_variant
+                    )),
+                    arch: Arch::aarch64,
+                    disk_images: Some(vec![Disk {
+                        source: Source::Web(WebSource::new(image.file_url, checksum, Some(ArchiveFormat::Xz), None)),
+                        format,
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                }
+            });
+        Ok(join_futures!(futures))
+    }
+}
+
+#[derive(Deserialize)]
+struct ArmbianImages {
+    assets: Vec<ArmbianImage>,
+}
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct ArmbianImage {
+    board_slug: String,
+    armbian_version: String,
+    file_url: String,
+    distro_release: String,
+    kernel_branch: String,
+    image_variant: String,
+    preinstalled_application: String,
+}
+
+const RASPIOS_MIRROR: &str = "https://downloads.raspberrypi.com/";
+
+pub struct RaspberryPiOS;
+impl Distro for RaspberryPiOS {
+    const NAME: &'static str = "raspios";
+    const PRETTY_NAME: &'static str = "Raspberry Pi OS";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.raspberrypi.com/software/");
+    const DESCRIPTION: Option<&'static str> = Some("Official Debian based operating system of the Raspberry Pi.");
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let release_regex = Arc::new(Regex::new(r"^raspios_[a-z_]*arm64-(\d{4}-\d{2}-\d{2})/$").unwrap());
+        let image_regex = Arc::new(Regex::new(r"^\d{4}-\d{2}-\d{2}-raspios-[a-z]+-arm64(?:-[a-z]+)?\.img\.xz$").unwrap());
+        let release_limit = Self::release_limit();
+
+        let futures = [("lite", "raspios_lite_arm64"), ("desktop", "raspios_arm64"), ("full", "raspios_full_arm64")].map(|(edition, directory)| {
+            let (release_regex, image_regex) = (release_regex.clone(), image_regex.clone());
+            async move {
+                let mirror = format!("{RASPIOS_MIRROR}{directory}/images/");
+                let mut releases = list_links_matching(&mirror, &release_regex).await?;
+                // Releases are named by date, so they sort chronologically
+                releases.sort_unstable_by(|a, b| b.cmp(a));
+                releases.dedup();
+                let futures = releases.into_iter().take(release_limit).filter_map(|release| {
+                    let url = format!("{mirror}{release}");
+                    let release = release_regex.captures(&release).map(|c| c[1].to_string())?;
+                    let image_regex = image_regex.clone();
+                    Some(async move {
+                        let image = list_links_matching(&url, &image_regex).await?.into_iter().next()?;
+                        let url = format!("{url}{image}");
+                        let checksum = single_checksum(&format!("{url}.sha256")).await;
+                        Some(Config {
+                            release,
+                            edition: Some(edition.to_string()),
+                            arch: Arch::aarch64,
+                            disk_images: Some(vec![Disk {
+                                source: Source::Web(WebSource::new(url, checksum, Some(ArchiveFormat::Xz), None)),
+                                format: DiskFormat::Raw,
+                                ..Default::default()
+                            }]),
+                            ..Default::default()
+                        })
+                    })
+                });
+                Some(join_futures!(futures, 1))
+            }
+        });
+        Ok(join_futures!(futures, 2))
+    }
+}
@@ -1,13 +1,19 @@
 use crate::{
-    store_data::{ChecksumSeparation, Config, Disk, Distro, Source, WebSource},
-    utils::{capture_page, GatherData, GithubAPI},
+    checksums::{single_checksum, ChecksumFormat, ReleaseNotes, Whitespace},
+    error::{GenerateError, OrGenerateError},
+    store_data::{cloud_image, Channel, ChecksumAlgorithm, ChecksumFileFormat, ChecksumSource, Config, ConfigMetadata, Disk, Distro, NetBoot, Signature, Source, SourceMetadata, WebSource},
+    utils::{arch_from_str, capture_page, list_links, list_links_matching, spawn, GatherData, GithubAPI, SourceForgeAPI, SourceForgeFile},
 };
 use join_futures::join_futures;
 use quickemu::config::{Arch, DiskFormat};
 use quickget_core::data_structures::ArchiveFormat;
 use regex::Regex;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
+const ANTIX_PROJECT: &str = "antix-linux";
 const ANTIX_MIRROR: &str = "https://sourceforge.net/projects/antix-linux/files/Final/";
 
 pub struct Antix;
@@ -16,60 +22,103 @@ impl Distro for Antix {
     const PRETTY_NAME: &'static str = "antiX";
     const HOMEPAGE: Option<&'static str> = Some("https://antixlinux.com/");
     const DESCRIPTION: Option<&'static str> = Some("Fast, lightweight and easy to install systemd-free linux live CD distribution based on Debian Stable for Intel-AMD x86 compatible systems.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let releases = capture_page(ANTIX_MIRROR).await?;
-
-        let releases_regex = Regex::new(r#""name":"antiX-([0-9.]+)""#).unwrap();
-        let iso_regex = Arc::new(Regex::new(r#""name":"(antiX-[0-9.]+(?:-runit)?(?:-[^_]+)?_x64-([^.]+).iso)".*?"download_url":"(.*?)""#).unwrap());
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let files_url = SourceForgeAPI::files_url(ANTIX_PROJECT, "Final");
+        let files = SourceForgeAPI::gather_data(&files_url).await.or_network_error(&files_url)?;
+        // runit ISOs are kept in a subdirectory of the release
+        let iso_regex = Regex::new(r"^/Final/antiX-([0-9.]+)/(runit-antiX-[0-9.]+/)?antiX-[0-9.]+(?:-runit)?(?:-[^_]+)?_x64-([^.]+)\.iso$").unwrap();
 
-        let skip_until_sha256 = |cs_data: String| {
-            cs_data
-                .lines()
-                .skip_while(|l| !l.starts_with("sha256"))
-                .collect::<Vec<_>>()
-                .join("\n")
-        };
+        let mut isos: HashMap<String, Vec<(String, &SourceForgeFile)>> = HashMap::new();
+        for file in &files {
+            if let Some(c) = iso_regex.captures(&file.path) {
+                let ending = if c.get(2).is_some() { "-runit" } else { "-sysv" };
+                isos.entry(c[1].to_string())
+                    .or_default()
+                    .push((c[3].to_string() + ending, file));
+            }
+        }
+        let mut releases = isos.into_iter().collect::<Vec<_>>();
+        releases.sort_by_cached_key(|(release, _)| std::cmp::Reverse(version_key(release)));
 
-        let futures = releases_regex.captures_iter(&releases).take(3).map(|c| {
-            let release = c[1].to_string();
+        let futures = releases.into_iter().take(Self::release_limit()).map(|(release, isos)| {
             let mirror = format!("{ANTIX_MIRROR}antiX-{release}/");
             let checksum_mirror = format!("{mirror}README.txt/download");
-            let runit_mirror = format!("{mirror}runit-antiX-{release}/");
-            let runit_checksum_mirror = format!("{runit_mirror}README2.txt/download");
-            let iso_regex = iso_regex.clone();
+            let runit_checksum_mirror = format!("{mirror}runit-antiX-{release}/README2.txt/download");
+            let isos = isos
+                .into_iter()
+                .map(|(edition, file)| (edition, file.name().to_string(), file.download_url.clone()))
+                .collect::<Vec<_>>();
 
             async move {
-                let main_checksums = capture_page(&checksum_mirror).await.map(skip_until_sha256).unwrap_or_default();
-                let runit_checksums = capture_page(&runit_checksum_mirror).await.map(skip_until_sha256);
-                let checksums = main_checksums + "\n" + &runit_checksums.unwrap_or_default();
-                let mut checksums = ChecksumSeparation::Whitespace.build_with_data(&checksums);
-
-                let page = capture_page(&mirror).await?;
-                let iso_regex = iso_regex.clone();
-                let main_releases = iso_regex.captures_iter(&page).zip(std::iter::repeat("-sysv"));
-                let runit_page = capture_page(&runit_mirror).await?;
-                let runit_releases = iso_regex.captures_iter(&runit_page).zip(std::iter::repeat("-runit"));
+                // The READMEs list MD5 and SHA256 checksums of every ISO, among the release notes
+                let mut checksums = ReleaseNotes(ChecksumAlgorithm::Sha256)
+                    .fetch(&checksum_mirror)
+                    .await
+                    .unwrap_or_default();
+                checksums.extend(
+                    ReleaseNotes(ChecksumAlgorithm::Sha256)
+                        .fetch(&runit_checksum_mirror)
+                        .await
+                        .unwrap_or_default(),
+                );
 
-                Some(
-                    main_releases
-                        .chain(runit_releases)
-                        .map(|(c, ending)| {
-                            let checksum = checksums.remove(&c[1]);
-                            let edition = c[2].to_string() + ending;
-                            let url = c[3].to_string();
-                            Config {
-                                release: release.to_string(),
-                                edition: Some(edition),
-                                iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
-                                ..Default::default()
-                            }
-                        })
-                        .collect::<Vec<_>>(),
-                )
+                isos.into_iter()
+                    .map(|(edition, name, url)| Config {
+                        release: release.to_string(),
+                        edition: Some(edition),
+                        iso: Some(vec![Source::Web(WebSource::new(
+                            url,
+                            checksums.remove(&name),
+                            None,
+                            Some(name),
+                        ))]),
+                        ..Default::default()
+                    })
+                    .collect::<Vec<_>>()
             }
         });
 
-        Some(join_futures!(futures, 2))
+        Ok(join_futures!(futures, 1))
+    }
+}
+
+const ASTRA_CE_MIRROR: &str = "https://dl.astralinux.ru/astra/stable/2.12_x86-64/iso/";
+
+pub struct AstraLinux;
+impl Distro for AstraLinux {
+    const NAME: &'static str = "astra";
+    const PRETTY_NAME: &'static str = "Astra Linux Common Edition";
+    const HOMEPAGE: Option<&'static str> = Some("https://astralinux.ru/");
+    const DESCRIPTION: Option<&'static str> = Some("Russian general-purpose distribution based on Debian, whose Common Edition (codenamed Orel) is freely available.");
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Md5;
+    const LOCALE: Option<&'static str> = Some("ru_RU");
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(ASTRA_CE_MIRROR).await.or_network_error(ASTRA_CE_MIRROR)?;
+        let iso_regex = Regex::new(r#"href="(orel-(2\.12(?:\.\d+)+)-[^"]*\.iso)""#).unwrap();
+
+        let mut isos = iso_regex
+            .captures_iter(&page)
+            .map(|c| (c[2].to_string(), c[1].to_string()))
+            .collect::<Vec<_>>();
+        isos.sort_by_cached_key(|(release, _)| version_key(release));
+        isos.dedup_by(|a, b| a.0 == b.0);
+
+        let futures = isos
+            .into_iter()
+            .rev()
+            .take(Self::release_limit())
+            .map(|(release, iso)| async move {
+                let url = format!("{ASTRA_CE_MIRROR}{iso}");
+                let checksum = ChecksumSource::new().suffix(".md5").find(&url).await;
+                Config {
+                    release,
+                    iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                    ..Default::default()
+                }
+            });
+
+        Ok(join_futures!(futures))
     }
 }
 
@@ -81,19 +130,19 @@ impl Distro for BunsenLabs {
     const PRETTY_NAME: &'static str = "BunsenLabs";
     const HOMEPAGE: Option<&'static str> = Some("https://www.bunsenlabs.org/");
     const DESCRIPTION: Option<&'static str> = Some("Light-weight and easily customizable Openbox desktop. The project is a community continuation of CrunchBang Linux.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let html = capture_page(BUNSENLABS_MIRROR).await?;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let html = capture_page(BUNSENLABS_MIRROR).await.or_network_error(BUNSENLABS_MIRROR)?;
         let release_regex = Regex::new(r#"href="(([^-]+)-1(:?-[0-9]+)?-amd64.hybrid.iso)""#).unwrap();
         // Gather all possible checksums
         let checksum_regex = Regex::new(r#"href="(.*?.sha256.txt)""#).unwrap();
 
         let checksum_futures = checksum_regex.captures_iter(&html).map(|c| {
             let url = format!("{BUNSENLABS_MIRROR}{}", &c[1]);
-            async move { ChecksumSeparation::Whitespace.build(&url).await }
+            async move { Whitespace.fetch(&url).await }
         });
         let mut checksums = join_futures!(checksum_futures, 2, HashMap<String, String>);
 
-        release_regex
+        Ok(release_regex
             .captures_iter(&html)
             .map(|c| c.extract())
             .map(|(_, [iso, release])| {
@@ -106,8 +155,7 @@ impl Distro for BunsenLabs {
                     ..Default::default()
                 }
             })
-            .collect::<Vec<Config>>()
-            .into()
+            .collect::<Vec<Config>>())
     }
 }
 
@@ -119,36 +167,76 @@ impl Distro for CrunchbangPlusPlus {
     const PRETTY_NAME: &'static str = "Crunchbangplusplus";
     const HOMEPAGE: Option<&'static str> = Some("https://crunchbangplusplus.org/");
     const DESCRIPTION: Option<&'static str> = Some("The classic minimal crunchbang feel, now with debian 12 bookworm.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let mut api_data = GithubAPI::gather_data(CRUNCHBANG_API).await?;
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Md5;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let mut api_data = GithubAPI::gather_data(CRUNCHBANG_API).await.or_network_error(CRUNCHBANG_API)?;
         api_data.retain(|v| !v.prerelease);
-        api_data
+        Ok(api_data
             .into_iter()
-            .take(3)
+            .take(Self::release_limit())
             .filter_map(|value| {
                 let release = value.tag_name;
                 let iso = value.assets.into_iter().find(|a| a.name.contains("amd64"))?;
                 let url = iso.browser_download_url;
-                let checksum_data = value
-                    .body
-                    .lines()
-                    .skip_while(|l| !l.contains("md5sum"))
-                    .collect::<Vec<&str>>()
-                    .join("\n");
-                let checksum = ChecksumSeparation::Whitespace.build_with_data(&checksum_data).remove(&iso.name);
+                let checksum = ReleaseNotes(ChecksumAlgorithm::Md5).parse(&value.body).remove(&iso.name);
                 Some(Config {
                     release,
                     iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
                     ..Default::default()
                 })
             })
-            .collect::<Vec<Config>>()
-            .into()
+            .collect::<Vec<Config>>())
     }
 }
 
 const LATEST_DEBIAN_MIRROR: &str = "https://cdimage.debian.org/debian-cd/";
 const PREVIOUS_DEBIAN_MIRROR: &str = "https://cdimage.debian.org/cdimage/archive/";
+// Debian CD signing key <debian-cd@lists.debian.org>
+const DEBIAN_SIGNING_KEY: &str = "DF9B9C49EAA9298432589D76DA87E80D6294BE9B";
+
+fn attach_debian_signature(url: &str, mirror: &str) {
+    SourceMetadata::attach(url, |m| {
+        m.signature = Some(Signature::checksum_file(
+            format!("{mirror}SHA256SUMS.sign"),
+            format!("{mirror}SHA256SUMS"),
+            ChecksumFileFormat::Whitespace,
+            DEBIAN_SIGNING_KEY,
+        ))
+    });
+}
+
+const DEBIAN_ARCHIVE: &str = "https://deb.debian.org/debian/";
+const DEBIAN_CLOUD_MIRROR: &str = "https://cloud.debian.org/images/cloud/";
+
+/// Maps each release still served by the main archive to its codename, which is needed to locate the installer's netboot files
+async fn debian_codenames() -> HashMap<u32, String> {
+    let futures = ["oldstable", "stable"].into_iter().map(|suite| async move {
+        let release_file = capture_page(&format!("{DEBIAN_ARCHIVE}dists/{suite}/Release")).await?;
+        let field = |name: &str| release_file.lines().find_map(|l| l.strip_prefix(name)).map(str::trim);
+        let release = field("Version:")?.split('.').next()?.parse::<u32>().ok()?;
+        Some((release, field("Codename:")?.to_string()))
+    });
+    join_futures!(futures, 1, HashMap<u32, String>)
+}
+
+async fn debian_netboot(release: u32, arch_text: &str, codenames: &HashMap<u32, String>) -> Option<NetBoot> {
+    let images = format!(
+        "{DEBIAN_ARCHIVE}dists/{}/main/installer-{arch_text}/current/images/",
+        codenames.get(&release)?
+    );
+    let mut checksums = Whitespace.fetch(&format!("{images}SHA256SUMS")).await;
+    let mut source = |file: &str| {
+        let path = format!("netboot/debian-installer/{arch_text}/{file}");
+        let checksum = checksums.as_mut().and_then(|cs| cs.remove(&format!("./{path}")));
+        WebSource::new(format!("{images}{path}"), checksum, None, None)
+    };
+    Some(NetBoot {
+        kernel: source("linux"),
+        initrd: source("initrd.gz"),
+        cmdline: None,
+        archive: None,
+    })
+}
 
 pub struct Debian;
 impl Distro for Debian {
@@ -156,23 +244,39 @@ impl Distro for Debian {
     const PRETTY_NAME: &'static str = "Debian";
     const HOMEPAGE: Option<&'static str> = Some("https://www.debian.org/");
     const DESCRIPTION: Option<&'static str> = Some("Complete Free Operating System with perfect level of ease of use and stability.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let latest_html = capture_page(LATEST_DEBIAN_MIRROR).await?;
-        let previous_html = capture_page(PREVIOUS_DEBIAN_MIRROR).await?;
-        let releases_regex = Regex::new(r#"href="([0-9.]+)/""#).unwrap();
-        let live_regex = Arc::new(Regex::new(">(debian-live-[0-9.]+-amd64-([^.]+).iso)<").unwrap());
-        let netinst_regex = Arc::new(Regex::new(">(debian-[0-9].+-(?:amd64|arm64)-(netinst).iso)<").unwrap());
+    const EOL_PRODUCT: Option<&'static str> = Some("debian");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let releases_regex = Regex::new(r"^([0-9.]+)/$").unwrap();
+        let latest_links = list_links_matching(LATEST_DEBIAN_MIRROR, &releases_regex)
+            .await
+            .or_network_error(LATEST_DEBIAN_MIRROR)?;
+        let previous_links = list_links_matching(PREVIOUS_DEBIAN_MIRROR, &releases_regex)
+            .await
+            .or_network_error(PREVIOUS_DEBIAN_MIRROR)?;
+        let live_regex = Arc::new(Regex::new(r"^debian-live-[0-9.]+-amd64-([^.]+)\.iso$").unwrap());
+        let netinst_regex = Arc::new(Regex::new(r"^debian-[0-9].+-(?:amd64|arm64)-(netinst)\.iso$").unwrap());
 
-        let latest_full_release = releases_regex.captures(&latest_html)?[1].to_string();
-        let latest_release = latest_full_release.split('.').next()?.parse::<u32>().ok()?;
+        let latest_full_release = latest_links
+            .first()
+            .or_parse_error("Debian's latest release")?
+            .trim_end_matches('/')
+            .to_string();
+        let latest_release = latest_full_release
+            .split('.')
+            .next()
+            .and_then(|r| r.parse::<u32>().ok())
+            .or_parse_error("Debian's major release number")?;
+        let codenames = Arc::new(debian_codenames().await);
 
-        let mut previous_captures = releases_regex
-            .captures_iter(&previous_html)
-            .map(|c| (c[1].to_string(), c[1].split('.').next().unwrap().parse::<u32>().unwrap()))
+        let mut previous_captures = previous_links
+            .iter()
+            .map(|link| link.trim_end_matches('/'))
+            .map(|r| (r.to_string(), r.split('.').next().unwrap().parse::<u32>().unwrap()))
             .fold(HashMap::new(), |mut acc, (full_release, release)| {
-                if acc.get(&release).map_or(true, |v: &String| {
-                    v.split('.').nth(1).unwrap().parse::<u32>().unwrap() < full_release.split('.').nth(1).unwrap().parse::<u32>().unwrap()
-                }) {
+                if acc
+                    .get(&release)
+                    .is_none_or(|v: &String| v.split('.').nth(1).unwrap().parse::<u32>().unwrap() < full_release.split('.').nth(1).unwrap().parse::<u32>().unwrap())
+                {
                     acc.insert(release, full_release);
                 }
                 acc
@@ -185,26 +289,31 @@ impl Distro for Debian {
         let futures = releases
             .flat_map(|(release, full_release, mirror)| {
                 let live_mirror = format!("{mirror}{full_release}-live/amd64/iso-hybrid/");
+                let torrent_mirror = format!("{mirror}{full_release}-live/amd64/bt-hybrid/");
                 let live_regex = live_regex.clone();
-                let live_configs = tokio::spawn(async move {
-                    let page = capture_page(&live_mirror).await?;
-                    let mut checksums = ChecksumSeparation::Whitespace.build(&format!("{live_mirror}SHA256SUMS")).await;
-                    Some(
-                        live_regex
-                            .captures_iter(&page)
-                            .map(|c| c.extract())
-                            .map(|(_, [iso, edition])| {
-                                let url = format!("{live_mirror}{iso}");
-                                let checksum = checksums.as_mut().and_then(|cs| cs.remove(iso));
-                                Config {
-                                    release: release.to_string(),
-                                    edition: Some(edition.to_string()),
-                                    iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
-                                    ..Default::default()
-                                }
-                            })
-                            .collect::<Vec<Config>>(),
-                    )
+                let live_configs = spawn(async move {
+                    let isos = list_links_matching(&live_mirror, &live_regex).await?;
+                    let checksums = debian_checksums(&live_mirror);
+                    let torrents = list_links(&torrent_mirror).await.unwrap_or_default();
+                    let mut configs = Vec::new();
+                    for iso in isos {
+                        let Some(edition) = live_regex.captures(&iso).map(|c| c[1].to_string()) else {
+                            continue;
+                        };
+                        let url = format!("{live_mirror}{iso}");
+                        attach_debian_signature(&url, &live_mirror);
+                        if torrents.contains(&format!("{iso}.torrent")) {
+                            SourceMetadata::attach(&url, |m| m.torrent = Some(format!("{torrent_mirror}{iso}.torrent")));
+                        }
+                        let checksum = checksums.find(&url).await;
+                        configs.push(Config {
+                            release: release.to_string(),
+                            edition: Some(edition),
+                            iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                            ..Default::default()
+                        });
+                    }
+                    Some(configs)
                 });
                 let netinst_configs = [Arch::x86_64, Arch::aarch64]
                     .iter()
@@ -215,36 +324,176 @@ impl Distro for Debian {
                             _ => unreachable!(),
                         };
                         let netinst_mirror = format!("{mirror}{full_release}/{arch_text}/iso-cd/");
-                        let checksum_mirror = format!("{netinst_mirror}SHA256SUMS");
                         let netinst_regex = netinst_regex.clone();
-                        tokio::spawn(async move {
-                            let page = capture_page(&netinst_mirror).await?;
-                            let mut checksums = ChecksumSeparation::Whitespace.build(&checksum_mirror).await;
-                            Some(
-                                netinst_regex
-                                    .captures_iter(&page)
-                                    .map(|c| c.extract())
-                                    .map(|(_, [iso, edition])| {
-                                        let url = format!("{netinst_mirror}{iso}");
-                                        let checksum = checksums.as_mut().and_then(|cs| cs.remove(iso));
-                                        Config {
-                                            release: release.to_string(),
-                                            edition: Some(edition.to_string()),
-                                            iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
-                                            arch: arch.clone(),
-                                            ..Default::default()
-                                        }
-                                    })
-                                    .collect::<Vec<Config>>(),
-                            )
+                        let codenames = codenames.clone();
+                        spawn(async move {
+                            let isos = list_links_matching(&netinst_mirror, &netinst_regex).await?;
+                            let checksums = debian_checksums(&netinst_mirror);
+                            let netboot = debian_netboot(release, arch_text, &codenames).await;
+                            let mut configs = Vec::new();
+                            for iso in isos {
+                                let Some(edition) = netinst_regex.captures(&iso).map(|c| c[1].to_string()) else {
+                                    continue;
+                                };
+                                let url = format!("{netinst_mirror}{iso}");
+                                attach_debian_signature(&url, &netinst_mirror);
+                                if let Some(netboot) = &netboot {
+                                    ConfigMetadata::attach(&url, |m| m.netboot = Some(netboot.clone()));
+                                }
+                                let checksum = checksums.find(&url).await;
+                                configs.push(Config {
+                                    release: release.to_string(),
+                                    edition: Some(edition),
+                                    iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                                    arch: arch.clone(),
+                                    ..Default::default()
+                                });
+                            }
+                            Some(configs)
                         })
                     })
                     .collect::<Vec<_>>();
-                [vec![live_configs], netinst_configs]
+                let codename = codenames.get(&release).cloned();
+                let cloud_configs = spawn(async move {
+                    let cloud_mirror = format!("{DEBIAN_CLOUD_MIRROR}{}/latest/", codename?);
+                    let checksums = ChecksumSource::new()
+                        .aggregate(format!("{cloud_mirror}SHA512SUMS"), Whitespace)
+                        .algorithm(ChecksumAlgorithm::Sha512);
+                    let mut configs = Vec::new();
+                    for (arch, arch_text) in [(Arch::x86_64, "amd64"), (Arch::aarch64, "arm64")] {
+                        let url = format!("{cloud_mirror}debian-{release}-genericcloud-{arch_text}.qcow2");
+                        let checksum = checksums.find(&url).await;
+                        configs.push(cloud_image(
+                            release.to_string(),
+                            arch,
+                            WebSource::new(url, checksum, None, None),
+                            "debian",
+                        ));
+                    }
+                    Some(configs)
+                });
+                [vec![live_configs, cloud_configs], netinst_configs]
             })
             .flatten();
 
-        Some(join_futures!(futures, 3))
+        Ok(join_futures!(futures, 3))
+    }
+}
+
+/// Debian publishes both SHA256SUMS and SHA512SUMS, the latter is occasionally the only one which is complete on a mirror
+fn debian_checksums(mirror: &str) -> ChecksumSource {
+    ChecksumSource::new()
+        .aggregate(format!("{mirror}SHA256SUMS"), Whitespace)
+        .aggregate(format!("{mirror}SHA512SUMS"), Whitespace)
+        .algorithm(ChecksumAlgorithm::Sha512)
+}
+
+pub struct DebianEdu;
+impl Distro for DebianEdu {
+    const NAME: &'static str = "debian-edu";
+    const PRETTY_NAME: &'static str = "Debian Edu";
+    const HOMEPAGE: Option<&'static str> = Some("https://wiki.debian.org/DebianEdu/");
+    const DESCRIPTION: Option<&'static str> = Some("Also known as Skolelinux, a Debian Pure Blend providing an out-of-the-box environment for a complete school network.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let latest_html = capture_page(LATEST_DEBIAN_MIRROR)
+            .await
+            .or_network_error(LATEST_DEBIAN_MIRROR)?;
+        let releases_regex = Regex::new(r#"href="([0-9.]+)/""#).unwrap();
+        let iso_regex = Arc::new(Regex::new(r#">(debian-edu-[0-9.]+-amd64-(netinst|BD-1).iso)<"#).unwrap());
+
+        let full_release = releases_regex
+            .captures(&latest_html)
+            .or_parse_error("Debian's latest release")?[1]
+            .to_string();
+        let release = full_release
+            .split('.')
+            .next()
+            .or_parse_error("Debian's major release number")?
+            .to_string();
+
+        let futures = ["iso-cd", "iso-bd"].into_iter().map(|directory| {
+            let mirror = format!("{LATEST_DEBIAN_MIRROR}{full_release}/amd64/{directory}/");
+            let iso_regex = iso_regex.clone();
+            let release = release.clone();
+            async move {
+                let page = capture_page(&mirror).await?;
+                let mut checksums = Whitespace.fetch(&format!("{mirror}SHA256SUMS")).await;
+                Some(
+                    iso_regex
+                        .captures_iter(&page)
+                        .map(|c| c.extract())
+                        .map(|(_, [iso, edition])| {
+                            let url = format!("{mirror}{iso}");
+                            attach_debian_signature(&url, &mirror);
+                            let checksum = checksums.as_mut().and_then(|cs| cs.remove(iso));
+                            let edition = if edition == "BD-1" { "bd" } else { edition };
+                            Config {
+                                release: release.clone(),
+                                edition: Some(edition.to_string()),
+                                iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                                ..Default::default()
+                            }
+                        })
+                        .collect::<Vec<Config>>(),
+                )
+            }
+        });
+
+        Ok(join_futures!(futures, 2))
+    }
+}
+
+const DEEPIN_MIRROR: &str = "https://cdimage.deepin.com/releases/";
+
+pub struct Deepin;
+impl Distro for Deepin {
+    const NAME: &'static str = "deepin";
+    const PRETTY_NAME: &'static str = "deepin";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.deepin.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Beautiful, easy to use Linux distribution, featuring its own Deepin Desktop Environment.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(DEEPIN_MIRROR).await.or_network_error(DEEPIN_MIRROR)?;
+        let release_regex = Regex::new(r#"href="(\d+(?:\.\d+)*)/""#).unwrap();
+        let mut releases = release_regex
+            .captures_iter(&page)
+            .map(|c| c[1].to_string())
+            .collect::<Vec<String>>();
+        releases.sort_by_cached_key(|r| r.split('.').map(|n| n.parse::<u32>().unwrap_or_default()).collect::<Vec<u32>>());
+        releases.dedup();
+
+        let futures = releases.into_iter().rev().take(Self::release_limit()).flat_map(|release| {
+            [Arch::x86_64, Arch::aarch64].into_iter().map(move |arch| {
+                let release = release.clone();
+                async move {
+                    let deb_arch = match arch {
+                        Arch::aarch64 => "arm64",
+                        _ => "amd64",
+                    };
+                    let iso_regex = Regex::new(&format!(r#"href="(deepin-desktop-community-[^"]+-{deb_arch}\.iso)""#)).unwrap();
+                    // Releases before 23 only published amd64 images, directly within the release directory
+                    let mut mirror = format!("{DEEPIN_MIRROR}{release}/{deb_arch}/");
+                    let mut page = capture_page(&mirror).await;
+                    if page.is_none() && arch == Arch::x86_64 {
+                        mirror = format!("{DEEPIN_MIRROR}{release}/");
+                        page = capture_page(&mirror).await;
+                    }
+                    let iso = iso_regex.captures(&page?)?[1].to_string();
+                    let url = format!("{mirror}{iso}");
+                    let checksum = ChecksumSource::new()
+                        .aggregate(format!("{mirror}SHA256SUMS"), Whitespace)
+                        .find(&url)
+                        .await;
+                    Some(Config {
+                        release,
+                        arch,
+                        iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                        ..Default::default()
+                    })
+                }
+            })
+        });
+
+        Ok(join_futures!(futures, 1))
     }
 }
 
@@ -257,30 +506,32 @@ impl Distro for Devuan {
     const HOMEPAGE: Option<&'static str> = Some("https://devuan.org/");
     const DESCRIPTION: Option<&'static str> =
         Some("Fork of Debian without systemd that allows users to reclaim control over their system by avoiding unnecessary entanglements and ensuring Init Freedom.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let release_html = capture_page(DEVUAN_MIRROR).await?;
-        let release_regex = Regex::new(r#"href="(devuan_[a-zA-Z]+/)""#).unwrap();
-        let iso_regex = Arc::new(Regex::new(r#"href="(devuan_[a-zA-Z]+_([0-9.]+)_amd64_desktop-live.iso)""#).unwrap());
-        let checksum_url_regex = Arc::new(Regex::new(r#"href="(SHA[^.]+.txt)""#).unwrap());
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let release_regex = Regex::new(r"^devuan_[a-zA-Z]+/$").unwrap();
+        let releases = list_links_matching(DEVUAN_MIRROR, &release_regex)
+            .await
+            .or_network_error(DEVUAN_MIRROR)?;
+        let iso_regex = Arc::new(Regex::new(r"^devuan_[a-zA-Z]+_([0-9.]+)_amd64_desktop-live\.iso$").unwrap());
+        let checksum_url_regex = Arc::new(Regex::new(r"^SHA[^.]+\.txt$").unwrap());
 
-        let futures = release_regex.captures_iter(&release_html).map(|c| {
-            let mirror = DEVUAN_MIRROR.to_string() + &c[1] + "desktop-live/";
+        let futures = releases.into_iter().map(|release| {
+            let mirror = DEVUAN_MIRROR.to_string() + &release + "desktop-live/";
             let iso_regex = iso_regex.clone();
             let checksum_url_regex = checksum_url_regex.clone();
 
             async move {
-                let page_data = capture_page(&mirror).await?;
-                let mut checksums = match checksum_url_regex.captures(&page_data) {
-                    Some(c) => ChecksumSeparation::Whitespace.build(&(mirror.to_string() + &c[1])).await,
+                let links = list_links(&mirror).await?;
+                let mut checksums = match links.iter().find(|link| checksum_url_regex.is_match(link)) {
+                    Some(checksum_file) => Whitespace.fetch(&(mirror.to_string() + checksum_file)).await,
                     None => None,
                 };
 
                 Some(
-                    iso_regex
-                        .captures_iter(&page_data)
-                        .map(|c| {
-                            let release = c[2].to_string();
-                            let iso = &c[1];
+                    links
+                        .iter()
+                        .filter_map(|link| Some((link, iso_regex.captures(link)?)))
+                        .map(|(iso, c)| {
+                            let release = c[1].to_string();
                             let url = mirror.clone() + iso;
                             let checksum = checksums.as_mut().and_then(|cs| cs.remove(iso));
                             Config {
@@ -293,7 +544,7 @@ impl Distro for Devuan {
                 )
             }
         });
-        Some(join_futures!(futures, 2))
+        Ok(join_futures!(futures, 2))
     }
 }
 
@@ -305,8 +556,10 @@ impl Distro for EasyOS {
     const PRETTY_NAME: &'static str = "EasyOS";
     const HOMEPAGE: Option<&'static str> = Some("https://easyos.org/");
     const DESCRIPTION: Option<&'static str> = Some("Experimental distribution designed from scratch to support containers.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let release_html = capture_page(EASYOS_MIRROR).await?;
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Md5;
+    const RELEASE_LIMIT: usize = 5;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let release_html = capture_page(EASYOS_MIRROR).await.or_network_error(EASYOS_MIRROR)?;
         let release_name_regex = Regex::new(r#"href="([a-z]+/)""#).unwrap();
         let subdirectory_regex = Arc::new(Regex::new(r#"href="([0-9]{4}/)""#).unwrap());
         let release_regex = Arc::new(Regex::new(r#"href="([0-9](?:\.[0-9]+)+)/""#).unwrap());
@@ -364,17 +617,14 @@ impl Distro for EasyOS {
                 true
             }
         });
-        println!("{:?}", releases);
 
-        let futures = releases.into_iter().take(5).map(|(release, mirror)| {
+        let futures = releases.into_iter().take(Self::release_limit()).map(|(release, mirror)| {
             let img_regex = img_regex.clone();
 
             async move {
                 let page = capture_page(&mirror).await?;
                 let checksum_url = mirror.clone() + "md5sum.txt";
-                let checksum = capture_page(&checksum_url)
-                    .await
-                    .and_then(|cs| cs.split_whitespace().next().map(ToString::to_string));
+                let checksum = single_checksum(&checksum_url).await;
 
                 let img_capture = img_regex.captures(&page)?;
                 let url = mirror + &img_capture[1];
@@ -390,7 +640,188 @@ impl Distro for EasyOS {
                 })
             }
         });
-        Some(join_futures!(futures, 1))
+        Ok(join_futures!(futures, 1))
+    }
+}
+
+const PUPPY_MIRROR: &str = "https://distro.ibiblio.org/puppylinux/";
+/// Editions alongside the directory they're published in. Releases are either placed directly within it, or in a subdirectory named by version
+const PUPPY_EDITIONS: [(&str, &str); 2] = [("fossapup64", "puppy-fossa/"), ("bookwormpup64", "puppy-bookwormpup/BookwormPup64/")];
+
+pub struct PuppyLinux;
+impl Distro for PuppyLinux {
+    const NAME: &'static str = "puppylinux";
+    const PRETTY_NAME: &'static str = "Puppy Linux";
+    const HOMEPAGE: Option<&'static str> = Some("https://puppylinux-woof-ce.github.io/");
+    const DESCRIPTION: Option<&'static str> = Some("Family of tiny distributions which run entirely from memory, built by woof-CE from the packages of Ubuntu, Debian and others.");
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Md5;
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let iso_regex = Arc::new(Regex::new(r"(?i)^(?:fossapup64|bookwormpup64)[-_](\d+(?:\.\d+)*)\.iso$").unwrap());
+        let version_regex = Arc::new(Regex::new(r"^\d+(?:\.\d+)*/$").unwrap());
+        let release_limit = Self::release_limit();
+
+        let futures = PUPPY_EDITIONS.map(|(edition, directory)| {
+            let (iso_regex, version_regex) = (iso_regex.clone(), version_regex.clone());
+            async move {
+                let mirror = format!("{PUPPY_MIRROR}{directory}");
+                let links = list_links(&mirror).await?;
+                let subdirectories = links
+                    .iter()
+                    .filter(|l| version_regex.is_match(l))
+                    .map(|l| format!("{mirror}{l}"))
+                    .map(|mirror| async move { list_links(&mirror).await.map(|links| (mirror, links)) });
+                let mut directories = join_futures!(subdirectories, 1, Vec<(String, Vec<String>)>);
+                directories.push((mirror, links));
+
+                let mut isos = directories
+                    .iter()
+                    .flat_map(|(mirror, links)| {
+                        let iso_regex = &iso_regex;
+                        links.iter().filter_map(move |iso| {
+                            let release = iso_regex.captures(iso)?[1].to_string();
+                            let checksum_file = [format!("{iso}.md5.txt"), format!("{iso}.md5")]
+                                .into_iter()
+                                .find(|c| links.contains(c))
+                                .map(|c| format!("{mirror}{c}"));
+                            Some((release, format!("{mirror}{iso}"), checksum_file))
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                let version = |release: &str| {
+                    release
+                        .split('.')
+                        .map(|v| v.parse::<u32>().unwrap_or_default())
+                        .collect::<Vec<u32>>()
+                };
+                isos.sort_by_key(|(release, ..)| std::cmp::Reverse(version(release)));
+                isos.dedup_by(|(a, ..), (b, ..)| a == b);
+
+                let futures = isos
+                    .into_iter()
+                    .take(release_limit)
+                    .map(|(release, url, checksum_file)| async move {
+                        let checksum = match checksum_file {
+                            Some(checksum_file) => single_checksum(&checksum_file).await,
+                            None => None,
+                        };
+                        Config {
+                            release,
+                            edition: Some(edition.to_string()),
+                            iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                            ..Default::default()
+                        }
+                    });
+                Some(join_futures!(futures))
+            }
+        });
+        Ok(join_futures!(futures, 2))
+    }
+}
+
+const PUREOS_MIRROR: &str = "https://downloads.pureos.net/";
+/// Suites alongside whether they're still in development. Landing receives packages before they reach the next release
+const PUREOS_SUITES: [(&str, bool); 2] = [("byzantium", false), ("landing", true)];
+
+pub struct PureOS;
+impl Distro for PureOS {
+    const NAME: &'static str = "pureos";
+    const PRETTY_NAME: &'static str = "PureOS";
+    const HOMEPAGE: Option<&'static str> = Some("https://pureos.net/");
+    const DESCRIPTION: Option<&'static str> = Some("Fully free Debian based distribution endorsed by the Free Software Foundation, developed by Purism with a focus on privacy and security.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let date_regex = Arc::new(Regex::new(r"^\d{4}-\d{2}-\d{2}/$").unwrap());
+
+        let futures = PUREOS_SUITES.into_iter().flat_map(|(suite, development)| {
+            let date_regex = date_regex.clone();
+            ["gnome", "plasma"].into_iter().map(move |edition| {
+                let date_regex = date_regex.clone();
+                async move {
+                    let mirror = format!("{PUREOS_MIRROR}{suite}/{edition}/");
+                    // Each build is published in a directory named by its date, only the newest is of interest
+                    let date = list_links_matching(&mirror, &date_regex).await?.into_iter().max()?;
+                    let mirror = format!("{mirror}{date}");
+                    let iso_regex = Regex::new(&format!(r"^pureos-([^-]+)-{edition}-live-\d{{8}}_amd64\.iso$")).unwrap();
+                    let iso = list_links_matching(&mirror, &iso_regex).await?.into_iter().next()?;
+                    let release = iso_regex.captures(&iso)?[1].to_string();
+                    let url = format!("{mirror}{iso}");
+                    let checksum_url = format!("{mirror}{}.checksums_sha256.txt", iso.trim_end_matches(".iso"));
+                    let checksum = ChecksumSource::new().aggregate(checksum_url, Whitespace).find(&url).await;
+                    if development {
+                        ConfigMetadata::attach(&url, |m| m.channel = Some(Channel::Testing));
+                    }
+                    Some(Config {
+                        release,
+                        edition: Some(edition.to_string()),
+                        iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                        ..Default::default()
+                    })
+                }
+            })
+        });
+
+        Ok(join_futures!(futures, 1))
+    }
+}
+
+const ELIVE_MIRROR: &str = "https://sourceforge.net/projects/elive/files/";
+const ELIVE_CHANNELS: [(&str, bool); 2] = [("Elive%20Stable%20Releases/", false), ("Elive%20Beta%20versions/", true)];
+
+pub struct Elive;
+impl Distro for Elive {
+    const NAME: &'static str = "elive";
+    const PRETTY_NAME: &'static str = "Elive";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.elivecd.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Unique Linux distribution based on Debian and the Enlightenment desktop, focused on beauty and running well on older hardware.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let release_regex = Arc::new(Regex::new(r#""name":"(\d+\.\d+\.\d+)""#).unwrap());
+        let iso_regex = Arc::new(Regex::new(r#""name":"(elive_[^"]+?_64bit\.iso)""#).unwrap());
+
+        let futures = ELIVE_CHANNELS.iter().map(|(channel, prerelease)| {
+            let mirror = format!("{ELIVE_MIRROR}{channel}");
+            let release_regex = release_regex.clone();
+            let iso_regex = iso_regex.clone();
+            async move {
+                let page = capture_page(&mirror).await?;
+                // Only the newest beta is relevant, it will be superseded by the next stable release
+                let count = if *prerelease { 1 } else { 3 };
+                let futures = release_regex
+                    .captures_iter(&page)
+                    .take(count)
+                    .map(|c| {
+                        let release = c[1].to_string();
+                        let mirror = format!("{mirror}{release}/");
+                        let iso_regex = iso_regex.clone();
+                        async move {
+                            let page = capture_page(&mirror).await?;
+                            let iso = &iso_regex.captures(&page)?[1];
+                            let url = format!("{mirror}{iso}/download");
+                            let checksum_url = format!("{mirror}{iso}.sha256/download");
+                            let checksum = single_checksum(&checksum_url).await;
+                            if *prerelease {
+                                ConfigMetadata::attach(&url, |m| m.channel = Some(Channel::Testing));
+                            }
+                            Some(Config {
+                                release,
+                                iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, Some(iso.to_string())))]),
+                                ..Default::default()
+                            })
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                Some(join_futures!(futures, 1))
+            }
+        });
+
+        let mut channels = join_futures!(futures).into_iter().map(Option::unwrap_or_default);
+        let mut configs = channels.next().unwrap_or_default();
+        // A beta keeps the number it's later released under, by which point only the stable release is wanted
+        let betas = channels
+            .flatten()
+            .filter(|beta| !configs.iter().any(|config| config.release == beta.release))
+            .collect::<Vec<_>>();
+        configs.extend(betas);
+        Ok(configs)
     }
 }
 
@@ -403,8 +834,9 @@ impl Distro for EndlessOS {
     const PRETTY_NAME: &'static str = "Endless OS";
     const HOMEPAGE: Option<&'static str> = Some("https://endlessos.org/");
     const DESCRIPTION: Option<&'static str> = Some("Completely Free, User-Friendly Operating System Packed with Educational Tools, Games, and More.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let release_html = capture_page(ENDLESS_DATA_MIRROR).await?;
+    const IMMUTABLE: bool = true;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let release_html = capture_page(ENDLESS_DATA_MIRROR).await.or_network_error(ENDLESS_DATA_MIRROR)?;
         let release_regex = Regex::new(r#"href="(\d+(?:.\d+){2})\/""#).unwrap();
         let edition_regex = Arc::new(Regex::new(r#"href="([^./]+)"#).unwrap());
         let iso_regex = Arc::new(Regex::new(r#"href="(eos-eos[\d.]+-amd64-amd64.[-\d]+.[^.]+.iso)""#).unwrap());
@@ -427,9 +859,7 @@ impl Distro for EndlessOS {
                         let url = format!("{ENDLESS_DL_MIRROR}{release}/eos-amd64-amd64/{edition}/{iso}");
 
                         let checksum_url = url.clone() + ".sha256";
-                        let checksum = capture_page(&checksum_url)
-                            .await
-                            .and_then(|cs| cs.split_whitespace().next().map(ToString::to_string));
+                        let checksum = single_checksum(&checksum_url).await;
                         Some(Config {
                             release,
                             edition: Some(edition),
@@ -442,6 +872,269 @@ impl Distro for EndlessOS {
             }
         });
 
-        Some(join_futures!(futures, 3))
+        Ok(join_futures!(futures, 3))
     }
 }
+
+const MX_PROJECT: &str = "mx-linux";
+
+pub struct MXLinux;
+impl Distro for MXLinux {
+    const NAME: &'static str = "mxlinux";
+    const PRETTY_NAME: &'static str = "MX Linux";
+    const HOMEPAGE: Option<&'static str> = Some("https://mxlinux.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Midweight desktop distribution based on Debian Stable and antiX, combining elegant and efficient desktops with simple configuration.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let files_url = SourceForgeAPI::files_url(MX_PROJECT, "Final");
+        let files = SourceForgeAPI::gather_data(&files_url).await.or_network_error(&files_url)?;
+        // Xfce ISOs don't name their desktop. AHS (Advanced Hardware Support) builds are Xfce with a newer kernel and graphics stack
+        let iso_regex = Regex::new(r"^/Final/[^/]+/MX-([\d.]+)(?:_(ahs|fluxbox|KDE))?_x64\.iso$").unwrap();
+
+        let mut isos = files
+            .iter()
+            .filter_map(|f| {
+                let c = iso_regex.captures(&f.path)?;
+                let edition = c.get(2).map_or("xfce", |e| e.as_str()).to_lowercase();
+                Some((c[1].to_string(), edition, f))
+            })
+            .collect::<Vec<_>>();
+        isos.sort_by_cached_key(|(release, ..)| std::cmp::Reverse(version_key(release)));
+        let mut releases = isos.iter().map(|(release, ..)| release.clone()).collect::<Vec<String>>();
+        releases.dedup();
+        releases.truncate(Self::release_limit());
+
+        let futures = isos
+            .into_iter()
+            .filter(|(release, ..)| releases.contains(release))
+            .map(|(release, edition, iso)| {
+                let url = iso.download_url.clone();
+                let file_name = iso.name().to_string();
+                let checksum_url = iso.sibling(&files, ".sha256").map(|f| f.download_url.clone());
+                async move {
+                    let checksum = match checksum_url {
+                        Some(checksum_url) => single_checksum(&checksum_url).await,
+                        None => None,
+                    };
+                    Config {
+                        release,
+                        edition: Some(edition),
+                        iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, Some(file_name)))]),
+                        ..Default::default()
+                    }
+                }
+            });
+
+        Ok(join_futures!(futures))
+    }
+}
+
+const PEPPERMINT_PROJECT: &str = "peppermintos";
+
+pub struct Peppermint;
+impl Distro for Peppermint {
+    const NAME: &'static str = "peppermint";
+    const PRETTY_NAME: &'static str = "Peppermint OS";
+    const HOMEPAGE: Option<&'static str> = Some("https://peppermintos.com/");
+    const DESCRIPTION: Option<&'static str> = Some("Lightweight, stable desktop distribution built on Debian or Devuan, with a focus on user control and flexibility.");
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Sha512;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let files_url = SourceForgeAPI::files_url(PEPPERMINT_PROJECT, "isos");
+        let files = SourceForgeAPI::gather_data(&files_url).await.or_network_error(&files_url)?;
+        // ISOs are rebuilt under the same name, within a directory named after their desktop
+        let iso_regex = Regex::new(r"^/isos/(?:[^/]+/)*?([^/]+)/PeppermintOS-(Debian|Devuan)[_-](ARM[_-])?64\.iso$").unwrap();
+
+        // The feed is newest first, so only the first ISO of each edition is kept
+        let mut editions = HashSet::new();
+        let futures = files
+            .iter()
+            .filter_map(|f| {
+                let c = iso_regex.captures(&f.path)?;
+                let edition = format!("{}-{}", &c[2], &c[1]).to_lowercase();
+                let arch = if c.get(3).is_some() { Arch::aarch64 } else { Arch::x86_64 };
+                Some((edition, arch, f))
+            })
+            .filter(|(edition, arch, _)| editions.insert((edition.clone(), arch.to_string())))
+            .map(|(edition, arch, iso)| {
+                let url = iso.download_url.clone();
+                let file_name = iso.name().to_string();
+                let checksum_url = iso.sibling(&files, ".sha512").map(|f| f.download_url.clone());
+                async move {
+                    let checksum = match checksum_url {
+                        Some(checksum_url) => single_checksum(&checksum_url).await,
+                        None => None,
+                    };
+                    Config {
+                        release: "latest".to_string(),
+                        edition: Some(edition),
+                        arch,
+                        iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, Some(file_name)))]),
+                        ..Default::default()
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Ok(join_futures!(futures))
+    }
+}
+
+const Q4OS_PROJECT: &str = "q4os";
+
+pub struct Q4OS;
+impl Distro for Q4OS {
+    const NAME: &'static str = "q4os";
+    const PRETTY_NAME: &'static str = "Q4OS";
+    const HOMEPAGE: Option<&'static str> = Some("https://q4os.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Fast and friendly desktop distribution based on Debian, offering the Plasma and Trinity desktops.");
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Md5;
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let files_url = SourceForgeAPI::files_url(Q4OS_PROJECT, "stable");
+        let files = SourceForgeAPI::gather_data(&files_url).await.or_network_error(&files_url)?;
+        // Trinity ISOs are marked with tde, the others ship Plasma. Rebuilds of a release increment the number after `r`
+        let iso_regex = Regex::new(r"^/stable/q4os-([\d.]+)-x64(-tde)?\.r(\d+)\.iso$").unwrap();
+
+        let mut isos = files
+            .iter()
+            .filter_map(|f| {
+                let c = iso_regex.captures(&f.path)?;
+                let edition = if c.get(2).is_some() { "trinity" } else { "plasma" };
+                Some((c[1].to_string(), edition, c[3].parse::<u32>().ok()?, f))
+            })
+            .collect::<Vec<_>>();
+        isos.sort_by_cached_key(|(release, edition, rebuild, _)| std::cmp::Reverse((version_key(release), *edition, *rebuild)));
+        isos.dedup_by(|a, b| a.0 == b.0 && a.1 == b.1);
+        let mut releases = isos.iter().map(|(release, ..)| release.clone()).collect::<Vec<String>>();
+        releases.dedup();
+        releases.truncate(Self::release_limit());
+
+        // SourceForge publishes the MD5 of every file, Q4OS doesn't provide checksum files
+        Ok(isos
+            .into_iter()
+            .filter(|(release, ..)| releases.contains(release))
+            .map(|(release, edition, _, iso)| Config {
+                release,
+                edition: Some(edition.to_string()),
+                iso: Some(vec![Source::Web(WebSource::new(
+                    iso.download_url.clone(),
+                    iso.md5.clone(),
+                    None,
+                    Some(iso.name().to_string()),
+                ))]),
+                ..Default::default()
+            })
+            .collect::<Vec<Config>>())
+    }
+}
+
+const SPARKY_PROJECT: &str = "sparkylinux";
+
+pub struct SparkyLinux;
+impl Distro for SparkyLinux {
+    const NAME: &'static str = "sparkylinux";
+    const PRETTY_NAME: &'static str = "SparkyLinux";
+    const HOMEPAGE: Option<&'static str> = Some("https://sparkylinux.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Fast, lightweight and fully customizable distribution based on Debian, with stable releases and a rolling edition based on Debian testing.");
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Md5;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let files_url = SourceForgeAPI::files_url(SPARKY_PROJECT, "/");
+        let files = SourceForgeAPI::gather_data(&files_url).await.or_network_error(&files_url)?;
+        // Stable releases are versioned like 7.5, rolling builds after the year and month they were made, like 2024.09
+        let iso_regex = Regex::new(r"^/[^/]+/sparkylinux-(\d+)\.(\d+)-x86_64-([a-z]+)\.iso$").unwrap();
+
+        let mut isos = files
+            .iter()
+            .filter_map(|f| {
+                let c = iso_regex.captures(&f.path)?;
+                let version = (c[1].parse::<u32>().ok()?, c[2].parse::<u32>().ok()?);
+                Some((version, c[3].to_string(), f))
+            })
+            .collect::<Vec<_>>();
+        isos.sort_by_key(|(version, ..)| std::cmp::Reverse(*version));
+        let rolling = |(major, _): &(u32, u32)| *major >= 2000;
+        let mut stable_releases = isos
+            .iter()
+            .map(|(version, ..)| *version)
+            .filter(|v| !rolling(v))
+            .collect::<Vec<_>>();
+        stable_releases.dedup();
+        stable_releases.truncate(Self::release_limit());
+        // Only the newest rolling build is relevant, older ones are replaced by it
+        let latest_rolling = isos.iter().map(|(version, ..)| *version).find(rolling);
+
+        // SourceForge publishes the MD5 of every file, which Sparky's own checksum files also list
+        Ok(isos
+            .into_iter()
+            .filter(|(version, ..)| stable_releases.contains(version) || Some(*version) == latest_rolling)
+            .map(|(version, edition, iso)| {
+                let release = if rolling(&version) { "rolling".to_string() } else { format!("{}.{}", version.0, version.1) };
+                Config {
+                    release,
+                    edition: Some(edition),
+                    iso: Some(vec![Source::Web(WebSource::new(
+                        iso.download_url.clone(),
+                        iso.md5.clone(),
+                        None,
+                        Some(iso.name().to_string()),
+                    ))]),
+                    ..Default::default()
+                }
+            })
+            .collect::<Vec<Config>>())
+    }
+}
+
+const UOS_MIRROR: &str = "https://cdimage-download.chinauos.com/";
+
+pub struct UnionTechOS;
+impl Distro for UnionTechOS {
+    const NAME: &'static str = "uos";
+    const PRETTY_NAME: &'static str = "UnionTech OS";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.chinauos.com/");
+    const DESCRIPTION: Option<&'static str> = Some("Commercial distribution built upon deepin, whose community edition is freely available for home use.");
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Md5;
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(UOS_MIRROR).await.or_network_error(UOS_MIRROR)?;
+        // Only the community (home) edition can be downloaded without activation
+        let iso_regex = Regex::new(r#"href="(uos-desktop-(\d+(?:\.\d+)*)-home-(amd64|arm64)\.iso)""#).unwrap();
+
+        let mut isos = iso_regex
+            .captures_iter(&page)
+            .filter_map(|c| {
+                let (_, [iso, release, arch]) = c.extract();
+                Some((release.to_string(), arch_from_str(arch)?, iso.to_string()))
+            })
+            .collect::<Vec<_>>();
+        isos.sort_by_cached_key(|(release, _, _)| {
+            release
+                .split('.')
+                .map(|n| n.parse::<u32>().unwrap_or_default())
+                .collect::<Vec<u32>>()
+        });
+        let mut releases = isos.iter().map(|(release, _, _)| release.clone()).collect::<Vec<String>>();
+        releases.dedup();
+        let releases = releases.into_iter().rev().take(Self::release_limit()).collect::<Vec<String>>();
+
+        let futures = isos
+            .into_iter()
+            .filter(|(release, _, _)| releases.contains(release))
+            .map(|(release, arch, iso)| async move {
+                let url = format!("{UOS_MIRROR}{iso}");
+                let checksum = single_checksum(&format!("{url}.md5sum")).await;
+                Config {
+                    release,
+                    arch,
+                    iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                    ..Default::default()
+                }
+            });
+
+        Ok(join_futures!(futures))
+    }
+}
+
+/// Orders dotted version numbers numerically, rather than as strings
+fn version_key(release: &str) -> Vec<u32> {
+    release.split('.').map(|n| n.parse::<u32>().unwrap_or_default()).collect()
+}
@@ -1,12 +1,23 @@
 use crate::{
-    store_data::{Arch, ChecksumSeparation, Config, Distro, Source, WebSource},
-    utils::{arch_from_str, capture_page, FedoraRelease, GatherData},
+    checksums::{single_checksum, Bsd, ChecksumFormat, Whitespace},
+    error::{GenerateError, OrGenerateError},
+    store_data::{
+        cloud_image, extract_disk_urls, filter_web_sources, Arch, ChecksumAlgorithm, ChecksumFileFormat, ChecksumSource, CloudInit, Config, ConfigMetadata, Distro, Signature, Source, SourceMetadata,
+        WebSource,
+    },
+    utils::{arch_from_str, capture_page, list_links, FedoraRelease, GatherData},
 };
 use join_futures::join_futures;
 use quickemu::config::DiskFormat;
 use quickget_core::data_structures::{ArchiveFormat, Disk};
 use regex::Regex;
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
 const ALMA_MIRROR: &str = "https://repo.almalinux.org/almalinux/";
 
@@ -16,8 +27,9 @@ impl Distro for Alma {
     const PRETTY_NAME: &'static str = "AlmaLinux";
     const HOMEPAGE: Option<&'static str> = Some("https://almalinux.org/");
     const DESCRIPTION: Option<&'static str> = Some("Community owned and governed, forever-free enterprise Linux distribution, focused on long-term stability, providing a robust production-grade platform. AlmaLinux OS is binary compatible with RHEL®.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let releases = capture_page(ALMA_MIRROR).await?;
+    const EOL_PRODUCT: Option<&'static str> = Some("almalinux");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let releases = capture_page(ALMA_MIRROR).await.or_network_error(ALMA_MIRROR)?;
 
         let releases_regex = Regex::new(r#"<a href="([0-9]+)/""#).unwrap();
         let iso_regex = Arc::new(Regex::new(r#"<a href="(AlmaLinux-[0-9]+-latest-(?:x86_64|aarch64)-([^-]+).iso)">"#).unwrap());
@@ -33,38 +45,104 @@ impl Distro for Alma {
 
                     async move {
                         let page = capture_page(&mirror).await?;
-                        let mut checksums = ChecksumSeparation::Sha256Regex.build(&format!("{mirror}CHECKSUM")).await;
-
-                        Some(
-                            iso_regex
-                                .captures_iter(&page)
-                                .map(|c| c.extract())
-                                .filter(|(capture, _)| !capture.ends_with(".manifest"))
-                                .map(|(_, [iso, edition])| {
-                                    let url = format!("{mirror}{iso}");
-                                    let checksum = checksums.as_mut().and_then(|cs| cs.remove(iso));
-                                    Config {
-                                        release: release.to_string(),
-                                        edition: Some(edition.to_string()),
-                                        arch: arch.clone(),
-                                        iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
-                                        ..Default::default()
-                                    }
-                                })
-                                .collect::<Vec<Config>>(),
-                        )
+                        let mut checksums = Bsd(ChecksumAlgorithm::Sha256).fetch(&format!("{mirror}CHECKSUM")).await;
+
+                        let mut configs = iso_regex
+                            .captures_iter(&page)
+                            .map(|c| c.extract())
+                            .filter(|(capture, _)| !capture.ends_with(".manifest"))
+                            .map(|(_, [iso, edition])| {
+                                let url = format!("{mirror}{iso}");
+                                let checksum = checksums.as_mut().and_then(|cs| cs.remove(iso));
+                                Config {
+                                    release: release.to_string(),
+                                    edition: Some(edition.to_string()),
+                                    arch: arch.clone(),
+                                    iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                                    ..Default::default()
+                                }
+                            })
+                            .collect::<Vec<Config>>();
+
+                        let cloud_mirror = format!("{ALMA_MIRROR}{release}/cloud/{arch}/images/");
+                        let url = format!("{cloud_mirror}AlmaLinux-{release}-GenericCloud-latest.{arch}.qcow2");
+                        let checksum = ChecksumSource::new()
+                            .aggregate(format!("{cloud_mirror}CHECKSUM"), Bsd(ChecksumAlgorithm::Sha256))
+                            .find(&url)
+                            .await;
+                        configs.push(cloud_image(
+                            release,
+                            arch.clone(),
+                            WebSource::new(url, checksum, None, None),
+                            "almalinux",
+                        ));
+                        Some(configs)
                     }
                 })
                 .collect::<Vec<_>>()
         });
 
-        Some(join_futures!(futures, 2))
+        Ok(join_futures!(futures, 2))
     }
 }
 
-const BAZZITE_WORKFLOW: &str = "https://raw.githubusercontent.com/ublue-os/bazzite/main/.github/workflows/build_iso.yml";
-const BAZZITE_EXCLUDE: [&str; 3] = ["nvidia", "ally", "asus"];
-const BAZZITE_MIRROR: &str = "https://download.bazzite.gg/";
+/// Universal Blue images are built from a matrix in each project's ISO workflow, where every image is listed as `- image-name`
+struct UBlueImages {
+    workflow: &'static str,
+    mirror: &'static str,
+    /// Name of the base image, variants are suffixed to it, e.g. bluefin-dx
+    image: &'static str,
+    /// Each published stream, alongside the release it's presented as
+    streams: &'static [(&'static str, &'static str)],
+    /// Variants which are of no use within a VM, such as those built for specific hardware
+    exclude: &'static [&'static str],
+}
+
+impl UBlueImages {
+    async fn configs(self, edition: fn(&str) -> String) -> Result<Vec<Config>, GenerateError> {
+        let workflow = capture_page(self.workflow).await.or_network_error(self.workflow)?;
+        let image_regex = Regex::new(&format!(r#"- ({}-?(.*))"#, regex::escape(self.image))).unwrap();
+        let mut images = image_regex
+            .captures_iter(&workflow)
+            .map(|c| c.extract())
+            .map(|(_, [image, variant])| (image.trim().to_string(), variant.trim().to_string()))
+            .filter(|(_, variant)| !self.exclude.iter().any(|e| variant.contains(e)))
+            .collect::<Vec<_>>();
+        images.sort_unstable();
+        images.dedup();
+
+        let futures = images
+            .into_iter()
+            .flat_map(|(image, variant)| {
+                let edition = edition(&variant);
+                self.streams.iter().map(move |(stream, release)| {
+                    let url = format!("{}{image}-{stream}.iso", self.mirror);
+                    let edition = edition.clone();
+                    async move {
+                        let checksum_url = url.clone() + "-CHECKSUM";
+                        let checksum = single_checksum(&checksum_url).await;
+                        Config {
+                            release: release.to_string(),
+                            edition: Some(edition),
+                            iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                            ..Default::default()
+                        }
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(join_futures!(futures))
+    }
+}
+
+/// Developer experience (-dx) variants add container and virtualization tooling to the base image
+fn ublue_edition(variant: &str) -> String {
+    match variant {
+        "" => "base".to_string(),
+        _ => variant.to_string(),
+    }
+}
 
 pub struct Bazzite;
 impl Distro for Bazzite {
@@ -72,40 +150,62 @@ impl Distro for Bazzite {
     const PRETTY_NAME: &'static str = "Bazzite";
     const HOMEPAGE: Option<&'static str> = Some("https://bazzite.gg/");
     const DESCRIPTION: Option<&'static str> = Some("Container native gaming and a ready-to-game SteamOS like.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let workflow = capture_page(BAZZITE_WORKFLOW).await?;
-        let workflow_capture_regex = Regex::new(r#"- (bazzite-?(.*))"#).unwrap();
-
-        let futures = workflow_capture_regex
-            .captures_iter(&workflow)
-            .map(|c| c.extract())
-            .map(|(_, [iso, edition_capture])| {
-                let edition = match edition_capture.len() {
-                    0 => "plasma".to_string(),
-                    1..=4 => format!("{edition_capture}-plasma"),
-                    _ => edition_capture.to_string(),
-                };
-                let url = format!("{BAZZITE_MIRROR}{iso}-stable.iso");
+    const IMMUTABLE: bool = true;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        UBlueImages {
+            workflow: "https://raw.githubusercontent.com/ublue-os/bazzite/main/.github/workflows/build_iso.yml",
+            mirror: "https://download.bazzite.gg/",
+            image: "bazzite",
+            streams: &[("stable", "latest")],
+            exclude: &["nvidia", "ally", "asus"],
+        }
+        .configs(|variant| match variant.len() {
+            0 => "plasma".to_string(),
+            1..=4 => format!("{variant}-plasma"),
+            _ => variant.to_string(),
+        })
+        .await
+    }
+}
 
-                async move {
-                    if BAZZITE_EXCLUDE.iter().any(|e| edition.contains(e)) {
-                        return None;
-                    }
-                    let checksum_url = url.clone() + "-CHECKSUM";
-                    let checksum = capture_page(&checksum_url)
-                        .await
-                        .and_then(|c| c.split_whitespace().next().map(ToString::to_string));
-                    Some(Config {
-                        release: "latest".to_string(),
-                        edition: Some(edition),
-                        iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
-                        ..Default::default()
-                    })
-                }
-            })
-            .collect::<Vec<_>>();
+pub struct Aurora;
+impl Distro for Aurora {
+    const NAME: &'static str = "aurora";
+    const PRETTY_NAME: &'static str = "Aurora";
+    const HOMEPAGE: Option<&'static str> = Some("https://getaurora.dev/");
+    const DESCRIPTION: Option<&'static str> = Some("Delightful KDE desktop experience for end-users that are looking for reliability and developers for the most-hassle free setup.");
+    const IMMUTABLE: bool = true;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        UBlueImages {
+            workflow: "https://raw.githubusercontent.com/ublue-os/aurora/main/.github/workflows/build-iso.yml",
+            mirror: "https://dl.getaurora.dev/",
+            image: "aurora",
+            streams: &[("stable", "stable"), ("latest", "latest")],
+            exclude: &["nvidia", "asus", "surface"],
+        }
+        .configs(ublue_edition)
+        .await
+    }
+}
 
-        Some(join_futures!(futures, 1))
+pub struct Bluefin;
+impl Distro for Bluefin {
+    const NAME: &'static str = "bluefin";
+    const PRETTY_NAME: &'static str = "Bluefin";
+    const HOMEPAGE: Option<&'static str> = Some("https://projectbluefin.io/");
+    const DESCRIPTION: Option<&'static str> = Some("Next generation Linux workstation, designed for reliability, performance, and sustainability.");
+    const IMMUTABLE: bool = true;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        UBlueImages {
+            workflow: "https://raw.githubusercontent.com/ublue-os/bluefin/main/.github/workflows/build-iso.yml",
+            mirror: "https://download.projectbluefin.io/",
+            image: "bluefin",
+            // GTS follows the previous Fedora release, for those who prefer to wait for new versions to mature
+            streams: &[("gts", "gts"), ("stable", "stable"), ("latest", "latest")],
+            exclude: &["nvidia", "asus", "surface"],
+        }
+        .configs(ublue_edition)
+        .await
     }
 }
 
@@ -120,8 +220,9 @@ impl Distro for CentOSStream {
     const HOMEPAGE: Option<&'static str> = Some("https://www.centos.org/centos-stream/");
     const DESCRIPTION: Option<&'static str> =
         Some("Continuously delivered distro that tracks just ahead of Red Hat Enterprise Linux (RHEL) development, positioned as a midstream between Fedora Linux and RHEL.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let releases = capture_page(CENTOS_MIRROR).await?;
+    const EOL_PRODUCT: Option<&'static str> = Some("centos-stream");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let releases = capture_page(CENTOS_MIRROR).await.or_network_error(CENTOS_MIRROR)?;
         let release_regex = Regex::new(r#"href="([0-9]+)-stream/""#).unwrap();
         let iso_regex = Arc::new(Regex::new(r#"href="(CentOS-Stream-[0-9]+-[0-9]{8}.0-[^-]+-([^-]+)\.iso)""#).unwrap());
 
@@ -141,19 +242,20 @@ impl Distro for CentOSStream {
 
                         async move {
                             let page = capture_page(&mirror).await?;
-                            let mut checksums = ChecksumSeparation::Sha256Regex.build(&checksum_url).await;
+                            let mut checksums = Bsd(ChecksumAlgorithm::Sha256).fetch(&checksum_url).await;
                             Some(
                                 iso_regex
                                     .captures_iter(&page)
                                     .map(|c| c.extract())
                                     .map(|(_, [iso, edition])| {
+                                        // Downloads are redirected through the mirror list, whose URL ends in its query string
                                         let url = format!("{final_mirror}{iso}{CENTOS_URL_SUFFIX}");
                                         let checksum = checksums.as_mut().and_then(|cs| cs.remove(iso));
                                         Config {
                                             release: release.clone(),
                                             edition: Some(edition.to_string()),
                                             arch: arch.clone(),
-                                            iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                                            iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, Some(iso.to_string())))]),
                                             ..Default::default()
                                         }
                                     })
@@ -165,13 +267,31 @@ impl Distro for CentOSStream {
             })
             .collect::<Vec<_>>();
 
-        Some(join_futures!(futures, 2))
+        Ok(join_futures!(futures, 2))
     }
 }
 
 const FEDORA_RELEASE_URL: &str = "https://fedoraproject.org/releases.json";
+const FEDORA_TORRENT_URL: &str = "https://torrent.fedoraproject.org/torrents/";
+// Each release is signed with its own key, listed at https://fedoraproject.org/security/
+const FEDORA_SIGNING_KEYS: [(&str, &str); 4] = [
+    ("39", "E8F23996F23218640CB44CBE75CF5AC418B8E74C"),
+    ("40", "115DF9AEF857853EE8445D0A0727707EA15B79CC"),
+    ("41", "466CF2D8B60BC3057AA9453ED0622462E99D6AD1"),
+    ("42", "B0F4950458F69E1150C6C5EDC8AC4916105EF944"),
+];
 const VALID_FEDORA_FILETYPES: [&str; 2] = ["raw.xz", "iso"];
-const BLACKLISTED_EDITIONS: [&str; 2] = ["Server", "Cloud_Base"];
+// Fedora's desktop editions, Spins and Labs are all included. Server and the Cloud Base disk images are rarely wanted as desktop VMs
+const OPT_IN_EDITIONS: [&str; 2] = ["Server", "Cloud_Base"];
+// Atomic desktops install an rpm-ostree image rather than individual packages. Sericea and Onyx were renamed Sway Atomic and Budgie Atomic
+const IMMUTABLE_EDITIONS: [&str; 7] = ["Silverblue", "Kinoite", "Sericea", "Onyx", "Sway_Atomic", "Budgie_Atomic", "COSMIC_Atomic"];
+
+static INCLUDE_SERVER_EDITIONS: AtomicBool = AtomicBool::new(false);
+
+/// Include the Server edition and the Cloud Base disk images, must be called before any Fedora configs are generated
+pub fn include_fedora_server_editions() {
+    INCLUDE_SERVER_EDITIONS.store(true, Ordering::Relaxed);
+}
 
 pub struct Fedora;
 impl Distro for Fedora {
@@ -179,10 +299,22 @@ impl Distro for Fedora {
     const PRETTY_NAME: &'static str = "Fedora";
     const HOMEPAGE: Option<&'static str> = Some("https://fedoraproject.org/");
     const DESCRIPTION: Option<&'static str> = Some("Innovative platform for hardware, clouds, and containers, built with love by you.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let mut releases = FedoraRelease::gather_data(FEDORA_RELEASE_URL).await?;
+    const EOL_PRODUCT: Option<&'static str> = Some("fedora");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let mut releases = FedoraRelease::gather_data(FEDORA_RELEASE_URL)
+            .await
+            .or_network_error(FEDORA_RELEASE_URL)?;
+        let cloud_images = releases
+            .iter()
+            .filter(|r| r.edition == "Cloud_Base" && r.link.ends_with(".qcow2"))
+            .filter_map(|r| {
+                let source = WebSource::new(r.link.clone(), r.sha256.clone(), None, None);
+                Some(cloud_image(r.release.clone(), arch_from_str(&r.arch)?, source, "fedora"))
+            })
+            .collect::<Vec<Config>>();
         // Filter out unwanted filetypes and editions
-        releases.retain(|FedoraRelease { link, edition, .. }| VALID_FEDORA_FILETYPES.iter().any(|ext| link.ends_with(ext)) && !BLACKLISTED_EDITIONS.iter().any(|e| edition == e));
+        let include_server = INCLUDE_SERVER_EDITIONS.load(Ordering::Relaxed);
+        releases.retain(|FedoraRelease { link, edition, .. }| VALID_FEDORA_FILETYPES.iter().any(|ext| link.ends_with(ext)) && (include_server || !OPT_IN_EDITIONS.contains(&edition.as_str())));
 
         releases
             .iter_mut()
@@ -190,11 +322,25 @@ impl Distro for Fedora {
                 if link.ends_with("raw.xz") {
                     *edition += "_preinstalled";
                     *archive_format = Some(ArchiveFormat::Xz);
+                } else if link.contains("-netinst-") && edition != "Everything" {
+                    // Server publishes both a full DVD and a network installer, Everything only has the latter
+                    *edition += "_netinst";
                 }
             });
-        releases.dedup_by(|a, b| a.release == b.release && a.edition == b.edition);
+        // Only the first image of each edition is kept, but every architecture it's built for is
+        let mut seen = HashSet::new();
+        releases.retain(|r| seen.insert((r.release.clone(), r.edition.clone(), r.arch.clone())));
 
-        releases
+        // Torrents are named after the ISO, minus the compose number
+        let torrent_regex = Regex::new(r#"href="(?:[^"]*/)?(Fedora-[^"/]+)\.torrent""#).unwrap();
+        let torrent_page = capture_page(FEDORA_TORRENT_URL).await.unwrap_or_default();
+        let torrents: HashSet<&str> = torrent_regex
+            .captures_iter(&torrent_page)
+            .map(|c| c.extract::<1>().1[0])
+            .collect();
+        let compose_regex = Regex::new(r"/(Fedora-[^/]+)-\d+(?:\.\d+)*\.iso$").unwrap();
+
+        let configs = releases
             .into_iter()
             .filter_map(
                 |FedoraRelease {
@@ -206,6 +352,14 @@ impl Distro for Fedora {
                      sha256,
                  }| {
                     let is_disk_image = archive_format.is_some();
+                    if IMMUTABLE_EDITIONS.contains(&edition.as_str()) {
+                        ConfigMetadata::attach(&link, |m| m.immutable = true);
+                    }
+                    if let Some(name) = compose_regex.captures(&link).map(|c| c.extract::<1>().1[0]) {
+                        if torrents.contains(name) {
+                            SourceMetadata::attach(&link, |m| m.torrent = Some(format!("{FEDORA_TORRENT_URL}{name}.torrent")));
+                        }
+                    }
                     let source = Source::Web(WebSource::new(link, sha256, archive_format, None));
                     let arch = arch_from_str(&arch)?;
                     let mut config = Config {
@@ -226,7 +380,221 @@ impl Distro for Fedora {
                     Some(config)
                 },
             )
-            .collect::<Vec<Config>>()
-            .into()
+            .chain(cloud_images)
+            .collect::<Vec<Config>>();
+        attach_fedora_signatures(&configs).await;
+        Ok(configs)
+    }
+}
+
+/// Each image directory has a clearsigned CHECKSUM file listing the checksums of the images within it
+async fn attach_fedora_signatures(configs: &[Config]) {
+    let directories = configs
+        .iter()
+        .flat_map(|config| {
+            let urls = [filter_web_sources(config.iso.as_deref()), extract_disk_urls(config.disk_images.as_deref())].concat();
+            urls.into_iter().map(|url| (config.release.clone(), url))
+        })
+        .filter_map(|(release, url)| Some((release, url.rsplit_once('/')?.0.to_string() + "/", url)))
+        .fold(
+            HashMap::<(String, String), Vec<String>>::new(),
+            |mut directories, (release, directory, url)| {
+                directories.entry((release, directory)).or_default().push(url);
+                directories
+            },
+        );
+
+    let futures = directories.into_iter().map(|((release, directory), urls)| async move {
+        let Some((_, key)) = FEDORA_SIGNING_KEYS.iter().find(|(r, _)| *r == release) else {
+            tracing::warn!("No signing key is known for Fedora {release}, its checksums can't be verified");
+            return;
+        };
+        let Some(links) = list_links(&directory).await else {
+            return;
+        };
+        let mut checksum_files = links.iter().filter(|link| link.ends_with("-CHECKSUM"));
+        let (Some(checksum_file), None) = (checksum_files.next(), checksum_files.next()) else {
+            tracing::warn!("{directory}: Expected a single CHECKSUM file, its checksums can't be verified");
+            return;
+        };
+        let checksum_file = format!("{directory}{checksum_file}");
+        let signature = Signature::checksum_file(checksum_file.clone(), checksum_file, ChecksumFileFormat::Bsd, key);
+        for url in urls {
+            SourceMetadata::attach(&url, |m| m.signature = Some(signature.clone()));
+        }
+    });
+    futures::future::join_all(futures).await;
+}
+
+const ROCKY_MIRROR: &str = "https://download.rockylinux.org/pub/rocky/";
+
+pub struct Rocky;
+impl Distro for Rocky {
+    const NAME: &'static str = "rockylinux";
+    const PRETTY_NAME: &'static str = "Rocky Linux";
+    const HOMEPAGE: Option<&'static str> = Some("https://rockylinux.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Open-source enterprise operating system designed to be 100% bug-for-bug compatible with Red Hat Enterprise Linux®.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let releases = capture_page(ROCKY_MIRROR).await.or_network_error(ROCKY_MIRROR)?;
+
+        let releases_regex = Regex::new(r#"<a href="([0-9]+)/""#).unwrap();
+        let iso_regex = Arc::new(Regex::new(r#"<a href="(Rocky-[0-9.]+-(?:x86_64|aarch64)-(minimal|dvd|boot)1?\.iso)">"#).unwrap());
+
+        let futures = releases_regex.captures_iter(&releases).flat_map(|c| {
+            let release = c[1].to_string();
+            [Arch::x86_64, Arch::aarch64]
+                .iter()
+                .map(|arch| {
+                    let release = release.to_string();
+                    let iso_regex = iso_regex.clone();
+                    let mirror = format!("{ROCKY_MIRROR}{release}/isos/{arch}/");
+
+                    async move {
+                        let page = capture_page(&mirror).await?;
+                        let mut checksums = Bsd(ChecksumAlgorithm::Sha256).fetch(&format!("{mirror}CHECKSUM")).await;
+
+                        let mut configs = iso_regex
+                            .captures_iter(&page)
+                            .map(|c| c.extract())
+                            .map(|(_, [iso, edition])| {
+                                let url = format!("{mirror}{iso}");
+                                let checksum = checksums.as_mut().and_then(|cs| cs.remove(iso));
+                                Config {
+                                    release: release.to_string(),
+                                    edition: Some(edition.to_string()),
+                                    arch: arch.clone(),
+                                    iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                                    ..Default::default()
+                                }
+                            })
+                            .collect::<Vec<Config>>();
+
+                        let url = format!("{ROCKY_MIRROR}{release}/images/{arch}/Rocky-{release}-GenericCloud-Base.latest.{arch}.qcow2");
+                        let checksum = ChecksumSource::new()
+                            .aggregate(format!("{url}.CHECKSUM"), Bsd(ChecksumAlgorithm::Sha256))
+                            .find(&url)
+                            .await;
+                        configs.push(cloud_image(
+                            release,
+                            arch.clone(),
+                            WebSource::new(url, checksum, None, None),
+                            "rocky",
+                        ));
+                        Some(configs)
+                    }
+                })
+                .collect::<Vec<_>>()
+        });
+
+        Ok(join_futures!(futures, 2))
+    }
+}
+
+const ORACLE_ISO_PAGE: &str = "https://yum.oracle.com/oracle-linux-isos.html";
+
+pub struct OracleLinux;
+impl Distro for OracleLinux {
+    const NAME: &'static str = "oraclelinux";
+    const PRETTY_NAME: &'static str = "Oracle Linux";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.oracle.com/linux/");
+    const DESCRIPTION: Option<&'static str> = Some("Open and enterprise-grade Linux distribution, binary compatible with Red Hat Enterprise Linux®, freely available from Oracle.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(ORACLE_ISO_PAGE).await.or_network_error(ORACLE_ISO_PAGE)?;
+        // Each ISO's SHA256 checksum is listed in the table row following its link
+        let iso_regex = Regex::new(
+            r#"(?s)href="(https://yum\.oracle\.com/ISOS/OracleLinux/OL[0-9]+/u[0-9]+/(?:x86_64|aarch64)/OracleLinux-R([0-9]+)-U([0-9]+)-(x86_64|aarch64)-(dvd|boot|boot-uek)\.iso)".*?([0-9a-f]{64})"#,
+        )
+        .unwrap();
+
+        let mut isos = iso_regex
+            .captures_iter(&page)
+            .filter_map(|c| {
+                let (_, [url, major, update, arch, edition, checksum]) = c.extract();
+                Some((
+                    major.parse::<u32>().ok()?,
+                    update.parse::<u32>().ok()?,
+                    arch,
+                    edition,
+                    url,
+                    checksum,
+                ))
+            })
+            .collect::<Vec<_>>();
+        isos.sort_unstable_by_key(|(major, update, arch, edition, ..)| (*major, *arch, *edition, *update));
+        isos.reverse();
+        // Only the latest update of each release is kept
+        isos.dedup_by(|a, b| a.0 == b.0 && a.2 == b.2 && a.3 == b.3);
+
+        let mut majors = isos.iter().map(|iso| iso.0).collect::<Vec<_>>();
+        majors.dedup();
+        let majors = majors.into_iter().take(Self::release_limit()).collect::<Vec<_>>();
+
+        Ok(isos
+            .into_iter()
+            .filter(|iso| majors.contains(&iso.0))
+            .filter_map(|(major, update, arch, edition, url, checksum)| {
+                Some(Config {
+                    release: format!("{major}.{update}"),
+                    edition: Some(edition.to_string()),
+                    arch: arch_from_str(arch)?,
+                    iso: Some(vec![Source::Web(WebSource::new(
+                        url.to_string(),
+                        Some(checksum.to_string()),
+                        None,
+                        None,
+                    ))]),
+                    ..Default::default()
+                })
+            })
+            .collect::<Vec<Config>>())
+    }
+}
+
+const AMAZON_LINUX_MIRROR: &str = "https://cdn.amazonlinux.com/al2023/os-images/latest/";
+
+pub struct AmazonLinux;
+impl Distro for AmazonLinux {
+    const NAME: &'static str = "amazonlinux";
+    const PRETTY_NAME: &'static str = "Amazon Linux";
+    const HOMEPAGE: Option<&'static str> = Some("https://aws.amazon.com/linux/amazon-linux-2023/");
+    const DESCRIPTION: Option<&'static str> = Some("General purpose Linux-based OS from Amazon Web Services, derived from Fedora and optimized for running workloads on AWS.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let image_regex = Arc::new(Regex::new(r#"href="(al2023-kvm-(\d{4}\.\d+\.\d{8}\.\d+)-kernel-[\d.]+-(?:x86_64|arm64)\.xfs\.gpt\.qcow2)""#).unwrap());
+
+        let futures = [(Arch::x86_64, "kvm"), (Arch::aarch64, "kvm-arm64")]
+            .into_iter()
+            .map(|(arch, directory)| {
+                let mirror = format!("{AMAZON_LINUX_MIRROR}{directory}/");
+                let image_regex = image_regex.clone();
+                async move {
+                    let page = capture_page(&mirror).await?;
+                    let (_, [image, release]) = image_regex.captures(&page)?.extract();
+                    let checksum = Whitespace
+                        .fetch(&format!("{mirror}SHA256SUMS"))
+                        .await
+                        .and_then(|mut cs| cs.remove(image));
+                    let url = format!("{mirror}{image}");
+
+                    // The images have no default password, a cloud-init seed is required to configure access
+                    ConfigMetadata::attach(&url, |m| {
+                        m.cloud_init = Some(CloudInit {
+                            default_user: "ec2-user".to_string(),
+                            default_password: None,
+                        })
+                    });
+                    Some(Config {
+                        release: release.to_string(),
+                        arch,
+                        disk_images: Some(vec![Disk {
+                            source: Source::Web(WebSource::new(url, checksum, None, None)),
+                            format: DiskFormat::Qcow2,
+                            ..Default::default()
+                        }]),
+                        ..Default::default()
+                    })
+                }
+            });
+
+        Ok(join_futures!(futures, 1))
     }
 }
@@ -1,11 +1,16 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use crate::{
-    store_data::{ArchiveFormat, ChecksumSeparation, Config, Distro, Source, WebSource},
-    utils::{arch_from_str, capture_page},
+    checksums::{single_checksum, Bsd, ChecksumFormat, CustomRegex, Whitespace},
+    error::{GenerateError, OrGenerateError},
+    store_data::{
+        add_latest_alias, mirrored_url, ova_disk, ArchiveFormat, Channel, ChecksumAlgorithm, ChecksumFileFormat, ChecksumSource, CloudInit, Config, ConfigMetadata, Disk, Distro, HardwareHints,
+        NetBoot, Region, Signature, Source, SourceMetadata, WebSource, GIB, MIB,
+    },
+    utils::{arch_from_str, capture_large_page, capture_page, list_links, list_links_matching, ova_disk_name, GatherData, SourceForgeAPI},
 };
 use join_futures::join_futures;
-use quickemu::config::Arch;
+use quickemu::config::{Arch, DiskFormat};
 use regex::Regex;
 use serde::Deserialize;
 
@@ -18,9 +23,11 @@ impl Distro for NixOS {
     const PRETTY_NAME: &'static str = "NixOS";
     const HOMEPAGE: Option<&'static str> = Some("https://nixos.org/");
     const DESCRIPTION: Option<&'static str> = Some("Linux distribution based on Nix package manager, tool that takes a unique approach to package management and system configuration.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let releases = capture_page(NIX_URL).await?;
-        let releases: NixReleases = quick_xml::de::from_str(&releases).ok()?;
+    const RELEASE_LIMIT: usize = 6;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        // The bucket listing includes every channel ever published
+        let releases = capture_large_page(NIX_URL).await.or_network_error(NIX_URL)?;
+        let releases: NixReleases = quick_xml::de::from_str(&releases)?;
 
         let standard_release = Regex::new(r#"nixos-(([0-9]+.[0-9]+|(unstable))(?:-small)?)"#).unwrap();
         let iso_regex = Regex::new(r#"latest-nixos-([^-]+)-([^-]+)-linux.iso"#).unwrap();
@@ -31,7 +38,7 @@ impl Distro for NixOS {
             .map(|r| r.key)
             .filter(|r| standard_release.is_match(r))
             .rev()
-            .take(6)
+            .take(Self::release_limit())
             .map(|r| standard_release.captures(&r).unwrap().get(1).unwrap().as_str().to_string())
             .collect();
         let mut futures = Vec::new();
@@ -73,7 +80,7 @@ impl Distro for NixOS {
                 );
             };
         }
-        Some(join_futures!(futures, 1))
+        Ok(join_futures!(futures, 1))
     }
 }
 
@@ -89,6 +96,8 @@ struct NixRelease {
 }
 
 const ALPINE_MIRROR: &str = "https://dl-cdn.alpinelinux.org/alpine/";
+// Natanael Copa <ncopa@alpinelinux.org>
+const ALPINE_SIGNING_KEY: &str = "0482D84022F52DF1C4E7CD43293ACD0907D9495A";
 
 pub struct Alpine;
 impl Distro for Alpine {
@@ -96,40 +105,92 @@ impl Distro for Alpine {
     const PRETTY_NAME: &'static str = "Alpine Linux";
     const HOMEPAGE: Option<&'static str> = Some("https://alpinelinux.org/");
     const DESCRIPTION: Option<&'static str> = Some("Security-oriented, lightweight Linux distribution based on musl libc and busybox.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let releases = capture_page(ALPINE_MIRROR).await?;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let releases = capture_page(ALPINE_MIRROR).await.or_network_error(ALPINE_MIRROR)?;
         let releases_regex = Regex::new(r#"<a href="(v[0-9]+\.[0-9]+)/""#).unwrap();
-        let iso_regex = Arc::new(Regex::new(r#"(?s)iso: (alpine-virt-[0-9]+\.[0-9]+.*?.iso).*? sha256: ([0-9a-f]+)"#).unwrap());
 
         let futures = releases_regex.captures_iter(&releases).flat_map(|r| {
             let release = r[1].to_string();
-            [Arch::x86_64, Arch::aarch64]
-                .iter()
-                .map(|arch| {
-                    let release = release.clone();
-                    let mirror = format!("{ALPINE_MIRROR}{release}/releases/{arch}/latest-releases.yaml");
-                    let iso_regex = iso_regex.clone();
-
-                    async move {
-                        let page = capture_page(&mirror).await?;
-                        let (_, [iso, checksum]) = iso_regex.captures(&page)?.extract();
-                        let url = format!("{ALPINE_MIRROR}{release}/releases/{arch}/{iso}");
-                        Some(Config {
-                            release: release.to_string(),
-                            arch: arch.clone(),
-                            iso: Some(vec![Source::Web(WebSource::new(url, Some(checksum.into()), None, None))]),
-                            ..Default::default()
-                        })
-                    }
-                })
-                .collect::<Vec<_>>()
+            [Arch::x86_64, Arch::aarch64].map(|arch| {
+                let release = release.clone();
+                let mirror = format!("{ALPINE_MIRROR}{release}/releases/{arch}/");
+                async move {
+                    let data = capture_page(&format!("{mirror}latest-releases.yaml")).await?;
+                    let entries: Vec<AlpineRelease> = serde_yaml::from_str(&data)
+                        .inspect_err(|e| tracing::warn!("Unable to parse Alpine {release} {arch} releases: {e}"))
+                        .ok()?;
+                    // Checksums aren't published for each netboot file, only for the tarball they're also packed into
+                    let netboot_archive = entries.iter().find(|entry| entry.flavor == "alpine-netboot").map(|entry| {
+                        WebSource::new(
+                            format!("{mirror}{}", entry.file),
+                            entry.sha256.clone(),
+                            Some(ArchiveFormat::TarGz),
+                            None,
+                        )
+                    });
+                    Some(
+                        entries
+                            .into_iter()
+                            .filter(|entry| entry.file.ends_with(".iso"))
+                            .filter_map(|entry| {
+                                let edition = entry.flavor.strip_prefix("alpine-")?;
+                                // Standard and extended ISOs boot the LTS kernel, virt ISOs boot a kernel trimmed down for VMs
+                                let kernel = match edition {
+                                    "standard" | "extended" => "lts",
+                                    "virt" => "virt",
+                                    _ => return None,
+                                };
+                                let url = format!("{mirror}{}", entry.file);
+                                SourceMetadata::attach(&url, |m| {
+                                    m.signature = Some(Signature::checksum_file(
+                                        format!("{mirror}latest-releases.yaml.asc"),
+                                        format!("{mirror}latest-releases.yaml"),
+                                        ChecksumFileFormat::Yaml,
+                                        ALPINE_SIGNING_KEY,
+                                    ))
+                                });
+                                let netboot = format!("{mirror}netboot/");
+                                ConfigMetadata::attach(&url, |m| {
+                                    m.netboot = Some(NetBoot {
+                                        kernel: WebSource::url_only(format!("{netboot}vmlinuz-{kernel}")),
+                                        initrd: WebSource::url_only(format!("{netboot}initramfs-{kernel}")),
+                                        cmdline: Some(format!(
+                                            "ip=dhcp modloop={netboot}modloop-{kernel} alpine_repo={ALPINE_MIRROR}{release}/main"
+                                        )),
+                                        archive: netboot_archive.clone(),
+                                    })
+                                });
+                                Some(Config {
+                                    release: release.clone(),
+                                    edition: Some(edition.to_string()),
+                                    arch: arch.clone(),
+                                    iso: Some(vec![Source::Web(WebSource::new(url, entry.sha256, None, None))]),
+                                    ..Default::default()
+                                })
+                            })
+                            .collect::<Vec<Config>>(),
+                    )
+                }
+            })
         });
 
-        Some(join_futures!(futures, 1))
+        Ok(join_futures!(futures, 2))
     }
 }
 
-const BATOCERA_MIRROR: &str = "https://mirrors.o2switch.fr/batocera/x86_64/stable/";
+/// An entry of latest-releases.yaml, which lists every flavour of the newest point release of a branch
+#[derive(Deserialize)]
+struct AlpineRelease {
+    /// Such as alpine-standard, alpine-virt or alpine-netboot
+    flavor: String,
+    file: String,
+    sha256: Option<String>,
+}
+
+const BATOCERA_MIRRORS: [(&str, Region); 2] = [
+    ("https://mirrors.o2switch.fr/batocera/x86_64/stable/", Region::Eu),
+    ("https://updates.batocera.org/x86_64/stable/", Region::Global),
+];
 
 pub struct Batocera;
 impl Distro for Batocera {
@@ -137,8 +198,10 @@ impl Distro for Batocera {
     const PRETTY_NAME: &'static str = "Batocera";
     const HOMEPAGE: Option<&'static str> = Some("https://batocera.org/");
     const DESCRIPTION: Option<&'static str> = Some("Retro-gaming distribution with the aim of turning any computer/nano computer into a gaming console during a game or permanently.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let release_data = capture_page(BATOCERA_MIRROR).await?;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let release_data = capture_page(BATOCERA_MIRRORS[0].0)
+            .await
+            .or_network_error(BATOCERA_MIRRORS[0].0)?;
         let batocera_regex = Regex::new(r#"<a href="([0-9]{2})/""#).unwrap();
         let iso_regex = Arc::new(Regex::new(r#"<a href="(batocera-x86_64.*?.img.gz)"#).unwrap());
 
@@ -151,14 +214,13 @@ impl Distro for Batocera {
 
         let futures = releases
             .into_iter()
-            .take(3)
+            .take(Self::release_limit())
             .map(|release| {
                 let iso_regex = iso_regex.clone();
                 async move {
-                    let url = format!("{BATOCERA_MIRROR}{release}/");
-                    let page = capture_page(&url).await?;
+                    let page = capture_page(&format!("{}{release}/", BATOCERA_MIRRORS[0].0)).await?;
                     let captures = iso_regex.captures(&page)?;
-                    let iso = format!("{url}{}", &captures[1]);
+                    let iso = mirrored_url(&BATOCERA_MIRRORS, &format!("{release}/{}", &captures[1]));
                     Some(Config {
                         release: release.to_string(),
                         img: Some(vec![Source::Web(WebSource::new(iso, None, Some(ArchiveFormat::Gz), None))]),
@@ -168,7 +230,15 @@ impl Distro for Batocera {
             })
             .collect::<Vec<_>>();
 
-        Some(join_futures!(futures, 1))
+        Ok(join_futures!(futures, 1))
+    }
+    fn hardware_hints(_config: &Config) -> Option<HardwareHints> {
+        // Emulators for recent consoles need plenty of memory, and the frontend's themes plenty of video memory
+        Some(HardwareHints {
+            ram: Some(4 * GIB),
+            cpu_cores: Some(2),
+            video_memory: Some(256 * MIB),
+        })
     }
 }
 
@@ -180,8 +250,8 @@ impl Distro for ChimeraLinux {
     const PRETTY_NAME: &'static str = "Chimera Linux";
     const HOMEPAGE: Option<&'static str> = Some("https://chimera-linux.org/");
     const DESCRIPTION: Option<&'static str> = Some("Modern, general-purpose non-GNU Linux distribution.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let releases = capture_page(CHIMERA_MIRROR).await?;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let releases = capture_page(CHIMERA_MIRROR).await.or_network_error(CHIMERA_MIRROR)?;
         let release_regex = Regex::new(r#"href="([0-9]{8})/""#).unwrap();
         let iso_regex = Arc::new(Regex::new(r#"href="(chimera-linux-(x86_64|aarch64|riscv64)-LIVE-[0-9]{8}-([^-]+).iso)""#).unwrap());
 
@@ -192,11 +262,7 @@ impl Distro for ChimeraLinux {
                 .collect::<Vec<u32>>();
             releases.sort_unstable();
             releases.reverse();
-            let mut releases = releases.iter().map(ToString::to_string).collect::<Vec<String>>();
-            if let Some(r) = releases.get_mut(0) {
-                *r = "latest".to_string();
-            }
-            releases
+            releases.iter().map(ToString::to_string).collect::<Vec<String>>()
         };
 
         let futures = releases.iter().map(|release| {
@@ -206,7 +272,7 @@ impl Distro for ChimeraLinux {
 
             async move {
                 let page = capture_page(&url).await?;
-                let mut checksums = ChecksumSeparation::Whitespace.build(&checksum_url).await;
+                let mut checksums = Whitespace.fetch(&checksum_url).await;
                 Some(
                     iso_regex
                         .captures_iter(&page)
@@ -228,7 +294,17 @@ impl Distro for ChimeraLinux {
             }
         });
 
-        Some(join_futures!(futures, 2))
+        let mut configs = join_futures!(futures, 2);
+        add_latest_alias(&mut configs);
+        Ok(configs)
+    }
+    fn hardware_hints(config: &Config) -> Option<HardwareHints> {
+        // Only the base edition boots to a console, the others start a full desktop from the live image
+        let ram = match config.edition.as_deref()? {
+            "base" => GIB,
+            _ => 4 * GIB,
+        };
+        Some(HardwareHints { ram: Some(ram), ..Default::default() })
     }
 }
 
@@ -240,7 +316,7 @@ impl Distro for Gentoo {
     const PRETTY_NAME: &'static str = "Gentoo";
     const HOMEPAGE: Option<&'static str> = Some("https://www.gentoo.org/");
     const DESCRIPTION: Option<&'static str> = Some("Highly flexible, source-based Linux distribution.");
-    async fn generate_configs() -> Option<Vec<Config>> {
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
         let iso_regex = Arc::new(Regex::new(r#"\d{8}T\d{6}Z\/(admincd|install|livegui).*?.iso"#).unwrap());
         let futures = [(Arch::x86_64, "amd64"), (Arch::aarch64, "arm64")]
             .into_iter()
@@ -261,10 +337,9 @@ impl Distro for Gentoo {
                             let checksum_url = url.clone() + ".sha256";
                             let arch = arch.clone();
                             async move {
-                                let checksum = capture_page(&checksum_url).await.and_then(|cs| {
-                                    cs.lines()
-                                        .find(|l| l.contains("iso"))
-                                        .and_then(|l| l.split_whitespace().next().map(ToString::to_string))
+                                let checksum = Whitespace.fetch(&checksum_url).await.and_then(|cs| {
+                                    cs.into_iter()
+                                        .find_map(|(file, checksum)| file.contains("iso").then_some(checksum))
                                 });
 
                                 Config {
@@ -280,11 +355,12 @@ impl Distro for Gentoo {
                     Some(join_futures!(futures))
                 }
             });
-        Some(join_futures!(futures, 2))
+        Ok(join_futures!(futures, 2))
     }
 }
 
 const GNOMEOS_MIRROR: &str = "https://download.gnome.org/gnomeos/";
+const GNOMEOS_NIGHTLY: &str = "https://os.gnome.org/download/latest/gnome_os_installer.iso";
 
 pub struct GnomeOS;
 impl Distro for GnomeOS {
@@ -292,8 +368,10 @@ impl Distro for GnomeOS {
     const PRETTY_NAME: &'static str = "GNOME OS";
     const HOMEPAGE: Option<&'static str> = Some("https://os.gnome.org/");
     const DESCRIPTION: Option<&'static str> = Some("Alpha nightly bleeding edge distro of GNOME");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let release_html = capture_page(GNOMEOS_MIRROR).await?;
+    const RELEASE_LIMIT: usize = 6;
+    const IMMUTABLE: bool = true;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let release_html = capture_page(GNOMEOS_MIRROR).await.or_network_error(GNOMEOS_MIRROR)?;
         let release_regex = Regex::new(r#"href="(\d[^/]+)\/""#).unwrap();
         let iso_regex = Arc::new(Regex::new(r#"href="(gnome_os.*?.iso)""#).unwrap());
 
@@ -303,12 +381,15 @@ impl Distro for GnomeOS {
             .collect::<Vec<_>>();
         releases.reverse();
 
-        let futures = releases.into_iter().take(6).map(|(release, mirror)| {
+        let futures = releases.into_iter().take(Self::release_limit()).map(|(release, mirror)| {
             let iso_regex = iso_regex.clone();
             async move {
                 let page = capture_page(&mirror).await?;
                 let iso = &iso_regex.captures(&page)?[1];
                 let url = format!("{mirror}{iso}");
+                if ["alpha", "beta", "rc"].iter().any(|p| release.contains(p)) {
+                    ConfigMetadata::attach(&url, |m| m.channel = Some(Channel::Testing));
+                }
                 Some(Config {
                     release,
                     iso: Some(vec![Source::Web(WebSource::url_only(url))]),
@@ -319,14 +400,860 @@ impl Distro for GnomeOS {
 
         let mut configs = join_futures!(futures, 1);
 
+        ConfigMetadata::attach(GNOMEOS_NIGHTLY, |m| m.channel = Some(Channel::Nightly));
         configs.push(Config {
             release: "nightly".to_string(),
-            iso: Some(vec![Source::Web(WebSource::url_only(
-                "https://os.gnome.org/download/latest/gnome_os_installer.iso",
+            iso: Some(vec![Source::Web(WebSource::url_only(GNOMEOS_NIGHTLY))]),
+            ..Default::default()
+        });
+
+        Ok(configs)
+    }
+}
+
+const NUTYX_MIRROR: &str = "https://downloads.nutyx.org/x86_64/iso/";
+
+pub struct NuTyX;
+impl Distro for NuTyX {
+    const NAME: &'static str = "nutyx";
+    const PRETTY_NAME: &'static str = "NuTyX";
+    const HOMEPAGE: Option<&'static str> = Some("https://nutyx.org/");
+    const DESCRIPTION: Option<&'static str> = Some("French Linux distribution built from scratch, using its own package manager (cards) and an innovative collection system.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(NUTYX_MIRROR).await.or_network_error(NUTYX_MIRROR)?;
+        let iso_regex = Regex::new(r#"href="(NuTyX_x86_64-([\d.]+)-(base|full)\.iso)""#).unwrap();
+
+        let mut isos = iso_regex
+            .captures_iter(&page)
+            .map(|c| c.extract())
+            .map(|(_, [iso, release, edition])| (iso.to_string(), release.to_string(), edition.to_string()))
+            .collect::<Vec<_>>();
+        isos.sort_by_key(|(_, release, _)| {
+            release
+                .split('.')
+                .map(|n| n.parse::<u32>().unwrap_or_default())
+                .collect::<Vec<u32>>()
+        });
+        isos.reverse();
+
+        let mut releases = isos.iter().map(|(_, release, _)| release).collect::<Vec<_>>();
+        releases.dedup();
+        let releases = releases
+            .into_iter()
+            .take(Self::release_limit())
+            .cloned()
+            .collect::<Vec<String>>();
+
+        let futures = isos
+            .into_iter()
+            .filter(|(_, release, _)| releases.contains(release))
+            .map(|(iso, release, edition)| {
+                let url = format!("{NUTYX_MIRROR}{iso}");
+                let checksum_url = url.clone() + ".sha256";
+                async move {
+                    let checksum = single_checksum(&checksum_url).await;
+                    Config {
+                        release,
+                        edition: Some(edition),
+                        iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                        ..Default::default()
+                    }
+                }
+            });
+
+        Ok(join_futures!(futures))
+    }
+}
+
+const VOID_MIRROR: &str = "https://repo-default.voidlinux.org/live/";
+
+pub struct VoidLinux;
+impl Distro for VoidLinux {
+    const NAME: &'static str = "void";
+    const PRETTY_NAME: &'static str = "Void Linux";
+    const HOMEPAGE: Option<&'static str> = Some("https://voidlinux.org/");
+    const DESCRIPTION: Option<&'static str> = Some("General purpose operating system. Its package system allows you to quickly install, update and remove software; software is provided in binary packages or can be built directly from sources.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let releases = capture_page(VOID_MIRROR).await.or_network_error(VOID_MIRROR)?;
+        let release_regex = Regex::new(r#"href="([0-9]{8})/""#).unwrap();
+        let iso_regex = Arc::new(Regex::new(r#"href="(void-live-(x86_64|aarch64)(-musl)?-[0-9]{8}-(base|xfce)\.iso)""#).unwrap());
+
+        let mut releases = release_regex
+            .captures_iter(&releases)
+            .map(|c| c[1].to_string())
+            .collect::<Vec<String>>();
+        releases.sort_unstable();
+        releases.dedup();
+
+        let futures = releases.into_iter().rev().take(Self::release_limit()).map(|release| {
+            let url = format!("{VOID_MIRROR}{release}/");
+            let iso_regex = iso_regex.clone();
+
+            async move {
+                let page = capture_page(&url).await?;
+                let mut checksums = Bsd(ChecksumAlgorithm::Sha256).fetch(&format!("{url}sha256sum.txt")).await;
+                Some(
+                    iso_regex
+                        .captures_iter(&page)
+                        .filter_map(|c| {
+                            let iso = &c[1];
+                            let arch = arch_from_str(&c[2])?;
+                            let edition = match c.get(3) {
+                                Some(_) => format!("{}-musl", &c[4]),
+                                None => c[4].to_string(),
+                            };
+                            let checksum = checksums.as_mut().and_then(|cs| cs.remove(iso));
+                            Some(Config {
+                                release: release.clone(),
+                                edition: Some(edition),
+                                arch,
+                                iso: Some(vec![Source::Web(WebSource::new(format!("{url}{iso}"), checksum, None, None))]),
+                                ..Default::default()
+                            })
+                        })
+                        .collect::<Vec<Config>>(),
+                )
+            }
+        });
+
+        Ok(join_futures!(futures, 2))
+    }
+}
+
+const GUIX_MIRROR: &str = "https://ftpmirror.gnu.org/gnu/guix/";
+const GUIX_CI_LATEST: &str = "https://ci.guix.gnu.org/search/latest/";
+// Maxim Cournoyer <maxim.cournoyer@gmail.com>
+const GUIX_SIGNING_KEY: &str = "27D586A4F8900854329FF09F1260E46482E63562";
+
+pub struct Guix;
+impl Distro for Guix {
+    const NAME: &'static str = "guix";
+    const PRETTY_NAME: &'static str = "Guix";
+    const HOMEPAGE: Option<&'static str> = Some("https://guix.gnu.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Distribution of the GNU operating system developed by the GNU Project, which respects the freedom of computer users.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(GUIX_MIRROR).await.or_network_error(GUIX_MIRROR)?;
+        let release_regex = Regex::new(r#"href="guix-system-install-(\d+\.\d+\.\d+)\.x86_64-linux\.iso""#).unwrap();
+        let release = release_regex
+            .captures_iter(&page)
+            .map(|c| c[1].to_string())
+            .max_by_key(|r| r.split('.').map(|n| n.parse::<u32>().unwrap_or_default()).collect::<Vec<u32>>())
+            .ok_or(GenerateError::NoReleasesFound)?;
+
+        // Releases are only signed, no checksums are published
+        let signed = |file: String| {
+            let url = format!("{GUIX_MIRROR}{file}");
+            SourceMetadata::attach(&url, |m| {
+                m.signature = Some(Signature::detached(format!("{url}.sig"), GUIX_SIGNING_KEY))
+            });
+            Source::Web(WebSource::url_only(url))
+        };
+        // The CI redirects to the newest successful build of the development branch
+        let latest = |query: &str| Source::Web(WebSource::url_only(format!("{GUIX_CI_LATEST}{query}")));
+        let vm_image = |source| {
+            Some(vec![Disk {
+                source,
+                format: DiskFormat::Qcow2,
+                ..Default::default()
+            }])
+        };
+
+        Ok(vec![
+            Config {
+                release: release.clone(),
+                edition: Some("installer".to_string()),
+                iso: Some(vec![signed(format!("guix-system-install-{release}.x86_64-linux.iso"))]),
+                ..Default::default()
+            },
+            Config {
+                release: release.clone(),
+                edition: Some("vm".to_string()),
+                disk_images: vm_image(signed(format!("guix-system-vm-image-{release}.x86_64-linux.qcow2"))),
+                ..Default::default()
+            },
+            Config {
+                release: "latest".to_string(),
+                edition: Some("installer".to_string()),
+                iso: Some(vec![latest(
+                    "ISO-9660?query=spec:images+status:success+system:x86_64-linux+image.iso",
+                )]),
+                ..Default::default()
+            },
+            Config {
+                release: "latest".to_string(),
+                edition: Some("vm".to_string()),
+                disk_images: vm_image(latest("qcow2?query=spec:images+status:success+system:x86_64-linux+image.qcow2")),
+                ..Default::default()
+            },
+        ])
+    }
+}
+
+const PARABOLA_MIRROR: &str = "https://repo.parabola.nu/iso/";
+
+pub struct Parabola;
+impl Distro for Parabola {
+    const NAME: &'static str = "parabola";
+    const PRETTY_NAME: &'static str = "Parabola";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.parabola.nu/");
+    const DESCRIPTION: Option<&'static str> = Some("Fully free operating system for the i686, x86_64 and armv7h architectures, based on Arch Linux with only free software packages.");
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Sha512;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(PARABOLA_MIRROR).await.or_network_error(PARABOLA_MIRROR)?;
+        let directory_regex = Regex::new(r#"href="(([a-z]+-[a-z0-9]+)-(\d{4}\.\d{2}))/""#).unwrap();
+        let iso_regex = Arc::new(Regex::new(r#"href="(parabola-[^"]*x86_64[^"]*\.iso)""#).unwrap());
+
+        // Each init system and interface combination is published separately, only the newest of each is of interest
+        let mut latest: HashMap<String, (String, String)> = HashMap::new();
+        for c in directory_regex.captures_iter(&page) {
+            let (directory, edition, release) = (c[1].to_string(), c[2].to_string(), c[3].to_string());
+            if latest.get(&edition).is_none_or(|(newest, _)| *newest < release) {
+                latest.insert(edition, (release, directory));
+            }
+        }
+
+        let futures = latest.into_iter().map(|(edition, (release, directory))| {
+            let mirror = format!("{PARABOLA_MIRROR}{directory}/");
+            let iso_regex = iso_regex.clone();
+            async move {
+                let page = capture_page(&mirror).await?;
+                let iso = &iso_regex.captures(&page)?[1];
+                let checksum = Whitespace
+                    .fetch(&format!("{mirror}SHA512SUMS"))
+                    .await
+                    .and_then(|mut cs| cs.remove(iso));
+                Some(Config {
+                    release,
+                    edition: Some(edition),
+                    iso: Some(vec![Source::Web(WebSource::new(
+                        format!("{mirror}{iso}"),
+                        checksum,
+                        None,
+                        None,
+                    ))]),
+                    ..Default::default()
+                })
+            }
+        });
+
+        Ok(join_futures!(futures, 1))
+    }
+}
+
+const OPENEULER_MIRROR: &str = "https://repo.openeuler.org/";
+
+pub struct OpenEuler;
+impl Distro for OpenEuler {
+    const NAME: &'static str = "openeuler";
+    const PRETTY_NAME: &'static str = "openEuler";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.openeuler.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Community-driven enterprise Linux distribution for servers, cloud and edge computing, originally developed by Huawei.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(OPENEULER_MIRROR).await.or_network_error(OPENEULER_MIRROR)?;
+        // Innovation releases are only supported for six months, so only LTS releases and their service packs are included
+        let release_regex = Regex::new(r#"href="openEuler-((\d+)\.(\d+)-LTS(?:-SP(\d+))?)/""#).unwrap();
+
+        let mut releases = release_regex
+            .captures_iter(&page)
+            .map(|c| {
+                let number = |i: usize| c.get(i).and_then(|n| n.as_str().parse::<u32>().ok()).unwrap_or_default();
+                ((number(2), number(3), number(4)), c[1].to_string())
+            })
+            .collect::<Vec<_>>();
+        releases.sort_unstable();
+        releases.reverse();
+        // Only the latest service pack of each LTS release is kept
+        releases.dedup_by(|a, b| (a.0 .0, a.0 .1) == (b.0 .0, b.0 .1));
+
+        let futures = releases.into_iter().take(Self::release_limit()).flat_map(|(_, release)| {
+            [Arch::x86_64, Arch::aarch64].into_iter().flat_map(move |arch| {
+                let release = release.clone();
+                ["dvd", "netinst"].into_iter().map(move |edition| {
+                    let release = release.clone();
+                    let arch = arch.clone();
+                    async move {
+                        let url = format!("{OPENEULER_MIRROR}openEuler-{release}/ISO/{arch}/openEuler-{release}-{arch}-{edition}.iso");
+                        let checksum = single_checksum(&format!("{url}.sha256sum")).await;
+                        Config {
+                            release: release.to_lowercase(),
+                            edition: Some(edition.to_string()),
+                            arch,
+                            iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                            ..Default::default()
+                        }
+                    }
+                })
+            })
+        });
+
+        Ok(join_futures!(futures))
+    }
+}
+
+const OPENKYLIN_MIRROR: &str = "https://mirrors.openkylin.top/openkylin-cdimage/";
+
+pub struct OpenKylin;
+impl Distro for OpenKylin {
+    const NAME: &'static str = "openkylin";
+    const PRETTY_NAME: &'static str = "openKylin";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.openkylin.top/");
+    const DESCRIPTION: Option<&'static str> = Some("Open-source desktop operating system built by a Chinese community, featuring the UKUI desktop environment.");
+    const LOCALE: Option<&'static str> = Some("zh_CN");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(OPENKYLIN_MIRROR).await.or_network_error(OPENKYLIN_MIRROR)?;
+        let release_regex = Regex::new(r#"href="((\d+(?:\.\d+)+)(?:-SP(\d+))?)/""#).unwrap();
+        let iso_regex = Arc::new(Regex::new(r#"href="(openKylin-[^"]*-(x86_64|arm64|riscv64)\.iso)""#).unwrap());
+
+        let mut releases = release_regex
+            .captures_iter(&page)
+            .map(|c| {
+                let mut version = c[2]
+                    .split('.')
+                    .map(|n| n.parse::<u32>().unwrap_or_default())
+                    .collect::<Vec<u32>>();
+                version.push(c.get(3).and_then(|sp| sp.as_str().parse().ok()).unwrap_or_default());
+                (version, c[1].to_string())
+            })
+            .collect::<Vec<_>>();
+        releases.sort_unstable();
+        releases.dedup();
+
+        let futures = releases.into_iter().rev().take(Self::release_limit()).map(|(_, release)| {
+            let mirror = format!("{OPENKYLIN_MIRROR}{release}/");
+            let iso_regex = iso_regex.clone();
+            async move {
+                let page = capture_page(&mirror).await?;
+                let checksums = ChecksumSource::new()
+                    .suffix(".sha256sum")
+                    .suffix(".md5sum")
+                    .algorithm(ChecksumAlgorithm::Md5);
+                let mut configs = Vec::new();
+                for c in iso_regex.captures_iter(&page) {
+                    let url = format!("{mirror}{}", &c[1]);
+                    let checksum = checksums.find(&url).await;
+                    configs.push(Config {
+                        release: release.to_lowercase(),
+                        arch: arch_from_str(&c[2])?,
+                        iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                        ..Default::default()
+                    });
+                }
+                Some(configs)
+            }
+        });
+
+        Ok(join_futures!(futures, 2))
+    }
+}
+
+const ROSA_MIRROR: &str = "https://mirror.rosa.ru/rosa/";
+
+pub struct RosaFresh;
+impl Distro for RosaFresh {
+    const NAME: &'static str = "rosa";
+    const PRETTY_NAME: &'static str = "ROSA Fresh";
+    const HOMEPAGE: Option<&'static str> = Some("https://rosa.ru/");
+    const DESCRIPTION: Option<&'static str> = Some("Community edition of ROSA, a Russian distribution descended from Mandriva, offering KDE Plasma, GNOME, Xfce and LXQt desktops.");
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(ROSA_MIRROR).await.or_network_error(ROSA_MIRROR)?;
+        let platform_regex = Regex::new(r#"href="rosa(\d{4}\.\d+)/""#).unwrap();
+        let iso_regex = Arc::new(Regex::new(r#"href="(ROSA\.FRESH\.([A-Z0-9]+)\.([\d.]+)\.x86_64\.iso)""#).unwrap());
+
+        let mut platforms = platform_regex
+            .captures_iter(&page)
+            .map(|c| c[1].to_string())
+            .collect::<Vec<String>>();
+        platforms.sort_by_cached_key(|p| p.split('.').map(|n| n.parse::<u32>().unwrap_or_default()).collect::<Vec<u32>>());
+        platforms.dedup();
+
+        // Fresh releases are built upon a platform, whose ISO directory holds every desktop of its latest release
+        let futures = platforms.into_iter().rev().take(Self::release_limit()).map(|platform| {
+            let mirror = format!("{ROSA_MIRROR}rosa{platform}/iso/");
+            let iso_regex = iso_regex.clone();
+            async move {
+                let page = capture_page(&mirror).await?;
+                let checksums = ChecksumSource::new()
+                    .suffix(".sha256sum")
+                    .suffix(".md5sum")
+                    .algorithm(ChecksumAlgorithm::Md5);
+                let mut configs = Vec::new();
+                for c in iso_regex.captures_iter(&page) {
+                    let url = format!("{mirror}{}", &c[1]);
+                    let checksum = checksums.find(&url).await;
+                    configs.push(Config {
+                        release: c[3].to_string(),
+                        edition: Some(c[2].to_lowercase()),
+                        iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                        ..Default::default()
+                    });
+                }
+                Some(configs)
+            }
+        });
+
+        Ok(join_futures!(futures, 2))
+    }
+}
+
+const ALT_REGULAR_MIRROR: &str = "https://nightly.altlinux.org/sisyphus/tested/";
+
+pub struct AltLinux;
+impl Distro for AltLinux {
+    const NAME: &'static str = "altlinux";
+    const PRETTY_NAME: &'static str = "ALT Linux";
+    const HOMEPAGE: Option<&'static str> = Some("https://getalt.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Independent distribution from Russia built on the Sisyphus repository, whose regular builds offer a wide choice of desktops.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(ALT_REGULAR_MIRROR).await.or_network_error(ALT_REGULAR_MIRROR)?;
+        // Regular builds are snapshots of Sisyphus, rebuilt every few weeks for each desktop
+        let iso_regex = Regex::new(r#"href="(regular-([a-z0-9-]+?)-(\d{8})-(x86_64|aarch64)\.iso)""#).unwrap();
+
+        let mut latest: HashMap<(String, String), (String, String)> = HashMap::new();
+        for c in iso_regex.captures_iter(&page) {
+            let (_, [iso, edition, date, arch]) = c.extract();
+            let key = (edition.to_string(), arch.to_string());
+            if latest.get(&key).is_none_or(|(newest, _)| newest.as_str() < date) {
+                latest.insert(key, (date.to_string(), iso.to_string()));
+            }
+        }
+
+        let checksums = Arc::new(ChecksumSource::new().aggregate(format!("{ALT_REGULAR_MIRROR}SHA256SUMS"), Whitespace));
+        let futures = latest.into_iter().map(|((edition, arch), (release, iso))| {
+            let checksums = checksums.clone();
+            async move {
+                let url = format!("{ALT_REGULAR_MIRROR}{iso}");
+                let checksum = checksums.find(&url).await;
+                Some(Config {
+                    release,
+                    edition: Some(edition),
+                    arch: arch_from_str(&arch)?,
+                    iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                    ..Default::default()
+                })
+            }
+        });
+
+        Ok(join_futures!(futures, 1))
+    }
+}
+
+const CLEAR_MIRROR: &str = "https://cdn.download.clearlinux.org/";
+
+pub struct ClearLinux;
+impl Distro for ClearLinux {
+    const NAME: &'static str = "clearlinux";
+    const PRETTY_NAME: &'static str = "Clear Linux";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.clearlinux.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Rolling distribution from Intel, tuned for performance and security on modern hardware and in the cloud.");
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Sha512;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        // Releases are numbered builds, only the latest is published with images
+        let latest_url = format!("{CLEAR_MIRROR}current/latest");
+        let release = capture_page(&latest_url)
+            .await
+            .or_network_error(&latest_url)?
+            .trim()
+            .to_string();
+        if release.is_empty() || !release.chars().all(|c| c.is_ascii_digit()) {
+            return Err(GenerateError::ParseError {
+                context: format!("Clear Linux's latest release: {release}"),
+            });
+        }
+        let mirror = format!("{CLEAR_MIRROR}releases/{release}/clear/");
+
+        let futures = ["live-desktop", "live-server"].into_iter().map(|edition| {
+            let url = format!("{mirror}clear-{release}-{edition}.iso");
+            let release = release.clone();
+            async move {
+                let checksum = ChecksumSource::new().suffix(".sha512sum").find(&url).await;
+                Config {
+                    release,
+                    edition: Some(edition.trim_start_matches("live-").to_string()),
+                    iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                    ..Default::default()
+                }
+            }
+        });
+        Ok(join_futures!(futures))
+    }
+}
+
+// The images aren't attached to vmware/photon's GitHub releases, whose notes only link back to this mirror
+const PHOTON_MIRROR: &str = "https://packages.vmware.com/photon/";
+
+pub struct PhotonOS;
+impl Distro for PhotonOS {
+    const NAME: &'static str = "photon";
+    const PRETTY_NAME: &'static str = "Photon OS";
+    const HOMEPAGE: Option<&'static str> = Some("https://vmware.github.io/photon/");
+    const DESCRIPTION: Option<&'static str> = Some("Minimal Linux container host from VMware, optimized for cloud-native applications and VMware platforms.");
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let release_regex = Regex::new(r"^(\d+)\.(\d+)/$").unwrap();
+        let mut releases = list_links_matching(PHOTON_MIRROR, &release_regex)
+            .await
+            .or_network_error(PHOTON_MIRROR)?
+            .into_iter()
+            .filter_map(|link| {
+                let c = release_regex.captures(&link)?;
+                let version = (c[1].parse::<u32>().ok()?, c[2].parse::<u32>().ok()?);
+                Some((version, link.trim_end_matches('/').to_string()))
+            })
+            .collect::<Vec<_>>();
+        releases.sort_unstable();
+        // Releases are reissued with updated packages as Rev2, Rev3 and so on, after the initial GA build
+        let build_regex = Arc::new(Regex::new(r"^(GA|Rev(\d+))/$").unwrap());
+        let iso_regex = Arc::new(Regex::new(r"^photon-(?:(minimal|rt)-)?[\d.]+-[0-9a-f]+\.(x86_64|aarch64)\.iso$").unwrap());
+        let ova_regex = Arc::new(Regex::new(r"^photon-hw(\d+)-[\d.]+-[0-9a-f]+\.(x86_64|aarch64)\.ova$").unwrap());
+
+        let futures = releases.into_iter().rev().take(Self::release_limit()).map(|(_, release)| {
+            let build_regex = build_regex.clone();
+            let iso_regex = iso_regex.clone();
+            let ova_regex = ova_regex.clone();
+            async move {
+                let release_mirror = format!("{PHOTON_MIRROR}{release}/");
+                let builds = list_links_matching(&release_mirror, &build_regex).await?;
+                let build = builds
+                    .iter()
+                    .max_by_key(|b| build_regex.captures(b).and_then(|c| c.get(2)?.as_str().parse::<u32>().ok()))?;
+                let mirror = format!("{release_mirror}{build}iso/");
+                let isos = list_links_matching(&mirror, &iso_regex).await?;
+                let checksums = Arc::new(ChecksumSource::new().suffix(".sha256"));
+
+                let futures = isos.into_iter().filter_map(|iso| {
+                    let c = iso_regex.captures(&iso)?;
+                    let edition = c.get(1).map_or("full", |e| e.as_str()).to_string();
+                    let arch = arch_from_str(&c[2])?;
+                    let url = format!("{mirror}{iso}");
+                    let release = release.clone();
+                    let checksums = checksums.clone();
+                    Some(async move {
+                        let checksum = checksums.find(&url).await;
+                        Config {
+                            release,
+                            edition: Some(edition),
+                            arch,
+                            iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                            ..Default::default()
+                        }
+                    })
+                });
+                let mut configs = join_futures!(futures);
+
+                // Appliances are built for several VMware hardware versions, the disk within them is the same
+                let ova_mirror = format!("{release_mirror}{build}ova/");
+                let mut appliances = list_links_matching(&ova_mirror, &ova_regex).await.unwrap_or_default();
+                appliances.sort_unstable_by_key(|ova| ova_regex.captures(ova).and_then(|c| c[1].parse::<u32>().ok()));
+                let mut appliance_arches = Vec::new();
+                let futures = appliances.into_iter().rev().filter_map(|ova| {
+                    let arch = arch_from_str(&ova_regex.captures(&ova)?[2])?;
+                    if appliance_arches.contains(&arch) {
+                        return None;
+                    }
+                    appliance_arches.push(arch.clone());
+                    let url = format!("{ova_mirror}{ova}");
+                    let release = release.clone();
+                    let checksums = checksums.clone();
+                    Some(async move {
+                        let Some(disk) = ova_disk_name(&url).await else {
+                            tracing::warn!("{url}: Unable to find the disk within the appliance");
+                            return None;
+                        };
+                        let checksum = checksums.find(&url).await;
+                        Some(Config {
+                            release,
+                            edition: Some("appliance".to_string()),
+                            arch,
+                            disk_images: Some(vec![ova_disk(url, checksum, disk)]),
+                            ..Default::default()
+                        })
+                    })
+                });
+                configs.extend(join_futures!(futures, 1));
+                Some(configs)
+            }
+        });
+        Ok(join_futures!(futures, 2))
+    }
+}
+
+const FLATCAR_CHANNELS: [&str; 3] = ["stable", "beta", "alpha"];
+
+pub struct Flatcar;
+impl Distro for Flatcar {
+    const NAME: &'static str = "flatcar";
+    const PRETTY_NAME: &'static str = "Flatcar Container Linux";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.flatcar.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Immutable, minimal container host which updates itself atomically, continuing the legacy of CoreOS Container Linux.");
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Sha512;
+    const IMMUTABLE: bool = true;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        // DIGESTS files list MD5, SHA1 and SHA512 hashes of the image under separate headings, only the latter are picked out by length
+        let digest_regex = Arc::new(Regex::new(r"(?m)^([0-9a-f]{128})\s+(\S+)$").unwrap());
+        let futures = FLATCAR_CHANNELS.into_iter().flat_map(|channel| {
+            [
+                (Arch::x86_64, "amd64-usr", "flatcar_production_qemu_image.img"),
+                (Arch::aarch64, "arm64-usr", "flatcar_production_qemu_uefi_image.img"),
+            ]
+            .into_iter()
+            .map(move |(arch, board, image)| (channel, arch, board, image))
+        });
+        let futures = futures.map(|(channel, arch, board, image)| {
+            let digest_regex = digest_regex.clone();
+            async move {
+                let channel_mirror = format!("https://{channel}.release.flatcar-linux.net/{board}/");
+                // The current directory is replaced by each release, the versioned directory it points to isn't
+                let version = capture_page(&format!("{channel_mirror}current/version.txt"))
+                    .await?
+                    .lines()
+                    .find_map(|l| l.strip_prefix("FLATCAR_VERSION="))?
+                    .trim()
+                    .to_string();
+                let url = format!("{channel_mirror}{version}/{image}");
+                let checksum = ChecksumSource::new()
+                    .aggregate(format!("{url}.DIGESTS"), CustomRegex(digest_regex, 2, 1))
+                    .find(&url)
+                    .await;
+                // Flatcar applies cloud-config user data through coreos-cloudinit, logging in as core
+                ConfigMetadata::attach(&url, |m| {
+                    m.cloud_init = Some(CloudInit {
+                        default_user: "core".to_string(),
+                        default_password: None,
+                    });
+                    if channel != "stable" {
+                        m.channel = Some(Channel::Testing);
+                    }
+                });
+                Some(Config {
+                    release: version,
+                    edition: Some(channel.to_string()),
+                    arch,
+                    disk_images: Some(vec![Disk {
+                        source: Source::Web(WebSource::new(url, checksum, None, None)),
+                        format: DiskFormat::Qcow2,
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                })
+            }
+        });
+        Ok(join_futures!(futures, 1))
+    }
+}
+
+const TINYCORE_MIRROR: &str = "http://www.tinycorelinux.net/";
+
+pub struct TinyCore;
+impl Distro for TinyCore {
+    const NAME: &'static str = "tinycore";
+    const PRETTY_NAME: &'static str = "Tiny Core Linux";
+    const HOMEPAGE: Option<&'static str> = Some("http://www.tinycorelinux.net/");
+    const DESCRIPTION: Option<&'static str> = Some("Highly modular, minimal Linux distribution which runs entirely from memory.");
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Md5;
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let series_regex = Regex::new(r"^(\d+)\.x/$").unwrap();
+        let iso_regex = Arc::new(Regex::new(r"^(Core|TinyCore|CorePlus|CorePure64|TinyCorePure64)-(\d+\.\d+)\.iso$").unwrap());
+        let mut series = list_links_matching(TINYCORE_MIRROR, &series_regex)
+            .await
+            .or_network_error(TINYCORE_MIRROR)?
+            .into_iter()
+            .filter_map(|s| series_regex.captures(&s)?[1].parse::<u32>().ok())
+            .collect::<Vec<u32>>();
+        series.sort_unstable_by(|a, b| b.cmp(a));
+        series.dedup();
+
+        // 64 bit editions are published separately from the 32 bit ones, which still run on x86_64 machines
+        let futures = series.into_iter().take(Self::release_limit()).flat_map(|series| {
+            ["x86", "x86_64"].map(|directory| {
+                let mirror = format!("{TINYCORE_MIRROR}{series}.x/{directory}/release/");
+                let iso_regex = iso_regex.clone();
+                async move {
+                    let isos = list_links_matching(&mirror, &iso_regex).await?;
+                    let futures = isos.into_iter().filter_map(|iso| {
+                        let c = iso_regex.captures(&iso)?;
+                        let (edition, release) = (c[1].to_string(), c[2].to_string());
+                        let url = format!("{mirror}{iso}");
+                        Some(async move {
+                            let checksum = single_checksum(&format!("{url}.md5.txt")).await;
+                            Config {
+                                release,
+                                edition: Some(edition),
+                                iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                                ..Default::default()
+                            }
+                        })
+                    });
+                    Some(join_futures!(futures))
+                }
+            })
+        });
+        Ok(join_futures!(futures, 2))
+    }
+}
+
+const SLITAZ_MIRROR: &str = "https://mirror.slitaz.org/iso/rolling/";
+
+pub struct SliTaz;
+impl Distro for SliTaz {
+    const NAME: &'static str = "slitaz";
+    const PRETTY_NAME: &'static str = "SliTaz";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.slitaz.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Tiny, fast and independent distribution which fits a graphical desktop into a very small ISO.");
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Md5;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let iso_regex = Regex::new(r"^slitaz-rolling(?:-([a-z0-9-]+))?\.iso$").unwrap();
+        let links = list_links(SLITAZ_MIRROR).await.or_network_error(SLITAZ_MIRROR)?;
+
+        let futures = links.iter().filter_map(|iso| {
+            let c = iso_regex.captures(iso)?;
+            let edition = c.get(1).map_or("standard", |e| e.as_str()).to_string();
+            // Checksums are named after either the ISO or the ISO without its extension
+            let stem = iso.trim_end_matches(".iso");
+            let checksum_file = links
+                .iter()
+                .find(|l| **l == format!("{iso}.md5") || **l == format!("{stem}.md5"))
+                .map(|l| format!("{SLITAZ_MIRROR}{l}"));
+            let url = format!("{SLITAZ_MIRROR}{iso}");
+            Some(async move {
+                let checksum = match checksum_file {
+                    Some(checksum_file) => single_checksum(&checksum_file).await,
+                    None => None,
+                };
+                Config {
+                    release: "rolling".to_string(),
+                    edition: Some(edition),
+                    iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                    ..Default::default()
+                }
+            })
+        });
+        Ok(join_futures!(futures))
+    }
+}
+
+const KAOS_PROJECT: &str = "kaosx";
+
+pub struct KaOS;
+impl Distro for KaOS {
+    const NAME: &'static str = "kaos";
+    const PRETTY_NAME: &'static str = "KaOS";
+    const HOMEPAGE: Option<&'static str> = Some("https://kaosx.us/");
+    const DESCRIPTION: Option<&'static str> = Some("Lean, rolling distribution focused on a single desktop, built from scratch around KDE Plasma and Qt.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        // kaosx.us links to SourceForge for downloads, which keeps only the newest few ISOs
+        let files_url = SourceForgeAPI::files_url(KAOS_PROJECT, "ISO");
+        let files = SourceForgeAPI::gather_data(&files_url).await.or_network_error(&files_url)?;
+        let iso_regex = Regex::new(r"^/ISO/KaOS-(\d{4}\.\d{2})-x86_64\.iso$").unwrap();
+        let (release, iso) = files
+            .iter()
+            .filter_map(|f| Some((iso_regex.captures(&f.path)?[1].to_string(), f)))
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .ok_or(GenerateError::NoReleasesFound)?;
+        let checksum = match iso.sibling(&files, ".sha256") {
+            Some(checksum_file) => single_checksum(&checksum_file.download_url).await,
+            None => None,
+        };
+
+        Ok(vec![Config {
+            release,
+            iso: Some(vec![Source::Web(WebSource::new(
+                iso.download_url.clone(),
+                checksum,
+                None,
+                Some(iso.name().to_string()),
             ))]),
             ..Default::default()
+        }])
+    }
+}
+
+const SOLUS_MIRROR: &str = "https://downloads.getsol.us/isos/";
+
+pub struct Solus;
+impl Distro for Solus {
+    const NAME: &'static str = "solus";
+    const PRETTY_NAME: &'static str = "Solus";
+    const HOMEPAGE: Option<&'static str> = Some("https://getsol.us/");
+    const DESCRIPTION: Option<&'static str> = Some("Independent distribution built for home computing, with a curated rolling release and its own Budgie desktop.");
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        // Each snapshot is published in a directory named after its date, which sorts chronologically
+        let release_regex = Regex::new(r"^(\d{4}-\d{2}-\d{2})/$").unwrap();
+        let mut releases = list_links_matching(SOLUS_MIRROR, &release_regex)
+            .await
+            .or_network_error(SOLUS_MIRROR)?
+            .into_iter()
+            .map(|link| link.trim_end_matches('/').to_string())
+            .collect::<Vec<String>>();
+        releases.sort_unstable();
+        releases.dedup();
+        let iso_regex = Arc::new(Regex::new(r"^Solus-(Budgie|GNOME|Plasma|XFCE)-Release-\d{4}-\d{2}-\d{2}\.iso$").unwrap());
+
+        let futures = releases.into_iter().rev().take(Self::release_limit()).map(|release| {
+            let iso_regex = iso_regex.clone();
+            async move {
+                let mirror = format!("{SOLUS_MIRROR}{release}/");
+                let isos = list_links_matching(&mirror, &iso_regex).await?;
+                let futures = isos.into_iter().filter_map(|iso| {
+                    let edition = iso_regex.captures(&iso)?[1].to_lowercase();
+                    let url = format!("{mirror}{iso}");
+                    let release = release.clone();
+                    Some(async move {
+                        let checksum = single_checksum(&format!("{url}.sha256sum")).await;
+                        Config {
+                            release,
+                            edition: Some(edition),
+                            iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                            ..Default::default()
+                        }
+                    })
+                });
+                Some(join_futures!(futures))
+            }
         });
+        Ok(join_futures!(futures, 2))
+    }
+}
+
+const PCLINUXOS_MIRROR: &str = "https://ftp.fau.de/pclinuxos/pclinuxos/iso/";
+
+pub struct PCLinuxOS;
+impl Distro for PCLinuxOS {
+    const NAME: &'static str = "pclinuxos";
+    const PRETTY_NAME: &'static str = "PCLinuxOS";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.pclinuxos.com/");
+    const DESCRIPTION: Option<&'static str> = Some("Independent rolling distribution, using APT to manage RPM packages, with KDE Plasma, MATE and Xfce desktops.");
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Md5;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        // Darkstar is the full KDE Plasma edition. Only the newest ISO of each edition is kept on the mirror
+        let iso_regex = Regex::new(r"^pclinuxos64-(kde-darkstar|mate|xfce)-(\d{4})\.(\d{2})\.iso$").unwrap();
+        let isos = list_links_matching(PCLINUXOS_MIRROR, &iso_regex)
+            .await
+            .or_network_error(PCLINUXOS_MIRROR)?;
+        let mut latest: HashMap<String, ((u32, u32), String)> = HashMap::new();
+        for iso in isos {
+            let Some(c) = iso_regex.captures(&iso) else { continue };
+            let (Ok(year), Ok(month)) = (c[2].parse::<u32>(), c[3].parse::<u32>()) else {
+                continue;
+            };
+            let edition = c[1].to_string();
+            if latest.get(&edition).is_none_or(|(version, _)| *version < (year, month)) {
+                latest.insert(edition, ((year, month), iso));
+            }
+        }
 
-        Some(configs)
+        let futures = latest.into_iter().map(|(edition, ((year, month), iso))| async move {
+            let url = format!("{PCLINUXOS_MIRROR}{iso}");
+            let checksum = single_checksum(&format!("{url}.md5sum")).await;
+            Config {
+                release: format!("{year}.{month:02}"),
+                edition: Some(edition),
+                iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                ..Default::default()
+            }
+        });
+        Ok(join_futures!(futures))
     }
 }
@@ -0,0 +1,189 @@
+use crate::{
+    checksums::{single_checksum, ChecksumFormat, CustomRegex},
+    error::{GenerateError, OrGenerateError},
+    store_data::{ArchiveFormat, ChecksumAlgorithm, Config, Disk, Distro, Signature, Source, SourceMetadata, WebSource},
+    utils::capture_page,
+};
+use join_futures::join_futures;
+use quickemu::config::DiskFormat;
+use regex::Regex;
+use serde::Deserialize;
+use std::sync::Arc;
+
+const TAILS_IDF_URL: &str = "https://tails.net/install/v2/Tails/amd64/stable/latest.json";
+// Tails developers <tails@boum.org>
+const TAILS_SIGNING_KEY: &str = "A490D0F4D311A4153E2BB7CADBB802B258ACD84F";
+
+pub struct Tails;
+impl Distro for Tails {
+    const NAME: &'static str = "tails";
+    const PRETTY_NAME: &'static str = "Tails";
+    const HOMEPAGE: Option<&'static str> = Some("https://tails.net/");
+    const DESCRIPTION: Option<&'static str> = Some("Portable operating system that protects against surveillance and censorship.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let data = capture_page(TAILS_IDF_URL).await.or_network_error(TAILS_IDF_URL)?;
+        let idf: TailsIDF = serde_json::from_str(&data)?;
+
+        Ok(idf
+            .installations
+            .into_iter()
+            .flat_map(|installation| {
+                let release = installation.version;
+                installation.installation_paths.into_iter().filter_map(move |path| {
+                    let file = path.target_files.into_iter().next()?;
+                    let signature = Signature::detached(format!("{}.sig", file.url), TAILS_SIGNING_KEY);
+                    SourceMetadata::attach(&file.url, |m| m.signature = Some(signature));
+                    let source = vec![Source::Web(WebSource::new(file.url, Some(file.sha256), None, None))];
+                    let mut config = Config {
+                        release: release.clone(),
+                        ..Default::default()
+                    };
+                    match path.kind.as_str() {
+                        "iso" => config.iso = Some(source),
+                        "img" => config.img = Some(source),
+                        _ => return None,
+                    }
+                    Some(config)
+                })
+            })
+            .collect::<Vec<Config>>())
+    }
+}
+
+/// Tails' Image Description File, which its upgrader and website use to find the latest images
+#[derive(Deserialize)]
+struct TailsIDF {
+    installations: Vec<TailsInstallation>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct TailsInstallation {
+    version: String,
+    installation_paths: Vec<TailsInstallationPath>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct TailsInstallationPath {
+    #[serde(rename = "type")]
+    kind: String,
+    target_files: Vec<TailsTargetFile>,
+}
+
+#[derive(Deserialize)]
+struct TailsTargetFile {
+    url: String,
+    sha256: String,
+}
+
+const WHONIX_MIRROR: &str = "https://download.whonix.org/libvirt/";
+// Patrick Schleizer <adrelanos@whonix.org>
+const WHONIX_SIGNING_KEY: &str = "916B8D99C38EAF5E8ADC7A2A8D66066A2EEACCDA";
+
+pub struct Whonix;
+impl Distro for Whonix {
+    const NAME: &'static str = "whonix";
+    const PRETTY_NAME: &'static str = "Whonix";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.whonix.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Desktop operating system designed for advanced security and privacy, routing every connection through Tor.");
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Sha512;
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(WHONIX_MIRROR).await.or_network_error(WHONIX_MIRROR)?;
+        let release_regex = Regex::new(r#"href="(\d+(?:\.\d+)+)/""#).unwrap();
+        let mut releases = release_regex
+            .captures_iter(&page)
+            .map(|c| c[1].to_string())
+            .collect::<Vec<String>>();
+        releases.sort_by_cached_key(|r| r.split('.').map(|n| n.parse::<u32>().unwrap_or_default()).collect::<Vec<u32>>());
+        releases.dedup();
+
+        // Each archive contains both the Gateway and Workstation qcow2 images, alongside their libvirt definitions.
+        // The OVA images are built for VirtualBox and are left out, since they can't be represented as a disk image
+        let futures = releases.into_iter().rev().take(Self::release_limit()).flat_map(|release| {
+            [("xfce", "Xfce"), ("cli", "CLI")].into_iter().map(move |(edition, flavour)| {
+                let release = release.clone();
+                async move {
+                    let url = format!("{WHONIX_MIRROR}{release}/Whonix-{flavour}-{release}.Intel_AMD64.qcow2.libvirt.xz");
+                    let checksum = single_checksum(&format!("{url}.sha512sums")).await;
+                    SourceMetadata::attach(&url, |m| {
+                        m.signature = Some(Signature::detached(format!("{url}.asc"), WHONIX_SIGNING_KEY))
+                    });
+                    Config {
+                        release,
+                        edition: Some(edition.to_string()),
+                        disk_images: Some(vec![Disk {
+                            source: Source::Web(WebSource::new(url, checksum, Some(ArchiveFormat::TarXz), None)),
+                            format: DiskFormat::Qcow2,
+                            ..Default::default()
+                        }]),
+                        ..Default::default()
+                    }
+                }
+            })
+        });
+
+        Ok(join_futures!(futures))
+    }
+}
+
+const QUBES_MIRROR: &str = "https://mirrors.edge.kernel.org/qubes/iso/";
+// Each major release is signed by its own release signing key
+const QUBES_SIGNING_KEYS: [(&str, &str); 2] = [
+    // Qubes OS Release 4.1 Signing Key
+    ("4.1", "5817A43B283DE5A9181A522E1848792F9E2795E9"),
+    // Qubes OS Release 4.2 Signing Key
+    ("4.2", "9C884DF3F81064A569A4A9FAE022E58F8E34D89F"),
+];
+
+pub struct Qubes;
+impl Distro for Qubes {
+    const NAME: &'static str = "qubes";
+    const PRETTY_NAME: &'static str = "Qubes OS";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.qubes-os.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Reasonably secure operating system, which isolates software by running it within separate virtual machines.");
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(QUBES_MIRROR).await.or_network_error(QUBES_MIRROR)?;
+        let iso_regex = Regex::new(r#"href="(Qubes-R((\d+\.\d+)(?:\.\d+)?)-x86_64\.iso)""#).unwrap();
+        // The digests file lists each ISO once per algorithm, and is itself clearsigned
+        let checksum_regex = Arc::new(Regex::new(r"(?m)^([0-9a-f]{64})\s+\*?(\S+\.iso)$").unwrap());
+
+        let mut isos = iso_regex
+            .captures_iter(&page)
+            .map(|c| c.extract())
+            .map(|(_, [iso, release, major])| (iso.to_string(), release.to_string(), major.to_string()))
+            .collect::<Vec<_>>();
+        isos.sort_by_cached_key(|(_, release, _)| {
+            release
+                .split('.')
+                .map(|n| n.parse::<u32>().unwrap_or_default())
+                .collect::<Vec<u32>>()
+        });
+        isos.reverse();
+        // Only the latest point release of each major release is kept
+        isos.dedup_by(|a, b| a.2 == b.2);
+
+        let futures = isos.into_iter().take(Self::release_limit()).map(|(iso, release, major)| {
+            let checksum_regex = checksum_regex.clone();
+            async move {
+                let url = format!("{QUBES_MIRROR}{iso}");
+                let checksum = CustomRegex(checksum_regex, 2, 1)
+                    .fetch(&format!("{url}.DIGESTS"))
+                    .await
+                    .and_then(|mut cs| cs.remove(&iso));
+                if let Some((_, key)) = QUBES_SIGNING_KEYS.iter().find(|(m, _)| *m == major) {
+                    SourceMetadata::attach(&url, |m| m.signature = Some(Signature::detached(format!("{url}.asc"), key)));
+                }
+                Config {
+                    release,
+                    iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                    ..Default::default()
+                }
+            }
+        });
+
+        Ok(join_futures!(futures))
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{
+    checksums::{single_checksum, ChecksumFormat, CustomRegex, Whitespace},
+    error::{GenerateError, OrGenerateError},
+    store_data::{ChecksumFileFormat, Config, Distro, Signature, Source, SourceMetadata, WebSource},
+    utils::{arch_from_str, capture_page},
+};
+use join_futures::join_futures;
+use regex::Regex;
+use std::sync::Arc;
+
+const KALI_MIRROR: &str = "https://cdimage.kali.org/";
+// Kali Linux Repository <devel@kali.org>
+const KALI_SIGNING_KEY: &str = "44C6513A8E4FB3D30875F758ED444FF07D8D0BF6";
+
+pub struct Kali;
+impl Distro for Kali {
+    const NAME: &'static str = "kali";
+    const PRETTY_NAME: &'static str = "Kali";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.kali.org/");
+    const DESCRIPTION: Option<&'static str> = Some("The most advanced Penetration Testing Distribution.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let iso_regex = Arc::new(Regex::new(r#"href="(kali-linux-(\d{4}\.\d+|\d{4}-W\d+)-(installer|live|installer-netinst)-(amd64|arm64)\.iso)""#).unwrap());
+
+        let futures = ["current", "kali-weekly"].into_iter().map(|directory| {
+            let mirror = format!("{KALI_MIRROR}{directory}/");
+            let iso_regex = iso_regex.clone();
+            async move {
+                let page = capture_page(&mirror).await?;
+                let mut checksums = Whitespace.fetch(&format!("{mirror}SHA256SUMS")).await;
+                Some(
+                    iso_regex
+                        .captures_iter(&page)
+                        .map(|c| c.extract())
+                        .filter_map(|(_, [iso, release, edition, arch])| {
+                            let url = format!("{mirror}{iso}");
+                            SourceMetadata::attach(&url, |m| {
+                                m.signature = Some(Signature::checksum_file(
+                                    format!("{mirror}SHA256SUMS.gpg"),
+                                    format!("{mirror}SHA256SUMS"),
+                                    ChecksumFileFormat::Whitespace,
+                                    KALI_SIGNING_KEY,
+                                ))
+                            });
+                            let checksum = checksums.as_mut().and_then(|cs| cs.remove(iso));
+                            Some(Config {
+                                release: release.to_string(),
+                                edition: Some(edition.trim_start_matches("installer-").to_string()),
+                                arch: arch_from_str(arch)?,
+                                iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                                ..Default::default()
+                            })
+                        })
+                        .collect::<Vec<Config>>(),
+                )
+            }
+        });
+
+        Ok(join_futures!(futures, 2))
+    }
+}
+
+const PARROT_MIRROR: &str = "https://deb.parrot.sh/parrot/iso/";
+
+pub struct Parrot;
+impl Distro for Parrot {
+    const NAME: &'static str = "parrotsec";
+    const PRETTY_NAME: &'static str = "Parrot Security";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.parrotsec.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Provides a huge arsenal of tools, utilities and libraries that IT and security professionals can use to test and assess the security of their assets in a reliable, compliant and reproducible way.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let releases = capture_page(PARROT_MIRROR).await.or_network_error(PARROT_MIRROR)?;
+        let release_regex = Regex::new(r#"href="(\d+)\.(\d+)/""#).unwrap();
+        let (major, minor) = release_regex
+            .captures_iter(&releases)
+            .filter_map(|c| Some((c[1].parse::<u32>().ok()?, c[2].parse::<u32>().ok()?)))
+            .max()
+            .ok_or(GenerateError::NoReleasesFound)?;
+        let release = format!("{major}.{minor}");
+        let mirror = format!("{PARROT_MIRROR}{release}/");
+
+        let page = capture_page(&mirror).await.or_network_error(&mirror)?;
+        let iso_regex = Regex::new(r#"href="(Parrot-(home|security)-[\d.]+_(amd64|arm64)\.iso)""#).unwrap();
+        // The hashes file lists every ISO once per algorithm
+        let checksum_regex = Regex::new(r"([0-9a-f]{64})\s+(\S+\.iso)").unwrap();
+        let mut checksums = CustomRegex(Arc::new(checksum_regex), 2, 1)
+            .fetch(&format!("{mirror}signed-hashes.txt"))
+            .await;
+
+        Ok(iso_regex
+            .captures_iter(&page)
+            .map(|c| c.extract())
+            .filter_map(|(_, [iso, edition, arch])| {
+                let checksum = checksums.as_mut().and_then(|cs| cs.remove(iso));
+                Some(Config {
+                    release: release.clone(),
+                    edition: Some(edition.to_string()),
+                    arch: arch_from_str(arch)?,
+                    iso: Some(vec![Source::Web(WebSource::new(
+                        format!("{mirror}{iso}"),
+                        checksum,
+                        None,
+                        None,
+                    ))]),
+                    ..Default::default()
+                })
+            })
+            .collect::<Vec<Config>>())
+    }
+}
+
+const BACKBOX_MIRROR: &str = "https://sourceforge.net/projects/backbox/files/";
+
+pub struct BackBox;
+impl Distro for BackBox {
+    const NAME: &'static str = "backbox";
+    const PRETTY_NAME: &'static str = "BackBox";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.backbox.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Ubuntu-based penetration testing and security assessment distribution, providing a fast and easy to use desktop.");
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let releases = capture_page(BACKBOX_MIRROR).await.or_network_error(BACKBOX_MIRROR)?;
+        let release_regex = Regex::new(r#""name":"BackBox-([\d.]+)""#).unwrap();
+        let iso_regex = Arc::new(Regex::new(r#""name":"(backbox-[\d.]+-desktop-amd64\.iso)""#).unwrap());
+
+        let futures = release_regex.captures_iter(&releases).take(Self::release_limit()).map(|c| {
+            let release = c[1].to_string();
+            let mirror = format!("{BACKBOX_MIRROR}BackBox-{release}/");
+            let iso_regex = iso_regex.clone();
+            async move {
+                let page = capture_page(&mirror).await?;
+                let iso = &iso_regex.captures(&page)?[1];
+                let checksum = single_checksum(&format!("{mirror}{iso}.sha256/download")).await;
+                Some(Config {
+                    release,
+                    iso: Some(vec![Source::Web(WebSource::new(
+                        format!("{mirror}{iso}/download"),
+                        checksum,
+                        None,
+                        Some(iso.to_string()),
+                    ))]),
+                    ..Default::default()
+                })
+            }
+        });
+
+        Ok(join_futures!(futures, 1))
+    }
+}
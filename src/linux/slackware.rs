@@ -0,0 +1,104 @@
+use crate::{
+    checksums::single_checksum,
+    error::{GenerateError, OrGenerateError},
+    store_data::{ChecksumAlgorithm, Config, Distro, Source, WebSource},
+    utils::capture_page,
+};
+use join_futures::join_futures;
+use regex::Regex;
+use std::sync::Arc;
+
+const SLACKWARE_MIRROR: &str = "https://mirrors.slackware.com/slackware/slackware-iso/";
+const SLACKWARE_CURRENT_MIRROR: &str = "https://slackware.uk/people/alien-current-iso/slackware64-current-iso/";
+
+pub struct Slackware;
+impl Distro for Slackware {
+    const NAME: &'static str = "slackware";
+    const PRETTY_NAME: &'static str = "Slackware";
+    const HOMEPAGE: Option<&'static str> = Some("http://www.slackware.com/");
+    const DESCRIPTION: Option<&'static str> = Some("Advanced Linux operating system, designed with the twin goals of ease of use and stability as top priorities.");
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Md5;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let releases = capture_page(SLACKWARE_MIRROR).await.or_network_error(SLACKWARE_MIRROR)?;
+        let release_regex = Regex::new(r#"href="slackware64-(\d+)\.(\d+)-iso/""#).unwrap();
+
+        let (major, minor) = release_regex
+            .captures_iter(&releases)
+            .filter_map(|c| Some((c[1].parse::<u32>().ok()?, c[2].parse::<u32>().ok()?)))
+            .max()
+            .ok_or(GenerateError::NoReleasesFound)?;
+        let release = format!("{major}.{minor}");
+        let stable_mirror = format!("{SLACKWARE_MIRROR}slackware64-{release}-iso/");
+
+        let futures = [
+            (release, stable_mirror, r#"href="(slackware64-[\d.]+-install-dvd\.iso)""#),
+            (
+                "current".to_string(),
+                SLACKWARE_CURRENT_MIRROR.to_string(),
+                r#"href="(slackware64-current-install-dvd\.iso)""#,
+            ),
+        ]
+        .into_iter()
+        .map(|(release, mirror, iso_regex)| async move {
+            let page = capture_page(&mirror).await?;
+            let iso = &Regex::new(iso_regex).unwrap().captures(&page)?[1];
+            let url = format!("{mirror}{iso}");
+            let checksum = single_checksum(&format!("{url}.md5")).await;
+            Some(Config {
+                release,
+                iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                ..Default::default()
+            })
+        });
+
+        Ok(join_futures!(futures, 1))
+    }
+}
+
+const SALIX_MIRROR: &str = "https://sourceforge.net/projects/salix/files/";
+
+pub struct Salix;
+impl Distro for Salix {
+    const NAME: &'static str = "salix";
+    const PRETTY_NAME: &'static str = "Salix";
+    const HOMEPAGE: Option<&'static str> = Some("https://salixos.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Linux distribution based on Slackware that is simple, fast, easy to use and compatible with Slackware Linux.");
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Md5;
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let releases = capture_page(SALIX_MIRROR).await.or_network_error(SALIX_MIRROR)?;
+        let release_regex = Regex::new(r#""name":"(\d+\.\d+)""#).unwrap();
+        let iso_regex = Arc::new(Regex::new(r#""name":"(salix64-([a-z]+)-[\d.]+\.iso)""#).unwrap());
+
+        let futures = release_regex.captures_iter(&releases).take(Self::release_limit()).map(|c| {
+            let release = c[1].to_string();
+            let mirror = format!("{SALIX_MIRROR}{release}/");
+            let iso_regex = iso_regex.clone();
+            async move {
+                let page = capture_page(&mirror).await?;
+                let futures = iso_regex
+                    .captures_iter(&page)
+                    .map(|c| c.extract())
+                    .map(|(_, [iso, edition])| {
+                        let url = format!("{mirror}{iso}/download");
+                        let file_name = iso.to_string();
+                        let checksum_url = format!("{mirror}{iso}.md5/download");
+                        let release = release.clone();
+                        async move {
+                            let checksum = single_checksum(&checksum_url).await;
+                            Config {
+                                release,
+                                edition: Some(edition.to_string()),
+                                iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, Some(file_name)))]),
+                                ..Default::default()
+                            }
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                Some(join_futures!(futures))
+            }
+        });
+
+        Ok(join_futures!(futures, 2))
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{
+    checksums::single_checksum,
+    error::{GenerateError, OrGenerateError},
+    store_data::{Arch, Config, Distro, Source, WebSource},
+    utils::{capture_page, list_links_matching},
+};
+use join_futures::join_futures;
+use regex::Regex;
+use std::sync::Arc;
+
+const LEAP_MIRROR: &str = "https://download.opensuse.org/distribution/leap/";
+
+pub struct OpenSUSELeap;
+impl Distro for OpenSUSELeap {
+    const NAME: &'static str = "opensuse-leap";
+    const PRETTY_NAME: &'static str = "openSUSE Leap";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.opensuse.org/#Leap");
+    const DESCRIPTION: Option<&'static str> = Some("Stable, regularly released distribution sharing a common core with SUSE Linux Enterprise.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let releases = capture_page(LEAP_MIRROR).await.or_network_error(LEAP_MIRROR)?;
+        let releases_regex = Regex::new(r#"href="(?:\./)?((\d+)\.(\d+))/""#).unwrap();
+        let iso_regex = Arc::new(Regex::new(r#"href="(?:\./)?(openSUSE-Leap-[\d.]+-(DVD|NET)-(x86_64|aarch64)-Media\.iso)""#).unwrap());
+
+        let mut releases = releases_regex
+            .captures_iter(&releases)
+            .filter_map(|c| Some((c[1].to_string(), c[2].parse::<u32>().ok()?, c[3].parse::<u32>().ok()?)))
+            // Leap 42.x predates the switch to SLE version numbers
+            .filter(|(_, major, _)| *major < 42)
+            .collect::<Vec<_>>();
+        releases.sort_unstable_by_key(|(_, major, minor)| (*major, *minor));
+        releases.dedup();
+
+        let futures = releases.into_iter().rev().take(Self::release_limit()).map(|(release, ..)| {
+            let mirror = format!("{LEAP_MIRROR}{release}/iso/");
+            let iso_regex = iso_regex.clone();
+            async move {
+                let page = capture_page(&mirror).await?;
+                let futures = iso_regex
+                    .captures_iter(&page)
+                    .map(|c| c.extract())
+                    .map(|(_, [iso, edition, arch])| {
+                        let url = format!("{mirror}{iso}");
+                        let edition = edition.to_lowercase();
+                        let arch = if arch == "aarch64" { Arch::aarch64 } else { Arch::x86_64 };
+                        let release = release.clone();
+                        async move { suse_config(url, release, edition, arch).await }
+                    })
+                    .collect::<Vec<_>>();
+                Some(join_futures!(futures))
+            }
+        });
+
+        Ok(join_futures!(futures, 2))
+    }
+}
+
+const TUMBLEWEED_MIRROR: &str = "https://download.opensuse.org/tumbleweed/iso/";
+const TUMBLEWEED_AARCH64_MIRROR: &str = "https://download.opensuse.org/ports/aarch64/tumbleweed/iso/";
+
+pub struct OpenSUSETumbleweed;
+impl Distro for OpenSUSETumbleweed {
+    const NAME: &'static str = "opensuse-tumbleweed";
+    const PRETTY_NAME: &'static str = "openSUSE Tumbleweed";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.opensuse.org/#Tumbleweed");
+    const DESCRIPTION: Option<&'static str> = Some("Pure rolling release version of openSUSE containing the latest stable versions of all software.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let futures = [(Arch::x86_64, TUMBLEWEED_MIRROR), (Arch::aarch64, TUMBLEWEED_AARCH64_MIRROR)]
+            .into_iter()
+            .flat_map(|(arch, mirror)| {
+                ["DVD", "NET"].into_iter().map(move |edition| {
+                    let url = format!("{mirror}openSUSE-Tumbleweed-{edition}-{arch}-Current.iso");
+                    suse_config(url, "latest".to_string(), edition.to_lowercase(), arch.clone())
+                })
+            });
+
+        Ok(join_futures!(futures))
+    }
+}
+
+pub struct OpenSUSEMicroOS;
+impl Distro for OpenSUSEMicroOS {
+    const NAME: &'static str = "opensuse-microos";
+    const PRETTY_NAME: &'static str = "openSUSE MicroOS";
+    const HOMEPAGE: Option<&'static str> = Some("https://microos.opensuse.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Transactional, self-updating operating system built on Tumbleweed, designed to host containerized workloads.");
+    const IMMUTABLE: bool = true;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let futures = [(Arch::x86_64, TUMBLEWEED_MIRROR), (Arch::aarch64, TUMBLEWEED_AARCH64_MIRROR)]
+            .into_iter()
+            .flat_map(|(arch, mirror)| {
+                ["DVD", "NET"].into_iter().map(move |edition| {
+                    let url = format!("{mirror}openSUSE-MicroOS-{edition}-{arch}-Current.iso");
+                    suse_config(url, "latest".to_string(), edition.to_lowercase(), arch.clone())
+                })
+            });
+
+        Ok(join_futures!(futures))
+    }
+}
+
+const SUSE_APPLIANCE_MIRROR: &str = "https://download.opensuse.org/tumbleweed/appliances/iso/";
+
+pub struct OpenSUSEAeon;
+impl Distro for OpenSUSEAeon {
+    const NAME: &'static str = "opensuse-aeon";
+    const PRETTY_NAME: &'static str = "openSUSE Aeon";
+    const HOMEPAGE: Option<&'static str> = Some("https://aeondesktop.github.io/");
+    const DESCRIPTION: Option<&'static str> = Some("Immutable GNOME desktop built on MicroOS, where applications are installed as Flatpaks or within Distrobox containers.");
+    const IMMUTABLE: bool = true;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        suse_desktop_appliance("aeon").await
+    }
+}
+
+pub struct OpenSUSEKalpa;
+impl Distro for OpenSUSEKalpa {
+    const NAME: &'static str = "opensuse-kalpa";
+    const PRETTY_NAME: &'static str = "openSUSE Kalpa";
+    const HOMEPAGE: Option<&'static str> = Some("https://kalpadesktop.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Immutable KDE Plasma desktop built on MicroOS, the Plasma sibling of Aeon.");
+    const IMMUTABLE: bool = true;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        suse_desktop_appliance("kalpa").await
+    }
+}
+
+/// Aeon and Kalpa are published as self-installing appliance ISOs, which are rebuilt alongside Tumbleweed
+async fn suse_desktop_appliance(name: &str) -> Result<Vec<Config>, GenerateError> {
+    let iso_regex = Regex::new(&format!(r"(?i)^opensuse-{name}[.-][^/]*x86_64[^/]*\.iso$")).unwrap();
+    let isos = list_links_matching(SUSE_APPLIANCE_MIRROR, &iso_regex)
+        .await
+        .or_network_error(SUSE_APPLIANCE_MIRROR)?;
+    // Builds are listed under both their build number and a name without it, which always points to the newest
+    let iso = isos
+        .into_iter()
+        .min_by_key(|iso| iso.chars().filter(char::is_ascii_digit).count())
+        .ok_or(GenerateError::NoReleasesFound)?;
+    let url = format!("{SUSE_APPLIANCE_MIRROR}{iso}");
+    Ok(vec![
+        suse_config(url, "latest".to_string(), "installer".to_string(), Arch::x86_64).await,
+    ])
+}
+
+async fn suse_config(url: String, release: String, edition: String, arch: Arch) -> Config {
+    let checksum = single_checksum(&format!("{url}.sha256")).await;
+    Config {
+        release,
+        edition: Some(edition),
+        arch,
+        iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+        ..Default::default()
+    }
+}
@@ -1,17 +1,27 @@
 use crate::{
-    store_data::{Config, Distro, Source, WebSource},
-    utils::capture_page,
+    checksums::{single_checksum, ChecksumFormat, CustomRegex, Whitespace},
+    error::{GenerateError, OrGenerateError},
+    store_data::{cloud_image, Channel, ChecksumAlgorithm, ChecksumFileFormat, ChecksumSource, Config, ConfigMetadata, Disk, Distro, Signature, Source, SourceMetadata, WebSource},
+    utils::{arch_from_str, capture_page, list_links},
 };
 use join_futures::join_futures;
 use once_cell::sync::Lazy;
-use quickemu::config::Arch;
+use quickemu::config::{Arch, DiskFormat};
 use quickget_core::data_structures::ArchiveFormat;
 use regex::Regex;
 use serde::Deserialize;
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 use tokio::runtime::Runtime;
 
 const LAUNCHPAD_RELEASES_URL: &str = "https://api.launchpad.net/devel/ubuntu/series";
+// Ubuntu CD Image Automatic Signing Key (2012) <cdimage@ubuntu.com>
+const UBUNTU_SIGNING_KEY: &str = "843938DF228D22F7B3742BC0D94AA3F0EFE21092";
 
 pub struct Ubuntu;
 impl Distro for Ubuntu {
@@ -19,7 +29,8 @@ impl Distro for Ubuntu {
     const PRETTY_NAME: &'static str = "Ubuntu";
     const HOMEPAGE: Option<&'static str> = Some("https://www.ubuntu.com/");
     const DESCRIPTION: Option<&'static str> = Some("Complete desktop Linux operating system, freely available with both community and professional support.");
-    async fn generate_configs() -> Option<Vec<Config>> {
+    const EOL_PRODUCT: Option<&'static str> = Some("ubuntu");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
         get_ubuntu_releases(UbuntuVariant::Ubuntu).await
     }
 }
@@ -30,8 +41,11 @@ impl Distro for UbuntuServer {
     const PRETTY_NAME: &'static str = "Ubuntu Server";
     const HOMEPAGE: Option<&'static str> = Some("https://www.ubuntu.com/server");
     const DESCRIPTION: Option<&'static str> = Some("Brings economic and technical scalability to your datacentre, public or private. Whether you want to deploy an OpenStack cloud, a Kubernetes cluster or a 50,000-node render farm, Ubuntu Server delivers the best value scale-out performance available.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        get_ubuntu_releases(UbuntuVariant::UbuntuServer).await
+    const EOL_PRODUCT: Option<&'static str> = Some("ubuntu");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let mut configs = get_ubuntu_releases(UbuntuVariant::UbuntuServer).await?;
+        configs.extend(ubuntu_cloud_images().await);
+        Ok(configs)
     }
 }
 
@@ -41,7 +55,7 @@ impl Distro for UbuntuUnity {
     const PRETTY_NAME: &'static str = "Ubuntu Unity";
     const HOMEPAGE: Option<&'static str> = Some("https://ubuntuunity.org/");
     const DESCRIPTION: Option<&'static str> = Some("Flavor of Ubuntu featuring the Unity7 desktop environment (the default desktop environment used by Ubuntu from 2010-2017).");
-    async fn generate_configs() -> Option<Vec<Config>> {
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
         get_ubuntu_releases(UbuntuVariant::UbuntuUnity).await
     }
 }
@@ -52,7 +66,7 @@ impl Distro for UbuntuStudio {
     const PRETTY_NAME: &'static str = "Ubuntu Studio";
     const HOMEPAGE: Option<&'static str> = Some("https://ubuntustudio.org/");
     const DESCRIPTION: Option<&'static str> = Some("Comes preinstalled with a selection of the most common free multimedia applications available, and is configured for best performance for various purposes: Audio, Graphics, Video, Photography and Publishing.");
-    async fn generate_configs() -> Option<Vec<Config>> {
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
         get_ubuntu_releases(UbuntuVariant::UbuntuStudio).await
     }
 }
@@ -64,7 +78,7 @@ impl Distro for UbuntuMATE {
     const HOMEPAGE: Option<&'static str> = Some("https://ubuntu-mate.org/");
     const DESCRIPTION: Option<&'static str> =
         Some("Stable, easy-to-use operating system with a configurable desktop environment. It is ideal for those who want the most out of their computers and prefer a traditional desktop metaphor.");
-    async fn generate_configs() -> Option<Vec<Config>> {
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
         get_ubuntu_releases(UbuntuVariant::UbuntuMATE).await
     }
 }
@@ -75,7 +89,7 @@ impl Distro for UbuntuBudgie {
     const PRETTY_NAME: &'static str = "Ubuntu Budgie";
     const HOMEPAGE: Option<&'static str> = Some("https://ubuntubudgie.org/");
     const DESCRIPTION: Option<&'static str> = Some("Community developed distribution, integrating the Budgie Desktop Environment with Ubuntu at its core.");
-    async fn generate_configs() -> Option<Vec<Config>> {
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
         get_ubuntu_releases(UbuntuVariant::UbuntuBudgie).await
     }
 }
@@ -87,7 +101,7 @@ impl Distro for Lubuntu {
     const HOMEPAGE: Option<&'static str> = Some("https://lubuntu.me/");
     const DESCRIPTION: Option<&'static str> =
         Some("Complete Operating System that ships the essential apps and services for daily use: office applications, PDF reader, image editor, music and video players, etc.");
-    async fn generate_configs() -> Option<Vec<Config>> {
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
         get_ubuntu_releases(UbuntuVariant::Lubuntu).await
     }
 }
@@ -98,7 +112,7 @@ impl Distro for Kubuntu {
     const PRETTY_NAME: &'static str = "Kubuntu";
     const HOMEPAGE: Option<&'static str> = Some("https://kubuntu.org/");
     const DESCRIPTION: Option<&'static str> = Some("Free, complete, and open-source alternative to Microsoft Windows and Mac OS X which contains everything you need to work, play, or share.");
-    async fn generate_configs() -> Option<Vec<Config>> {
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
         get_ubuntu_releases(UbuntuVariant::Kubuntu).await
     }
 }
@@ -109,7 +123,7 @@ impl Distro for Xubuntu {
     const PRETTY_NAME: &'static str = "Xubuntu";
     const HOMEPAGE: Option<&'static str> = Some("https://xubuntu.org/");
     const DESCRIPTION: Option<&'static str> = Some("Elegant and easy to use operating system. Xubuntu comes with Xfce, which is a stable, light and configurable desktop environment.");
-    async fn generate_configs() -> Option<Vec<Config>> {
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
         get_ubuntu_releases(UbuntuVariant::Xubuntu).await
     }
 }
@@ -120,7 +134,7 @@ impl Distro for Edubuntu {
     const PRETTY_NAME: &'static str = "Edubuntu";
     const HOMEPAGE: Option<&'static str> = Some("https://www.edubuntu.org/");
     const DESCRIPTION: Option<&'static str> = Some("Stable, secure and privacy concious option for schools.");
-    async fn generate_configs() -> Option<Vec<Config>> {
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
         get_ubuntu_releases(UbuntuVariant::Edubuntu).await
     }
 }
@@ -132,7 +146,7 @@ impl Distro for UbuntuCinnamon {
     const HOMEPAGE: Option<&'static str> = Some("https://ubuntucinnamon.org/");
     const DESCRIPTION: Option<&'static str> =
         Some("Community-driven, featuring Linux Mint’s Cinnamon Desktop with Ubuntu at the core, packed fast and full of features, here is the most traditionally modern desktop you will ever love.");
-    async fn generate_configs() -> Option<Vec<Config>> {
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
         get_ubuntu_releases(UbuntuVariant::UbuntuCinnamon).await
     }
 }
@@ -144,25 +158,26 @@ impl Distro for UbuntuKylin {
     const HOMEPAGE: Option<&'static str> = Some("https://www.ubuntukylin.com/");
     const DESCRIPTION: Option<&'static str> =
         Some("Universal desktop operating system for personal computers, laptops, and embedded devices. It is dedicated to bringing a smarter user experience to users all over the world.");
-    async fn generate_configs() -> Option<Vec<Config>> {
+    const LOCALE: Option<&'static str> = Some("zh_CN");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
         get_ubuntu_releases(UbuntuVariant::UbuntuKylin).await
     }
 }
 
-async fn get_ubuntu_releases(variant: UbuntuVariant) -> Option<Vec<Config>> {
+async fn get_ubuntu_releases(variant: UbuntuVariant) -> Result<Vec<Config>, GenerateError> {
     let futures = UBUNTU_RELEASES.iter().flat_map(|release| {
         variant
             .supported_architectures()
             .into_iter()
-            .map(move |arch| {
+            .flat_map(move |arch| {
                 let arch_text = match arch {
-                    Arch::x86_64 => "amd64.iso",
-                    Arch::aarch64 => "arm64.iso",
-                    Arch::riscv64 => "riscv64.img.gz",
+                    Arch::x86_64 => "amd64",
+                    Arch::aarch64 => "arm64",
+                    Arch::riscv64 => "riscv64",
                 };
                 let mut release = release.clone();
                 let url = match (release.as_str(), &variant, &arch) {
-                    ("daily-live", ..) => format!("https://cdimage.ubuntu.com/{}/{release}/current/", variant.as_ref()),
+                    (r, ..) if r.ends_with("-devel") => format!("https://cdimage.ubuntu.com/{}/daily-live/current/", variant.as_ref()),
                     ("22.04", UbuntuVariant::Ubuntu, Arch::aarch64) => {
                         release += "-daily";
                         "https://cdimage.ubuntu.com/jammy/daily-live/current/".into()
@@ -177,36 +192,95 @@ async fn get_ubuntu_releases(variant: UbuntuVariant) -> Option<Vec<Config>> {
                     UbuntuVariant::UbuntuStudio => "dvd",
                     _ => "desktop",
                 };
-                async move {
-                    let text = match capture_page(&format!("{}SHA256SUMS", url)).await {
-                        Some(text) => text,
-                        None => capture_page(&format!("{}MD5SUMS", url)).await?,
-                    };
-
-                    let line = text.lines().find(|l| l.contains(arch_text) && l.contains(sku))?;
-                    let checksum = line.split_whitespace().next().map(ToString::to_string);
-                    let iso = format!("{url}{}", line.split('*').nth(1)?);
-
-                    Some(match arch {
-                        Arch::riscv64 => Config {
-                            img: Some(vec![Source::Web(WebSource::new(iso, checksum, Some(ArchiveFormat::Gz), None))]),
-                            release,
-                            arch,
-                            ..Default::default()
-                        },
-                        _ => Config {
-                            iso: Some(vec![Source::Web(WebSource::new(iso, checksum, None, None))]),
+                // Images are matched by the end of their file name, other files in the directory target specific boards, e.g. `riscv64+unmatched`
+                let mut images = vec![(None, format!("-{sku}-{arch_text}.iso"))];
+                // The generic preinstalled server image boots directly on QEMU's virt machine, rather than being installed onto a disk
+                if arch == Arch::riscv64 && matches!(variant, UbuntuVariant::UbuntuServer) {
+                    images.push((Some("preinstalled"), format!("-preinstalled-server-{arch_text}.img.xz")));
+                }
+
+                images.into_iter().map(move |(edition, suffix)| {
+                    let (release, url, arch) = (release.clone(), url.clone(), arch.clone());
+                    async move {
+                        let (text, checksum_file, checksum_algorithm) = match capture_page(&format!("{url}SHA256SUMS")).await {
+                            Some(text) => (text, "SHA256SUMS", ChecksumAlgorithm::Sha256),
+                            None => (capture_page(&format!("{url}MD5SUMS")).await?, "MD5SUMS", ChecksumAlgorithm::Md5),
+                        };
+
+                        let (checksum, file) = text.lines().find_map(|l| {
+                            let (checksum, file) = l.split_once(" *")?;
+                            file.ends_with(&suffix).then_some((checksum.to_string(), file))
+                        })?;
+                        let iso = format!("{url}{file}");
+                        // Torrents are only published for finished releases, not daily builds
+                        let torrent = capture_page(&url)
+                            .await
+                            .filter(|page| page.contains(&format!("href=\"{file}.torrent\"")))
+                            .map(|_| format!("{iso}.torrent"));
+                        if url.contains("/daily-live/") {
+                            ConfigMetadata::attach(&iso, |m| m.channel = Some(Channel::Nightly));
+                        }
+                        SourceMetadata::attach(&iso, |m| {
+                            m.torrent = torrent;
+                            m.signature = Some(Signature::checksum_file(
+                                format!("{url}{checksum_file}.gpg"),
+                                format!("{url}{checksum_file}"),
+                                ChecksumFileFormat::Whitespace,
+                                UBUNTU_SIGNING_KEY,
+                            ));
+                            m.checksum_algorithm = Some(checksum_algorithm);
+                        });
+
+                        let mut config = Config {
                             release,
+                            edition: edition.map(ToString::to_string),
                             arch,
                             ..Default::default()
-                        },
-                    })
-                }
+                        };
+                        if iso.ends_with(".img.xz") {
+                            config.disk_images = Some(vec![Disk {
+                                source: Source::Web(WebSource::new(iso, Some(checksum), Some(ArchiveFormat::Xz), None)),
+                                format: DiskFormat::Raw,
+                                ..Default::default()
+                            }]);
+                        } else {
+                            config.iso = Some(vec![Source::Web(WebSource::new(iso, Some(checksum), None, None))]);
+                        }
+                        Some(config)
+                    }
+                })
             })
             .collect::<Vec<_>>()
     });
 
-    Some(join_futures!(futures, 1))
+    Ok(join_futures!(futures, 1))
+}
+
+const UBUNTU_CLOUD_MIRROR: &str = "https://cloud-images.ubuntu.com/releases/";
+
+async fn ubuntu_cloud_images() -> Vec<Config> {
+    // Checksums are listed against the file name prefixed with an asterisk, denoting binary mode
+    let checksum_regex = Arc::new(Regex::new(r"(?m)^([0-9a-f]{64}) \*?(\S+)$").unwrap());
+    let futures = UBUNTU_RELEASES
+        .iter()
+        .filter(|release| !release.ends_with("-devel"))
+        .flat_map(|release| {
+            let mirror = format!("{UBUNTU_CLOUD_MIRROR}{release}/release/");
+            let checksums = Arc::new(ChecksumSource::new().aggregate(format!("{mirror}SHA256SUMS"), CustomRegex(checksum_regex.clone(), 2, 1)));
+            [(Arch::x86_64, "amd64"), (Arch::aarch64, "arm64")]
+                .into_iter()
+                .map(move |(arch, arch_text)| {
+                    let release = release.clone();
+                    let url = format!("{mirror}ubuntu-{release}-server-cloudimg-{arch_text}.img");
+                    let checksums = checksums.clone();
+                    async move {
+                        let checksum = checksums.find(&url).await;
+                        cloud_image(release, arch, WebSource::new(url, checksum, None, None), "ubuntu")
+                    }
+                })
+        });
+
+    join_futures!(futures)
 }
 
 static UBUNTU_RELEASES: Lazy<Vec<String>> = Lazy::new(|| {
@@ -216,19 +290,28 @@ static UBUNTU_RELEASES: Lazy<Vec<String>> = Lazy::new(|| {
     };
 
     let entries: Option<LaunchpadContents> = text.and_then(|t| serde_json::from_str(&t).ok());
-    let mut releases: Vec<String> = entries
+    let include_devel = INCLUDE_DEVEL.load(Ordering::Relaxed);
+    entries
         .map(|page| {
             page.entries
                 .into_iter()
-                .filter(|e| e.status == "Supported" || e.status == "Current Stable Release")
-                .map(|e| e.version)
+                .filter_map(|e| match e.status.as_str() {
+                    "Supported" | "Current Stable Release" => Some(e.version),
+                    "Active Development" | "Pre-release Freeze" if include_devel => Some(e.version + "-devel"),
+                    _ => None,
+                })
                 .collect()
         })
-        .unwrap_or_default();
-    releases.push("daily-live".to_string());
-    releases
+        .unwrap_or_default()
 });
 
+static INCLUDE_DEVEL: AtomicBool = AtomicBool::new(false);
+
+/// Include daily builds of the release currently in development, must be called before any Ubuntu configs are generated
+pub fn include_devel_releases() {
+    INCLUDE_DEVEL.store(true, Ordering::Relaxed);
+}
+
 #[derive(Copy, Clone)]
 enum UbuntuVariant {
     Ubuntu,
@@ -268,6 +351,7 @@ impl UbuntuVariant {
     fn supported_architectures(&self) -> Vec<Arch> {
         match self {
             UbuntuVariant::UbuntuServer => vec![Arch::x86_64, Arch::aarch64, Arch::riscv64],
+            // Desktop images for arm64 are published from 24.04 onwards, 22.04 only has daily builds
             UbuntuVariant::Ubuntu => vec![Arch::x86_64, Arch::aarch64],
             _ => vec![Arch::x86_64],
         }
@@ -294,22 +378,72 @@ impl Distro for Elementary {
     const PRETTY_NAME: &'static str = "elementary OS";
     const HOMEPAGE: Option<&'static str> = Some("https://elementary.io/");
     const DESCRIPTION: Option<&'static str> = Some("Thoughtful, capable, and ethical replacement for Windows and macOS.");
-    async fn generate_configs() -> Option<Vec<Config>> {
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
         let download_regex = Regex::new(r#"download-link http" href="(.*?)">Download"#).unwrap();
         let checksum_regex = Regex::new(r#""language-bash">([0-9a-f]{64})</code>"#).unwrap();
 
-        let page = capture_page(ELEMENTARY_URL).await?;
-        let dl_link = download_regex.captures(&page).map(|c| "https:".to_string() + &c[1])?;
+        let page = capture_page(ELEMENTARY_URL).await.or_network_error(ELEMENTARY_URL)?;
+        let dl_link = download_regex
+            .captures(&page)
+            .map(|c| "https:".to_string() + &c[1])
+            .or_parse_error("elementary OS' download link")?;
 
         let checksum = capture_page(ELEMENTARY_CHECKSUM_URL)
             .await
             .and_then(|html| checksum_regex.captures(&html).map(|c| c[1].to_string()));
 
-        vec![Config {
+        Ok(vec![Config {
             iso: Some(vec![Source::Web(WebSource::new(dl_link, checksum, None, None))]),
             ..Default::default()
-        }]
-        .into()
+        }])
+    }
+}
+
+const KDENEON_MIRROR: &str = "https://files.kde.org/neon/images/";
+// Testing builds the upcoming Plasma release, unstable builds KDE's development branches daily
+const KDENEON_EDITIONS: [(&str, Option<Channel>); 3] = [("user", None), ("testing", Some(Channel::Testing)), ("unstable", Some(Channel::Nightly))];
+
+pub struct KDENeon;
+impl Distro for KDENeon {
+    const NAME: &'static str = "kdeneon";
+    const PRETTY_NAME: &'static str = "KDE neon";
+    const HOMEPAGE: Option<&'static str> = Some("https://neon.kde.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Latest and greatest of KDE community software packaged on a rock-solid base.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let iso_regex = Arc::new(Regex::new(r"^neon-[a-z]+-(\d{8})-\d{4}\.iso$").unwrap());
+        let futures = KDENEON_EDITIONS.into_iter().map(|(edition, channel)| {
+            let iso_regex = iso_regex.clone();
+            async move {
+                // Only the newest build is kept in current/, older ones are moved to dated directories
+                let mirror = format!("{KDENEON_MIRROR}{edition}/current/");
+                let links = list_links(&mirror).await?;
+                let iso = links.iter().find(|link| iso_regex.is_match(link))?;
+                let release = iso_regex.captures(iso)?[1].to_string();
+                let url = format!("{mirror}{iso}");
+                let checksum_file = links
+                    .iter()
+                    .find(|link| link.ends_with(".sha256sum") && !link.contains("-current"));
+                let checksum = match checksum_file {
+                    Some(checksum_file) => {
+                        ChecksumSource::new()
+                            .aggregate(format!("{mirror}{checksum_file}"), Whitespace)
+                            .find(&url)
+                            .await
+                    }
+                    None => None,
+                };
+                if let Some(channel) = channel {
+                    ConfigMetadata::attach(&url, |m| m.channel = Some(channel));
+                }
+                Some(Config {
+                    release,
+                    edition: Some(edition.to_string()),
+                    iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                    ..Default::default()
+                })
+            }
+        });
+        Ok(join_futures!(futures, 1))
     }
 }
 
@@ -321,12 +455,12 @@ impl Distro for Bodhi {
     const PRETTY_NAME: &'static str = "Bodhi";
     const HOMEPAGE: Option<&'static str> = Some("https://www.bodhilinux.com/");
     const DESCRIPTION: Option<&'static str> = Some("Lightweight distribution featuring the fast & fully customizable Moksha Desktop.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let page = capture_page(BODHI_MIRROR).await?;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(BODHI_MIRROR).await.or_network_error(BODHI_MIRROR)?;
         let release_regex = Regex::new(r#""name":"([0-9]+.[0-9]+.[0-9]+)""#).unwrap();
         let iso_regex = Arc::new(Regex::new(r#""name":"(bodhi-[0-9]+.[0-9]+.[0-9]+-64(-[^-.]+)?.iso)""#).unwrap());
 
-        let futures = release_regex.captures_iter(&page).take(3).map(|c| {
+        let futures = release_regex.captures_iter(&page).take(Self::release_limit()).map(|c| {
             let release = c[1].to_string();
             let mirror = format!("{BODHI_MIRROR}{release}/");
             let iso_regex = iso_regex.clone();
@@ -336,13 +470,12 @@ impl Distro for Bodhi {
                     let release = release.clone();
                     let edition = c.get(2).map(|m| m.as_str()[1..].to_string()).unwrap_or("standard".to_string());
                     let iso = format!("{mirror}{}/download", &c[1]);
+                    let file_name = c[1].to_string();
                     let checksum_url = format!("{mirror}{}.sha256/download", &c[1]);
                     async move {
-                        let checksum = capture_page(&checksum_url)
-                            .await
-                            .and_then(|c| c.split_whitespace().next().map(Into::into));
+                        let checksum = single_checksum(&checksum_url).await;
                         Config {
-                            iso: Some(vec![Source::Web(WebSource::new(iso, checksum, None, None))]),
+                            iso: Some(vec![Source::Web(WebSource::new(iso, checksum, None, Some(file_name)))]),
                             release,
                             edition: Some(edition),
                             ..Default::default()
@@ -353,6 +486,238 @@ impl Distro for Bodhi {
             }
         });
 
-        Some(join_futures!(futures, 2))
+        Ok(join_futures!(futures, 2))
+    }
+}
+
+const ANDUINOS_MIRROR: &str = "https://download.anduinos.com/";
+
+pub struct AnduinOS;
+impl Distro for AnduinOS {
+    const NAME: &'static str = "anduinos";
+    const PRETTY_NAME: &'static str = "AnduinOS";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.anduinos.com/");
+    const DESCRIPTION: Option<&'static str> = Some("Ubuntu-based distribution with a familiar Windows-like GNOME desktop, aimed at users migrating from Windows.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let major_html = capture_page(ANDUINOS_MIRROR).await.or_network_error(ANDUINOS_MIRROR)?;
+        let major_regex = Regex::new(r#"href="(\d+\.\d+)/""#).unwrap();
+        let release_regex = Arc::new(Regex::new(r#"href="(\d+\.\d+\.\d+)/""#).unwrap());
+        let iso_regex = Arc::new(Regex::new(r#"href="(AnduinOS-[\d.]+-([^".]+)\.iso)""#).unwrap());
+
+        let futures = major_regex.captures_iter(&major_html).map(|c| {
+            let mirror = format!("{ANDUINOS_MIRROR}{}/", &c[1]);
+            let release_regex = release_regex.clone();
+            let iso_regex = iso_regex.clone();
+            async move {
+                let release_html = capture_page(&mirror).await?;
+                // Each major version receives frequent point releases, only the newest is of interest
+                let release = release_regex
+                    .captures_iter(&release_html)
+                    .map(|c| c[1].to_string())
+                    .max_by_key(|r| r.split('.').map(|n| n.parse::<u32>().unwrap_or_default()).collect::<Vec<u32>>())?;
+                let mirror = format!("{mirror}{release}/");
+                let page = capture_page(&mirror).await?;
+
+                let futures = iso_regex
+                    .captures_iter(&page)
+                    .map(|c| {
+                        let release = release.clone();
+                        let edition = c[2].to_string();
+                        let url = format!("{mirror}{}", &c[1]);
+                        let checksum_url = url.clone() + ".sha256";
+                        async move {
+                            let checksum = single_checksum(&checksum_url).await;
+                            Config {
+                                release,
+                                edition: Some(edition),
+                                iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                                ..Default::default()
+                            }
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                Some(join_futures!(futures))
+            }
+        });
+
+        Ok(join_futures!(futures, 2))
+    }
+}
+
+const LINUXLITE_MIRROR: &str = "https://sourceforge.net/projects/linux-lite/files/";
+
+pub struct LinuxLite;
+impl Distro for LinuxLite {
+    const NAME: &'static str = "linuxlite";
+    const PRETTY_NAME: &'static str = "Linux Lite";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.linuxliteos.com/");
+    const DESCRIPTION: Option<&'static str> = Some("Your first simple, fast and free stop in the world of Linux.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(LINUXLITE_MIRROR).await.or_network_error(LINUXLITE_MIRROR)?;
+        let release_regex = Regex::new(r#""name":"(\d+\.\d+)""#).unwrap();
+        let iso_regex = Arc::new(Regex::new(r#""name":"(linux-lite-[\d.]+-64bit\.iso)""#).unwrap());
+
+        let futures = release_regex.captures_iter(&page).take(Self::release_limit()).map(|c| {
+            let release = c[1].to_string();
+            let mirror = format!("{LINUXLITE_MIRROR}{release}/");
+            let iso_regex = iso_regex.clone();
+            async move {
+                let release_page = capture_page(&mirror).await?;
+                let iso = &iso_regex.captures(&release_page)?[1];
+                let checksum = single_checksum(&format!("{mirror}{iso}.sha256/download")).await;
+                Some(Config {
+                    release,
+                    iso: Some(vec![Source::Web(WebSource::new(
+                        format!("{mirror}{iso}/download"),
+                        checksum,
+                        None,
+                        Some(iso.to_string()),
+                    ))]),
+                    ..Default::default()
+                })
+            }
+        });
+
+        Ok(join_futures!(futures, 1))
+    }
+}
+
+const POPOS_API: &str = "https://api.pop-os.org/builds/";
+const POPOS_RELEASES: [&str; 2] = ["22.04", "24.04"];
+
+pub struct PopOS;
+impl Distro for PopOS {
+    const NAME: &'static str = "popos";
+    const PRETTY_NAME: &'static str = "Pop!_OS";
+    const HOMEPAGE: Option<&'static str> = Some("https://pop.system76.com/");
+    const DESCRIPTION: Option<&'static str> = Some("Operating system for STEM and creative professionals who use their computer as a tool to discover and create.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let futures = POPOS_RELEASES.iter().flat_map(|release| {
+            ["intel", "nvidia"].into_iter().flat_map(move |edition| {
+                [(Arch::x86_64, "amd64"), (Arch::aarch64, "arm64")]
+                    .into_iter()
+                    .map(move |(arch, arch_text)| async move {
+                        let data = capture_page(&format!("{POPOS_API}{release}/{edition}?arch={arch_text}")).await?;
+                        let build: PopOSBuild = serde_json::from_str(&data).ok()?;
+                        Some(Config {
+                            release: release.to_string(),
+                            edition: Some(edition.to_string()),
+                            arch,
+                            iso: Some(vec![Source::Web(WebSource::new(build.url, Some(build.sha_sum), None, None))]),
+                            ..Default::default()
+                        })
+                    })
+            })
+        });
+
+        Ok(join_futures!(futures, 1))
+    }
+}
+
+#[derive(Deserialize)]
+struct PopOSBuild {
+    url: String,
+    sha_sum: String,
+}
+
+const TRISQUEL_MIRROR: &str = "https://cdimage.trisquel.info/trisquel-images/";
+
+pub struct Trisquel;
+impl Distro for Trisquel {
+    const NAME: &'static str = "trisquel";
+    const PRETTY_NAME: &'static str = "Trisquel";
+    const HOMEPAGE: Option<&'static str> = Some("https://trisquel.info/");
+    const DESCRIPTION: Option<&'static str> = Some("Fully free Ubuntu based distribution endorsed by the Free Software Foundation, for home users, small enterprises and educational centers.");
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(TRISQUEL_MIRROR).await.or_network_error(TRISQUEL_MIRROR)?;
+        let iso_regex = Regex::new(r#"href="((trisquel|triskel|trisquel-mini|trisquel-sugar|trisquel-netinst)_(\d+(?:\.\d+)*)_(amd64|arm64)\.iso)""#).unwrap();
+
+        let isos = iso_regex.captures_iter(&page).map(|c| c.extract::<4>()).collect::<Vec<_>>();
+        let mut releases = isos.iter().map(|(_, [.., release, _])| *release).collect::<Vec<&str>>();
+        releases.sort_by_cached_key(|r| std::cmp::Reverse(r.split('.').map(|n| n.parse::<u32>().unwrap_or_default()).collect::<Vec<u32>>()));
+        releases.dedup();
+        releases.truncate(Self::release_limit());
+
+        let futures = isos
+            .into_iter()
+            .filter(|(_, [.., release, _])| releases.contains(release))
+            .filter_map(|(_, [iso, edition, release, arch])| {
+                // The standard edition is named after the distro itself and ships MATE, the others after their desktop or purpose
+                let edition = match edition {
+                    "trisquel" => "mate",
+                    "triskel" => "triskel",
+                    edition => edition.strip_prefix("trisquel-")?,
+                };
+                let arch = arch_from_str(arch)?;
+                let url = format!("{TRISQUEL_MIRROR}{iso}");
+                Some(async move {
+                    let checksum = single_checksum(&format!("{url}.sha256")).await;
+                    Config {
+                        release: release.to_string(),
+                        edition: Some(edition.to_string()),
+                        arch,
+                        iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                        ..Default::default()
+                    }
+                })
+            });
+
+        Ok(join_futures!(futures))
+    }
+}
+
+const ZORIN_MIRROR: &str = "https://mirrors.edge.kernel.org/zorinos-isos/";
+
+pub struct Zorin;
+impl Distro for Zorin {
+    const NAME: &'static str = "zorin";
+    const PRETTY_NAME: &'static str = "Zorin OS";
+    const HOMEPAGE: Option<&'static str> = Some("https://zorin.com/os/");
+    const DESCRIPTION: Option<&'static str> = Some("Alternative to Windows and macOS designed to make your computer faster, more powerful and secure.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(ZORIN_MIRROR).await.or_network_error(ZORIN_MIRROR)?;
+        let major_regex = Regex::new(r#"href="(\d+)/""#).unwrap();
+        let iso_regex = Arc::new(Regex::new(r#"href="(Zorin-OS-([\d.]+)-(Core|Lite|Education)-64-bit(?:-r(\d+))?\.iso)""#).unwrap());
+
+        let futures = major_regex.captures_iter(&page).map(|c| {
+            let mirror = format!("{ZORIN_MIRROR}{}/", &c[1]);
+            let iso_regex = iso_regex.clone();
+            async move {
+                let page = capture_page(&mirror).await?;
+                let mut checksums = Whitespace.fetch(&format!("{mirror}SHA256SUMS")).await;
+                // Every point release, and any rebuilds of it, stay on the mirror. Only the newest of each edition is of interest
+                let mut latest: HashMap<String, (Vec<u32>, String, String)> = HashMap::new();
+                for c in iso_regex.captures_iter(&page) {
+                    let mut version: Vec<u32> = c[2].split('.').filter_map(|n| n.parse().ok()).collect();
+                    version.push(c.get(4).and_then(|r| r.as_str().parse().ok()).unwrap_or_default());
+                    let edition = c[3].to_lowercase();
+                    if latest.get(&edition).is_none_or(|(newest, ..)| *newest < version) {
+                        latest.insert(edition, (version, c[2].to_string(), c[1].to_string()));
+                    }
+                }
+                Some(
+                    latest
+                        .into_iter()
+                        .map(|(edition, (_, release, iso))| {
+                            let checksum = checksums.as_mut().and_then(|cs| cs.remove(&iso));
+                            Config {
+                                release,
+                                edition: Some(edition),
+                                iso: Some(vec![Source::Web(WebSource::new(
+                                    format!("{mirror}{iso}"),
+                                    checksum,
+                                    None,
+                                    None,
+                                ))]),
+                                ..Default::default()
+                            }
+                        })
+                        .collect::<Vec<Config>>(),
+                )
+            }
+        });
+
+        Ok(join_futures!(futures, 2))
     }
 }
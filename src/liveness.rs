@@ -0,0 +1,88 @@
+use crate::{fixtures, utils::capture_headers};
+use once_cell::sync::OnceCell;
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs::File,
+    path::PathBuf,
+    sync::Mutex,
+};
+
+static LIVENESS: OnceCell<Liveness> = OnceCell::new();
+
+/// Tracks how many consecutive runs each distro's homepage has been unreachable for.
+/// Derivatives sometimes die while their mirrors linger, a homepage which stays down is the clearest sign of that
+pub struct Liveness {
+    path: PathBuf,
+    /// Number of runs in a row each homepage failed to respond, homepages which responded aren't listed
+    failures: Mutex<BTreeMap<String, u32>>,
+    /// Consecutive failures after which a distro is flagged as possibly discontinued
+    threshold: u32,
+    flagged: Mutex<HashSet<String>>,
+}
+
+pub fn init(path: PathBuf, threshold: u32) {
+    let failures = match File::open(&path) {
+        Ok(file) => match serde_json::from_reader(file) {
+            Ok(failures) => failures,
+            Err(e) => {
+                tracing::error!("Unable to parse {}: {e}", path.display());
+                return;
+            }
+        },
+        // The state file is created by the first run which uses it
+        Err(_) => BTreeMap::new(),
+    };
+    let _ = LIVENESS.set(Liveness {
+        path,
+        failures: Mutex::new(failures),
+        threshold: threshold.max(1),
+        flagged: Mutex::new(HashSet::new()),
+    });
+}
+
+pub fn get() -> Option<&'static Liveness> {
+    LIVENESS.get()
+}
+
+/// Whether the distro's homepage has been unreachable for enough consecutive runs to flag it
+pub fn possibly_discontinued(name: &str) -> bool {
+    LIVENESS
+        .get()
+        .is_some_and(|liveness| liveness.flagged.lock().unwrap().contains(name))
+}
+
+impl Liveness {
+    /// Checks whether a distro's homepage responds, updating its count of consecutive failures.
+    /// Replayed runs have no network access, so they leave the state untouched
+    pub async fn check(&self, name: &str, homepage: Option<&str>) {
+        let Some(homepage) = homepage else {
+            return;
+        };
+        if fixtures::replaying() {
+            return;
+        }
+        let reachable = capture_headers(homepage).await.is_some();
+        let mut failures = self.failures.lock().unwrap();
+        if reachable {
+            failures.remove(name);
+            return;
+        }
+        let count = failures.entry(name.to_string()).or_default();
+        *count += 1;
+        if *count >= self.threshold {
+            tracing::warn!("Homepage {homepage} has been unreachable for {count} consecutive runs, {name} may be discontinued");
+            self.flagged.lock().unwrap().insert(name.to_string());
+        } else {
+            tracing::info!("Homepage {homepage} is unreachable ({count} consecutive runs)");
+        }
+    }
+    pub fn save(&self) {
+        let failures = self.failures.lock().unwrap();
+        let result = File::create(&self.path)
+            .map_err(|e| e.to_string())
+            .and_then(|file| serde_json::to_writer_pretty(file, &*failures).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            tracing::error!("Failed to save {}: {e}", self.path.display());
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::store_data::{web_sources_mut, ChecksumAlgorithm, Config, SourceMetadata, WebSource};
+use once_cell::sync::OnceCell;
+use std::{collections::BTreeMap, fs::File, path::PathBuf, sync::Mutex};
+
+static LOCKFILE: OnceCell<Lockfile> = OnceCell::new();
+
+/// Words within a release or URL path which show the artifact is replaced over time, so its checksum can't be pinned
+const MUTABLE_MARKERS: [&str; 8] = ["latest", "current", "daily", "nightly", "devel", "snapshot", "snapshots", "rolling"];
+
+/// Checksums of versioned artifacts, which never change once published.
+/// It's committed alongside the code, so a checksum file being briefly unavailable doesn't produce configs without checksums.
+pub struct Lockfile {
+    path: PathBuf,
+    checksums: Mutex<BTreeMap<String, String>>,
+}
+
+pub fn init(path: PathBuf) {
+    let checksums: BTreeMap<String, String> = match File::open(&path) {
+        Ok(file) => match serde_json::from_reader(file) {
+            Ok(checksums) => checksums,
+            Err(e) => {
+                tracing::error!("Unable to parse {}: {e}", path.display());
+                return;
+            }
+        },
+        // The lockfile is created by the first run which uses it
+        Err(_) => BTreeMap::new(),
+    };
+    let checksums = checksums
+        .into_iter()
+        .filter(|(url, checksum)| {
+            let valid = is_hex(checksum)
+                && [ChecksumAlgorithm::Md5, ChecksumAlgorithm::Sha256, ChecksumAlgorithm::Sha512]
+                    .iter()
+                    .any(|a| a.digest_length() == checksum.len());
+            if !valid {
+                tracing::warn!("Ignoring malformed checksum {checksum} of {url} in the lockfile");
+            }
+            valid
+        })
+        .collect();
+    let _ = LOCKFILE.set(Lockfile {
+        path,
+        checksums: Mutex::new(checksums),
+    });
+}
+
+pub fn get() -> Option<&'static Lockfile> {
+    LOCKFILE.get()
+}
+
+impl Lockfile {
+    /// Replaces the checksums of immutable artifacts with the pinned ones, filling in any the scraper couldn't find.
+    /// Pins which aren't a digest of the source's checksum algorithm are ignored
+    pub fn apply(&self, configs: &mut [Config], default_algorithm: ChecksumAlgorithm) {
+        let checksums = self.checksums.lock().unwrap();
+        for config in configs {
+            for source in immutable_sources(config) {
+                let Some(pinned) = checksums.get(&source.url) else {
+                    continue;
+                };
+                let algorithm = SourceMetadata::checksum_algorithm(&source.url, default_algorithm);
+                if pinned.len() != algorithm.digest_length() {
+                    tracing::warn!(
+                        "Ignoring pinned checksum {pinned} of {}, it isn't a {algorithm:?} digest",
+                        source.url
+                    );
+                    continue;
+                }
+                match &source.checksum {
+                    Some(checksum) if !checksum.eq_ignore_ascii_case(pinned) => {
+                        tracing::warn!(
+                            "Checksum of {} changed from {pinned} to {checksum}, keeping the pinned checksum",
+                            source.url
+                        )
+                    }
+                    Some(_) => {}
+                    None => tracing::info!("Using pinned checksum of {}", source.url),
+                }
+                source.checksum = Some(pinned.clone());
+            }
+        }
+    }
+    /// Pins the checksums of any immutable artifacts which aren't in the lockfile yet
+    pub fn record(&self, configs: &mut [Config]) {
+        let mut checksums = self.checksums.lock().unwrap();
+        for config in configs {
+            for source in immutable_sources(config) {
+                if let Some(checksum) = &source.checksum {
+                    checksums.entry(source.url.clone()).or_insert_with(|| checksum.clone());
+                }
+            }
+        }
+    }
+    pub fn save(&self) {
+        let checksums = self.checksums.lock().unwrap();
+        let result = File::create(&self.path)
+            .map_err(|e| e.to_string())
+            .and_then(|file| serde_json::to_writer_pretty(file, &*checksums).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            tracing::error!("Failed to save {}: {e}", self.path.display());
+        }
+    }
+}
+
+/// Sources of the config whose artifacts never change, those of a versioned release which aren't replaced by newer builds.
+/// Disk images are included alongside ISOs and other media
+fn immutable_sources(config: &mut Config) -> impl Iterator<Item = &mut WebSource> {
+    let mutable_release = is_mutable(&config.release);
+    web_sources_mut(config).filter(move |w| !mutable_release && !is_mutable(url_path(&w.url)))
+}
+
+/// Whether any word of the text is a marker, such as the `current` directory or the `daily` in `ubuntu-daily-live`.
+/// Words which merely contain a marker, like `currently`, don't count
+fn is_mutable(text: &str) -> bool {
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .any(|word| MUTABLE_MARKERS.iter().any(|marker| word.eq_ignore_ascii_case(marker)))
+}
+
+/// Path and file name of a URL, leaving out the host, whose name says nothing about the artifact
+fn url_path(url: &str) -> &str {
+    let url = url.split(['?', '#']).next().unwrap_or_default();
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    without_scheme.split_once('/').map_or("", |(_, path)| path)
+}
+
+fn is_hex(checksum: &str) -> bool {
+    !checksum.is_empty() && checksum.chars().all(|c| c.is_ascii_hexdigit())
+}
@@ -0,0 +1,84 @@
+use crate::{
+    error::{GenerateError, OrGenerateError},
+    store_data::{Config, Distro, Source, SourceMetadata, WebSource},
+    utils::{capture_headers, post_page},
+};
+use join_futures::join_futures;
+use quickemu::config::{GuestOS, MacOSRelease};
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE, COOKIE, SET_COOKIE, USER_AGENT};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MACOS_RECOVERY_URL: &str = "http://osrecovery.apple.com/";
+const MACOS_IMAGE_URL: &str = "http://osrecovery.apple.com/InstallationPayload/RecoveryImage";
+// Apple doesn't say how long asset tokens are valid for, so they're only relied upon until the data is next generated
+const ASSET_TOKEN_LIFETIME: u64 = 24 * 60 * 60;
+
+// Board IDs and MLBs of models which are served each release's recovery image
+const MACOS_RELEASES: [(&str, MacOSRelease, &str, &str); 5] = [
+    ("catalina", MacOSRelease::Catalina, "Mac-00BE6ED71E35EB86", "00000000000000000"),
+    ("big-sur", MacOSRelease::BigSur, "Mac-42FD25EABCABB274", "00000000000000000"),
+    ("monterey", MacOSRelease::Monterey, "Mac-E43C1C25D4880AD6", "00000000000000000"),
+    ("ventura", MacOSRelease::Ventura, "Mac-BE088AF8C5EB4FA2", "00000000000000000"),
+    ("sonoma", MacOSRelease::Sonoma, "Mac-827FAC58A8FDFA22", "00000000000000000"),
+];
+
+pub struct MacOS;
+impl Distro for MacOS {
+    const NAME: &'static str = "macos";
+    const PRETTY_NAME: &'static str = "macOS";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.apple.com/macos/");
+    const DESCRIPTION: Option<&'static str> =
+        Some("Work and play on your Mac are even more powerful. Elevate your presence on video calls. Access information in all-new ways. And discover even more ways to personalize your Mac.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        // Apple's recovery server requires a session cookie to be sent alongside requests for recovery images
+        let headers = capture_headers(MACOS_RECOVERY_URL).await.or_network_error(MACOS_RECOVERY_URL)?;
+        let session = headers
+            .get_all(SET_COOKIE)
+            .iter()
+            .filter_map(|cookie| cookie.to_str().ok())
+            .find(|cookie| cookie.starts_with("session="))
+            .and_then(|cookie| cookie.split(';').next())
+            .or_parse_error("Apple's recovery session cookie")?
+            .to_string();
+
+        let futures = MACOS_RELEASES.into_iter().map(|(release, macos_release, board_id, mlb)| {
+            let session = session.clone();
+            async move {
+                let mut headers = HeaderMap::new();
+                headers.insert(USER_AGENT, HeaderValue::from_static("InternetRecovery/1.0"));
+                headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+                headers.insert(COOKIE, HeaderValue::from_str(&session).ok()?);
+                let body = format!(
+                    "cid=3076CE439155BA14\nsn={mlb}\nbid={board_id}\nk={0}\nfg={0}\nos=default",
+                    "0".repeat(64)
+                );
+
+                let response = post_page(MACOS_IMAGE_URL, headers, body).await?;
+                let value = |key: &str| response.lines().find_map(|l| l.strip_prefix(key)).map(|v| v.trim().to_string());
+                // The recovery image is accompanied by a chunklist, which is used to verify it.
+                // Each is only served to requests carrying the asset token issued alongside its URL
+                let image = value("AU:")?;
+                let chunklist = value("CU:")?;
+                let expires = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs() + ASSET_TOKEN_LIFETIME;
+                for (url, token) in [(&image, value("AT:")?), (&chunklist, value("CT:")?)] {
+                    SourceMetadata::attach(url, |m| {
+                        m.headers.insert("Cookie".to_string(), format!("AssetToken={token}"));
+                        m.expires = Some(expires);
+                    });
+                }
+
+                Some(Config {
+                    release: release.to_string(),
+                    guest_os: GuestOS::MacOS { release: macos_release },
+                    img: Some(vec![
+                        Source::Web(WebSource::url_only(image)),
+                        Source::Web(WebSource::url_only(chunklist)),
+                    ]),
+                    ..Default::default()
+                })
+            }
+        });
+
+        Ok(join_futures!(futures, 1))
+    }
+}
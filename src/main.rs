@@ -1,131 +1,466 @@
-mod bsd;
-mod linux;
-mod other;
-mod store_data;
-mod utils;
+mod budget;
+mod changelog;
+mod diff;
+mod edition_filter;
+mod index;
+mod patch;
+mod shared_artifacts;
+mod signing;
+mod validate;
 
-use join_futures::join_futures;
-use std::{fs::File, io::Write};
-use store_data::{ToOS, OS};
-use tokio::spawn;
+use clap::{Parser, Subcommand};
+use flate2::{write::GzEncoder, Compression};
+use quickget_ci::{bsd, cache, fixtures, generate, linux, liveness, lockfile, metrics, progress, report, schema, store_data, utils, verification, Selection};
+use serde::{ser::SerializeMap, Serialize};
+use serde_json::{Map, Value};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use store_data::Arch;
+use tracing_subscriber::{filter::LevelFilter, layer::SubscriberExt, util::SubscriberInitExt};
+
+#[derive(Parser)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Directory in which pages and configs are kept between runs. Mirrors can skip sending pages which haven't changed,
+    /// and distros whose pages are all unchanged reuse their previous configs, which are still validated
+    #[arg(long)]
+    cache_dir: Option<PathBuf>,
+    /// JSON file pinning the checksums of versioned artifacts, which is read before generating and updated afterwards
+    #[arg(long)]
+    lockfile: Option<PathBuf>,
+    /// JSON file tracking how many consecutive runs each distro's homepage has been unreachable for. Homepages are only checked when it's set
+    #[arg(long)]
+    liveness_state: Option<PathBuf>,
+    /// Consecutive runs a homepage must be unreachable for before its distro is flagged as possibly discontinued
+    #[arg(long, default_value_t = 3)]
+    liveness_threshold: u32,
+    /// Only generate configs for these distros
+    #[arg(long, value_delimiter = ',', conflicts_with = "exclude")]
+    only: Vec<String>,
+    /// Generate configs for every distro except these
+    #[arg(long, value_delimiter = ',')]
+    exclude: Vec<String>,
+    /// Seconds to wait for a single distro before giving up on it
+    #[arg(long, default_value_t = 300)]
+    timeout: u64,
+    /// Save every response to this directory, so the run can be replayed later
+    #[arg(long, conflicts_with = "replay_fixtures")]
+    record_fixtures: Option<PathBuf>,
+    /// Serve responses from fixtures recorded in this directory, rather than accessing the network
+    #[arg(long)]
+    replay_fixtures: Option<PathBuf>,
+    /// Maximum number of concurrent requests
+    #[arg(long, env = "QUICKGET_MAX_CONNECTIONS", default_value_t = 150)]
+    max_connections: usize,
+    /// Maximum number of concurrent requests to a host and its subdomains, formatted as host=limit
+    #[arg(long = "host-limit", env = "QUICKGET_HOST_LIMITS", value_delimiter = ',', value_parser = parse_host_limit)]
+    host_limits: Vec<(String, usize)>,
+    /// Maximum random delay, in milliseconds, before each request to a rate limited host
+    #[arg(long, env = "QUICKGET_JITTER_MS", default_value_t = 250)]
+    jitter_ms: u64,
+    /// Seconds to wait before checking unresolvable URLs a second time
+    #[arg(long, default_value_t = 30)]
+    validation_retry_delay: u64,
+    /// Remove configs with unresolvable URLs, even if their checksums are known
+    #[arg(long)]
+    drop_unverified: bool,
+    /// Fail when a signed checksum file can't be verified because its key isn't embedded or gpg isn't installed,
+    /// rather than publishing its checksums unchecked
+    #[arg(long, env = "QUICKGET_STRICT_VERIFICATION")]
+    strict_verification: bool,
+    /// Run every scraper without checking the URLs they find, reporting how many configs each generated.
+    /// Much faster, but the output mustn't be published
+    #[arg(long)]
+    skip_url_validation: bool,
+    /// Number of releases to keep for a distro, formatted as distro=count. Overrides the release limits file
+    #[arg(long = "release-limit", env = "QUICKGET_RELEASE_LIMITS", value_delimiter = ',', value_parser = parse_release_limit)]
+    release_limits: Vec<(String, usize)>,
+    /// JSON file mapping distro names to the number of releases to keep for them
+    #[arg(long)]
+    release_limits_file: Option<PathBuf>,
+    /// JSON file mapping distro names to glob patterns of the editions to keep (`allow`) and remove (`deny`)
+    #[arg(long)]
+    edition_filter_file: Option<PathBuf>,
+    /// Remove a distro's editions matching a glob pattern, formatted as distro=pattern. Added to the edition filter file's deny lists
+    #[arg(long = "deny-edition", env = "QUICKGET_DENIED_EDITIONS", value_delimiter = ',', value_parser = parse_denied_edition)]
+    denied_editions: Vec<(String, String)>,
+    /// JSON file limiting the releases kept for each OS and the size of the output, pruning configs to stay within it
+    #[arg(long)]
+    budget_file: Option<PathBuf>,
+    /// What to do with images which appear under more than one OS, by URL or checksum
+    #[arg(long, value_enum, default_value = "warn")]
+    shared_artifacts: shared_artifacts::SharedArtifactPolicy,
+    /// Private key used to produce detached signatures of the output files. Nothing is signed without one
+    #[arg(long, env = "QUICKGET_SIGNING_KEY")]
+    signing_key: Option<PathBuf>,
+    /// Tool the signing key belongs to
+    #[arg(long, env = "QUICKGET_SIGNATURE_FORMAT", value_enum, default_value = "ssh")]
+    signature_format: signing::SignatureFormat,
+    /// URL of the currently published quickget_data.json. When set, the differences from it are written to CHANGELOG_RUN.md,
+    /// along with a JSON Patch which updates it to the new dataset
+    #[arg(long, env = "QUICKGET_PUBLISHED_DATA_URL")]
+    published_data_url: Option<String>,
+    /// Include daily builds of the Ubuntu release currently in development
+    #[arg(long)]
+    ubuntu_devel: bool,
+    /// Include weekly snapshot builds of FreeBSD's STABLE branches and CURRENT
+    #[arg(long)]
+    freebsd_snapshots: bool,
+    /// Include Fedora Server and the Fedora Cloud Base disk images, alongside the desktop editions, Spins and Labs
+    #[arg(long)]
+    fedora_server: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Compare two generated datasets, printing the operating systems, releases, URLs and checksums which changed
+    Diff {
+        old: PathBuf,
+        new: PathBuf,
+        /// Fail if more than this percentage of the old dataset's releases were removed
+        #[arg(long, default_value_t = 20.0)]
+        max_removed_percent: f64,
+    },
+    /// Check every URL in an existing dataset, reporting the ones which no longer resolve
+    Validate {
+        data: PathBuf,
+        /// Also flag URLs which respond with an empty body or an HTML page
+        #[arg(long)]
+        check_length: bool,
+        /// Where the list of dead links is written
+        #[arg(long, default_value = "validation_report.json")]
+        report: PathBuf,
+    },
+    /// Write the JSON Schemas of the published data, without generating anything
+    Schema {
+        #[arg(default_value = ".")]
+        directory: PathBuf,
+    },
+}
+
+/// Incremented whenever a change to the output would break existing consumers
+const SCHEMA_VERSION: u32 = 1;
+
+fn parse_host_limit(input: &str) -> Result<(String, usize), String> {
+    let (host, limit) = input.split_once('=').ok_or("Expected host=limit")?;
+    let limit = limit.parse().map_err(|e| format!("Invalid limit for {host}: {e}"))?;
+    Ok((host.to_string(), limit))
+}
+
+fn parse_release_limit(input: &str) -> Result<(String, usize), String> {
+    let (distro, count) = input.split_once('=').ok_or("Expected distro=count")?;
+    let count = count.parse().map_err(|e| format!("Invalid release count for {distro}: {e}"))?;
+    Ok((distro.to_string(), count))
+}
+
+fn parse_denied_edition(input: &str) -> Result<(String, String), String> {
+    let (distro, pattern) = input.split_once('=').ok_or("Expected distro=pattern")?;
+    Ok((distro.to_string(), pattern.to_string()))
+}
+
+impl Args {
+    fn release_limits(&self) -> Option<HashMap<String, usize>> {
+        let mut limits = match &self.release_limits_file {
+            Some(path) => {
+                let data = std::fs::read_to_string(path)
+                    .inspect_err(|e| tracing::error!("Unable to read {}: {e}", path.display()))
+                    .ok()?;
+                serde_json::from_str(&data)
+                    .inspect_err(|e| tracing::error!("Unable to parse {}: {e}", path.display()))
+                    .ok()?
+            }
+            None => HashMap::new(),
+        };
+        limits.extend(self.release_limits.iter().cloned());
+        Some(limits)
+    }
+
+    fn selection(&self) -> Selection {
+        Selection {
+            only: self.only.clone(),
+            exclude: self.exclude.clone(),
+            timeout: Duration::from_secs(self.timeout),
+        }
+    }
+}
+
+/// Options which change what scrapers return, so cached configs generated under other options aren't reused.
+/// Edition filters and the budget are left out, as they're applied to cached and freshly generated configs alike
+#[derive(Serialize)]
+struct GenerationOptions {
+    ubuntu_devel: bool,
+    freebsd_snapshots: bool,
+    fedora_server: bool,
+    release_limits: BTreeMap<String, usize>,
+}
+
+impl GenerationOptions {
+    fn new(args: &Args, release_limits: &HashMap<String, usize>) -> Self {
+        Self {
+            ubuntu_devel: args.ubuntu_devel,
+            freebsd_snapshots: args.freebsd_snapshots,
+            fedora_server: args.fedora_server,
+            release_limits: release_limits.iter().map(|(distro, limit)| (distro.clone(), *limit)).collect(),
+        }
+    }
+}
 
 #[tokio::main]
 async fn main() {
-    env_logger::Builder::new().filter_level(log::LevelFilter::Debug).init();
-    let futures = spawn_distros!(
-        bsd::FreeBSD,
-        linux::Ubuntu,
-        linux::UbuntuServer,
-        linux::UbuntuUnity,
-        linux::Lubuntu,
-        linux::Kubuntu,
-        linux::UbuntuMATE,
-        linux::UbuntuBudgie,
-        linux::UbuntuStudio,
-        linux::UbuntuKylin,
-        linux::Edubuntu,
-        linux::Xubuntu,
-        linux::UbuntuCinnamon,
-        linux::NixOS,
-        linux::Alma,
-        linux::Alpine,
-        linux::Antix,
-        linux::Archcraft,
-        linux::Elementary,
-        linux::ArchLinux,
-        linux::ArcoLinux,
-        linux::ArtixLinux,
-        linux::AthenaOS,
-        linux::Batocera,
-        linux::Bazzite,
-        linux::BigLinux,
-        linux::BlendOS,
-        linux::Bodhi,
-        linux::BunsenLabs,
-        linux::CachyOS,
-        linux::CentOSStream,
-        linux::ChimeraLinux,
-        linux::CrunchbangPlusPlus,
-        linux::Debian,
-        linux::Devuan,
-        bsd::DragonFlyBSD,
-        linux::EasyOS,
-        linux::EndeavourOS,
-        linux::EndlessOS,
-        linux::Fedora,
-        other::FreeDOS,
-        linux::Garuda,
-        linux::Garuda,
-        linux::Gentoo,
-        bsd::GhostBSD,
-        linux::GnomeOS,
-    );
+    tracing_subscriber::registry()
+        .with(LevelFilter::DEBUG)
+        .with(tracing_subscriber::fmt::layer().with_writer(|| progress::LogWriter))
+        .with(report::ReportLayer)
+        .init();
+    let args = Args::parse();
+    if let Some(Command::Diff { old, new, max_removed_percent }) = &args.command {
+        let success = diff::run(old, new, *max_removed_percent);
+        std::process::exit(if success { 0 } else { 1 });
+    }
+    if let Some(Command::Schema { directory }) = &args.command {
+        schema::write_schemas(directory);
+        return;
+    }
+    metrics::start();
+    utils::set_connection_limits(utils::ConnectionLimits {
+        max_connections: args.max_connections,
+        host_limits: args.host_limits.clone(),
+        jitter: Duration::from_millis(args.jitter_ms),
+    });
+    if let Some(Command::Validate { data, check_length, report }) = &args.command {
+        let success = validate::run(data, report, *check_length).await;
+        std::process::exit(if success { 0 } else { 1 });
+    }
+    store_data::set_validation_policy(store_data::ValidationPolicy {
+        retry_delay: Duration::from_secs(args.validation_retry_delay),
+        keep_unverified: !args.drop_unverified,
+        skip_urls: args.skip_url_validation,
+    });
+    let Some(release_limits) = args.release_limits() else {
+        std::process::exit(1);
+    };
+    store_data::set_release_limits(release_limits.clone());
+    let Some(edition_filters) = edition_filter::load(args.edition_filter_file.as_deref(), &args.denied_editions) else {
+        std::process::exit(1);
+    };
+    let Some(budget) = budget::load(args.budget_file.as_deref()) else {
+        std::process::exit(1);
+    };
+    if args.ubuntu_devel {
+        linux::include_devel_releases();
+    }
+    if args.freebsd_snapshots {
+        bsd::include_freebsd_snapshots();
+    }
+    if args.fedora_server {
+        linux::include_fedora_server_editions();
+    }
+    if let Some(cache_dir) = args.cache_dir.clone() {
+        cache::init(cache_dir, &GenerationOptions::new(&args, &release_limits));
+    }
+    if let Some(lockfile) = args.lockfile.clone() {
+        lockfile::init(lockfile);
+    }
+    if let Some(liveness_state) = args.liveness_state.clone() {
+        liveness::init(liveness_state, args.liveness_threshold);
+    }
+    if let Some(directory) = args.record_fixtures.clone() {
+        fixtures::init(fixtures::FixtureMode::Record(directory));
+    } else if let Some(directory) = args.replay_fixtures.clone() {
+        fixtures::init(fixtures::FixtureMode::Replay(directory));
+    }
+    progress::enable();
+    let mut distros = generate(&args.selection()).await;
+    if args.strict_verification && verification::verification_unavailable() {
+        tracing::error!("Some signed checksum files couldn't be verified, as their keys or gpg are missing");
+        std::process::exit(1);
+    }
+    edition_filter::apply(&mut distros, &edition_filters);
+    shared_artifacts::apply(&mut distros, args.shared_artifacts);
+    budget::apply(&mut distros, &budget, "budget_report.json");
+    let generated_at = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    // Metadata can only be inserted into the JSON, which every output is then written from
+    let mut output = serde_json::to_value(&distros).unwrap();
+    drop(distros);
+    store_data::insert_metadata(&mut output);
+    // quickget_data.json stays a bare array, which every existing quickget build reads
+    write_all_compressions(&output, "quickget_data");
+    index::write_index(&output, "quickget_index.jsonl");
+    // Published alongside the data, so it always describes the release it came with
+    schema::write_schemas(Path::new("."));
+    if let Some(url) = &args.published_data_url {
+        match utils::capture_large_page(url).await {
+            Some(published) => {
+                changelog::write_changelog(&published, &output, "CHANGELOG_RUN.md");
+                patch::write_patch(&published, &output, "quickget_data", "quickget_patches.json");
+            }
+            None => tracing::warn!("Unable to fetch the published dataset from {url}, no changelog or patch will be written"),
+        }
+    }
+    write_all_compressions(&Versioned::new(&output, generated_at), "quickget_data_versioned");
 
-    let distros = join_futures!(futures, 2, Vec<OS>).distro_sort();
+    // Consumers only interested in a single architecture can avoid downloading the entire dataset
+    for arch in [Arch::x86_64, Arch::aarch64, Arch::riscv64] {
+        let filtered = ArchFilter::new(&output, &arch);
+        write_all_compressions(&Versioned::new(filtered, generated_at), &format!("quickget_data_{arch}"));
+    }
+    let signed_files = ["quickget_data".to_string(), "quickget_data_versioned".to_string()]
+        .into_iter()
+        .chain([Arch::x86_64, Arch::aarch64, Arch::riscv64].map(|arch| format!("quickget_data_{arch}")))
+        .flat_map(|name| COMPRESSION_EXTENSIONS.map(|extension| format!("{name}.{extension}")))
+        .collect::<Vec<_>>();
+    signing::sign_outputs(
+        args.signing_key.as_ref(),
+        args.signature_format,
+        &signed_files,
+        "quickget_signatures.json",
+    );
+    report::write_report("generation_report.json");
+    metrics::write_metrics("metrics.prom").await;
 
-    if let Ok(output) = serde_json::to_string_pretty(&distros) {
-        println!("{}", output);
+    if let Some(cache) = cache::get() {
+        cache.save();
+    }
+    if let Some(lockfile) = lockfile::get() {
+        lockfile.save();
     }
+    if let Some(liveness) = liveness::get() {
+        liveness.save();
+    }
+}
 
-    let output = serde_json::to_string(&distros).unwrap();
+/// Wraps the list of operating systems, allowing consumers to detect data they're unable to parse
+#[derive(Serialize)]
+struct Versioned<T> {
+    schema_version: u32,
+    generated_at: u64,
+    distros: T,
+}
 
-    output.write_with_compression("quickget_data.json", CompressionType::None);
-    output.write_with_compression("quickget_data.json.gz", CompressionType::Gzip);
-    output.write_with_compression("quickget_data.json.zst", CompressionType::Zstd);
+impl<T: Serialize> Versioned<T> {
+    fn new(distros: T, generated_at: u64) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            generated_at,
+            distros,
+        }
+    }
 }
 
-trait DistroSort {
-    fn distro_sort(self) -> Self;
+/// The operating systems with only their configs for a single architecture, serialized straight from the full dataset rather than copied out of it.
+/// Systems without any configs for the architecture are left out
+struct ArchFilter<'a> {
+    distros: &'a Value,
+    arch: Value,
+    /// Configs may leave out their architecture when it's the default
+    default_arch: Value,
 }
 
-impl DistroSort for Vec<OS> {
-    fn distro_sort(mut self) -> Self {
-        self.sort_unstable_by(|a, b| a.name.cmp(&b.name));
-        self.iter_mut().for_each(|d| {
-            d.releases.sort_unstable_by(|a, b| {
-                let (release_a, release_b) = (a.release.trim_start_matches('v'), b.release.trim_start_matches('v'));
-                let (mut split_a, mut split_b) = (release_a.split('.'), release_b.split('.'));
-                while let (Some(a), Some(b)) = (split_a.next(), split_b.next()) {
-                    if let (Ok(a), Ok(b)) = (a.parse::<u64>(), b.parse::<u64>()) {
-                        let comparison = b.cmp(&a);
-                        if comparison != std::cmp::Ordering::Equal {
-                            return comparison;
-                        }
-                    } else {
-                        break;
-                    }
-                }
-                b.release.cmp(&a.release).then(a.edition.cmp(&b.edition))
-            })
-        });
-        self
+impl<'a> ArchFilter<'a> {
+    fn new(distros: &'a Value, arch: &Arch) -> Self {
+        Self {
+            distros,
+            arch: serde_json::to_value(arch).unwrap(),
+            default_arch: serde_json::to_value(Arch::default()).unwrap(),
+        }
     }
 }
 
-enum CompressionType {
-    None,
-    Gzip,
-    Zstd,
+impl Serialize for ArchFilter<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let systems = self
+            .distros
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_object)
+            .filter_map(|os| {
+                let releases = os
+                    .get("releases")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter(|config| config.get("arch").unwrap_or(&self.default_arch) == &self.arch)
+                    .collect::<Vec<_>>();
+                (!releases.is_empty()).then_some(FilteredOS { os, releases })
+            });
+        serializer.collect_seq(systems)
+    }
 }
 
-trait WriteCompressedData {
-    fn write_with_compression(&self, filename: &str, compression: CompressionType);
+struct FilteredOS<'a> {
+    os: &'a Map<String, Value>,
+    releases: Vec<&'a Value>,
 }
 
-impl WriteCompressedData for String {
-    fn write_with_compression(&self, filename: &str, compression: CompressionType) {
-        let mut file = File::create(filename).unwrap();
-        let data = self.as_bytes();
-        match compression {
-            CompressionType::None => file.write_all(data).unwrap(),
-            CompressionType::Gzip => {
-                let mut compressor = libdeflater::Compressor::new(libdeflater::CompressionLvl::best());
-                let mut output = vec![0; compressor.gzip_compress_bound(data.len())];
-                let final_size = compressor.gzip_compress(data, &mut output).unwrap();
-                output.resize(final_size, 0);
-                file.write_all(&output).unwrap();
+impl Serialize for FilteredOS<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.os.len()))?;
+        for (key, value) in self.os {
+            match key.as_str() {
+                "releases" => map.serialize_entry(key, &self.releases)?,
+                _ => map.serialize_entry(key, value)?,
             }
-            CompressionType::Zstd => zstd::stream::copy_encode(data, file, 22).unwrap(),
         }
+        map.end()
+    }
+}
+
+/// Extensions of the files written for each dataset, one per compression type
+const COMPRESSION_EXTENSIONS: [&str; 3] = ["json", "json.gz", "json.zst"];
+
+/// Writes every compression of a dataset at once. The data is serialized a single time, straight into each file,
+/// rather than being held in memory as a string and compressed buffers
+struct CompressedWriter {
+    plain: BufWriter<File>,
+    gzip: GzEncoder<BufWriter<File>>,
+    zstd: zstd::Encoder<'static, BufWriter<File>>,
+}
+
+impl CompressedWriter {
+    fn create(name: &str) -> std::io::Result<Self> {
+        let create = |extension: &str| File::create(format!("{name}.{extension}")).map(BufWriter::new);
+        Ok(Self {
+            plain: create("json")?,
+            gzip: GzEncoder::new(create("json.gz")?, Compression::best()),
+            zstd: zstd::Encoder::new(create("json.zst")?, 22)?,
+        })
+    }
+    fn finish(self) -> std::io::Result<()> {
+        let mut plain = self.plain;
+        plain.flush()?;
+        self.gzip.finish()?.flush()?;
+        self.zstd.finish()?.flush()
+    }
+}
+
+impl Write for CompressedWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.plain.write_all(buf)?;
+        self.gzip.write_all(buf)?;
+        self.zstd.write_all(buf)?;
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.plain.flush()?;
+        self.gzip.flush()?;
+        self.zstd.flush()
+    }
+}
+
+fn write_all_compressions(data: &impl Serialize, name: &str) {
+    let result = CompressedWriter::create(name).and_then(|mut writer| {
+        serde_json::to_writer(&mut writer, data)?;
+        writer.finish()
+    });
+    if let Err(e) = result {
+        tracing::error!("Failed to write {name}: {e}");
+        std::process::exit(1);
     }
 }
@@ -0,0 +1,109 @@
+use crate::report;
+use once_cell::sync::{Lazy, OnceCell};
+use std::{collections::BTreeMap, fmt::Write, sync::Mutex, time::Instant};
+
+static START: OnceCell<Instant> = OnceCell::new();
+static HOSTS: Lazy<Mutex<BTreeMap<String, HostMetrics>>> = Lazy::new(Default::default);
+
+#[derive(Default)]
+struct HostMetrics {
+    requests: u64,
+    failures: u64,
+    retries: u64,
+}
+
+/// Marks the beginning of the run, which its duration is measured from
+pub fn start() {
+    START.get_or_init(Instant::now);
+}
+
+/// Counts a single attempt at a request. Attempts which were retried by the client are counted individually
+pub fn record_response(host: &str, failed: bool, retried: bool) {
+    let mut hosts = HOSTS.lock().unwrap();
+    let metrics = hosts.entry(host.to_string()).or_default();
+    metrics.requests += 1;
+    metrics.failures += failed as u64;
+    metrics.retries += retried as u64;
+}
+
+/// Formats every metric in Prometheus' text exposition format
+fn render() -> String {
+    let mut output = String::new();
+    let mut metric = |name: &str, kind: &str, help: &str, values: Vec<(String, u64)>| {
+        let _ = writeln!(output, "# HELP {name} {help}\n# TYPE {name} {kind}");
+        for (labels, value) in values {
+            let _ = writeln!(output, "{name}{labels} {value}");
+        }
+    };
+
+    let hosts = HOSTS.lock().unwrap();
+    let per_host = |value: fn(&HostMetrics) -> u64| {
+        hosts
+            .iter()
+            .map(|(host, m)| (format!("{{host=\"{host}\"}}"), value(m)))
+            .collect::<Vec<_>>()
+    };
+    metric(
+        "quickget_http_requests_total",
+        "counter",
+        "HTTP requests made, including retries",
+        per_host(|m| m.requests),
+    );
+    metric(
+        "quickget_http_failures_total",
+        "counter",
+        "HTTP requests which errored or responded with a client or server error",
+        per_host(|m| m.failures),
+    );
+    metric(
+        "quickget_http_retries_total",
+        "counter",
+        "HTTP requests which failed transiently, and were retried unless the retry limit was reached",
+        per_host(|m| m.retries),
+    );
+
+    let (configs, dropped) = report::with_reports(|reports| {
+        let per_distro = |value: fn(&report::DistroReport) -> usize| {
+            reports
+                .iter()
+                .map(|r| (format!("{{distro=\"{}\"}}", r.name), value(r) as u64))
+                .collect::<Vec<_>>()
+        };
+        (per_distro(|r| r.configs), per_distro(|r| r.dropped.len()))
+    });
+    metric("quickget_configs_generated", "gauge", "Configs included in the output", configs);
+    metric(
+        "quickget_configs_dropped",
+        "gauge",
+        "Configs removed due to unresolvable URLs",
+        dropped,
+    );
+
+    let duration = START.get().map(|start| start.elapsed().as_secs()).unwrap_or_default();
+    metric(
+        "quickget_run_duration_seconds",
+        "gauge",
+        "Time taken to generate every config",
+        vec![(String::new(), duration)],
+    );
+    output
+}
+
+/// Writes the metrics to a textfile, and pushes them to a Pushgateway if QUICKGET_PUSHGATEWAY_URL is set
+pub async fn write_metrics(filename: &str) {
+    let metrics = render();
+    if let Err(e) = std::fs::write(filename, &metrics) {
+        tracing::error!("Failed to write {filename}: {e}");
+    }
+
+    let Ok(pushgateway) = std::env::var("QUICKGET_PUSHGATEWAY_URL") else {
+        return;
+    };
+    // The shared client would count this request towards the metrics being pushed
+    let url = format!("{}/metrics/job/quickget_ci", pushgateway.trim_end_matches('/'));
+    match reqwest::Client::new().put(&url).body(metrics).send().await {
+        Ok(response) if !response.status().is_success() => tracing::error!("Pushgateway rejected metrics: {}", response.status()),
+        Err(e) => tracing::error!("Failed to push metrics to {url}: {e}"),
+        _ => {}
+    }
+}
@@ -1,7 +1,9 @@
-use crate::store_data::{ArchiveFormat, ChecksumSeparation, Config, Distro, Source, WebSource};
-use crate::utils::capture_page;
+use crate::checksums::{single_checksum, ChecksumFormat, Whitespace};
+use crate::error::{GenerateError, OrGenerateError};
+use crate::store_data::{ArchiveFormat, ChecksumAlgorithm, Config, Disk, Distro, HardwareHints, Source, WebSource, GIB, MIB};
+use crate::utils::{arch_from_str, capture_page, list_links, list_links_matching, spawn};
 use join_futures::join_futures;
-use quickemu::config::GuestOS;
+use quickemu::config::{Arch, DiskFormat, GuestOS};
 use regex::Regex;
 use std::sync::Arc;
 
@@ -13,8 +15,8 @@ impl Distro for FreeDOS {
     const PRETTY_NAME: &'static str = "FreeDOS";
     const HOMEPAGE: Option<&'static str> = Some("https://www.freedos.org/");
     const DESCRIPTION: Option<&'static str> = Some("DOS-compatible operating system that you can use to play classic DOS games, run legacy business software, or develop embedded systems.");
-    async fn generate_configs() -> Option<Vec<Config>> {
-        let release_html = capture_page(FREEDOS_MIRROR).await?;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let release_html = capture_page(FREEDOS_MIRROR).await.or_network_error(FREEDOS_MIRROR)?;
         let release_regex = Regex::new(r#"href="(\d+\.\d+)/""#).unwrap();
         let iso_regex = Arc::new(Regex::new(r#"href="(FD\d+-?(.*?CD)\.(iso|zip))""#).unwrap());
         let checksum_regex = Arc::new(Regex::new(r#"FD\d+.sha|verify.txt"#).unwrap());
@@ -30,7 +32,7 @@ impl Distro for FreeDOS {
                 let mut checksums = match checksum_regex.find(&page) {
                     Some(cs_match) => {
                         let checksum_url = format!("{mirror}{}", cs_match.as_str());
-                        ChecksumSeparation::Whitespace.build(&checksum_url).await
+                        Whitespace.fetch(&checksum_url).await
                     }
                     None => None,
                 };
@@ -60,6 +62,312 @@ impl Distro for FreeDOS {
             }
         });
 
-        Some(join_futures!(futures, 2))
+        Ok(join_futures!(futures, 2))
+    }
+}
+
+const HAIKU_RELEASE_MIRROR: &str = "https://cdn.haiku-os.org/haiku-release/";
+const HAIKU_NIGHTLY_MIRROR: &str = "https://download.haiku-os.org/nightly-images/";
+
+pub struct Haiku;
+impl Distro for Haiku {
+    const NAME: &'static str = "haiku";
+    const PRETTY_NAME: &'static str = "Haiku";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.haiku-os.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Specifically targets personal computing. Inspired by the BeOS, Haiku is fast, simple to use, easy to learn and yet very powerful.");
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let release_html = capture_page(HAIKU_RELEASE_MIRROR)
+            .await
+            .or_network_error(HAIKU_RELEASE_MIRROR)?;
+        let release_regex = Regex::new(r#"href="(r\d+(?:beta|alpha)?\d*)/""#).unwrap();
+        let mut releases = release_regex
+            .captures_iter(&release_html)
+            .map(|c| c[1].to_string())
+            .collect::<Vec<_>>();
+        releases.sort_unstable();
+        releases.dedup();
+
+        let release_futures = releases.into_iter().rev().take(Self::release_limit()).flat_map(|release| {
+            // x86_gcc2h is a 32-bit build, retaining compatibility with BeOS applications
+            ["x86_64", "x86_gcc2h"].into_iter().map(move |edition| {
+                let release = release.clone();
+                spawn(async move {
+                    let url = format!("{HAIKU_RELEASE_MIRROR}{release}/haiku-{release}-{edition}-anyboot.iso");
+                    let checksum = single_checksum(&format!("{url}.sha256")).await;
+                    Some(Config {
+                        guest_os: GuestOS::Haiku,
+                        release,
+                        edition: Some(edition.to_string()),
+                        iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                        ..Default::default()
+                    })
+                })
+            })
+        });
+
+        let nightly_futures = [(Arch::x86_64, "x86_64"), (Arch::aarch64, "arm64"), (Arch::riscv64, "riscv64")]
+            .into_iter()
+            .map(|(arch, arch_text)| {
+                spawn(async move {
+                    let mirror = format!("{HAIKU_NIGHTLY_MIRROR}{arch_text}/");
+                    let page = capture_page(&mirror).await?;
+                    let image_regex = Regex::new(&format!(r#"href="(haiku-master-hrev(\d+)-{arch_text}-(anyboot|mmc)\.zip)""#)).unwrap();
+                    // Architectures without anyboot images publish a disk image for SD cards instead, both are built from the same revision
+                    let (image, kind) = image_regex
+                        .captures_iter(&page)
+                        .filter_map(|c| {
+                            Some((
+                                c[2].parse::<u32>().ok()?,
+                                &c[3] == "anyboot",
+                                c[1].to_string(),
+                                c[3].to_string(),
+                            ))
+                        })
+                        .max()
+                        .map(|(_, _, image, kind)| (image, kind))?;
+                    let url = format!("{mirror}{image}");
+                    let checksum = single_checksum(&format!("{url}.sha256")).await;
+                    let source = Source::Web(WebSource::new(url, checksum, Some(ArchiveFormat::Zip), None));
+                    let mut config = Config {
+                        guest_os: GuestOS::Haiku,
+                        release: "nightly".to_string(),
+                        arch,
+                        ..Default::default()
+                    };
+                    if kind == "anyboot" {
+                        config.iso = Some(vec![source]);
+                    } else {
+                        config.disk_images = Some(vec![Disk {
+                            source,
+                            format: DiskFormat::Raw,
+                            ..Default::default()
+                        }]);
+                    }
+                    Some(config)
+                })
+            });
+
+        let futures = release_futures.chain(nightly_futures);
+        Ok(join_futures!(futures, 2))
+    }
+    fn hardware_hints(_config: &Config) -> Option<HardwareHints> {
+        // The package manager and WebPositive are sluggish with less, on both the 64-bit and 32-bit builds
+        Some(HardwareHints {
+            ram: Some(2 * GIB),
+            cpu_cores: Some(2),
+            ..Default::default()
+        })
+    }
+}
+
+const REACTOS_MIRROR: &str = "https://sourceforge.net/projects/reactos/files/ReactOS/";
+
+pub struct ReactOS;
+impl Distro for ReactOS {
+    const NAME: &'static str = "reactos";
+    const PRETTY_NAME: &'static str = "ReactOS";
+    const HOMEPAGE: Option<&'static str> = Some("https://reactos.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Imagine running your favorite Windows applications and drivers in an open-source environment you can trust.");
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Md5;
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let releases = capture_page(REACTOS_MIRROR).await.or_network_error(REACTOS_MIRROR)?;
+        let release_regex = Regex::new(r#""name":"(\d+\.\d+\.\d+)""#).unwrap();
+        // SourceForge lists the MD5 of each file alongside its name
+        let iso_regex = Arc::new(Regex::new(r#""name":"(ReactOS-[^"]+?-(iso|live)\.zip)"[^}]*?"md5":"([0-9a-f]{32})""#).unwrap());
+
+        let futures = release_regex.captures_iter(&releases).take(Self::release_limit()).map(|c| {
+            let release = c[1].to_string();
+            let mirror = format!("{REACTOS_MIRROR}{release}/");
+            let iso_regex = iso_regex.clone();
+            async move {
+                let page = capture_page(&mirror).await?;
+                Some(
+                    iso_regex
+                        .captures_iter(&page)
+                        .map(|c| c.extract())
+                        .map(|(_, [iso, edition, checksum])| {
+                            let edition = if edition == "iso" { "bootcd" } else { "livecd" };
+                            Config {
+                                guest_os: GuestOS::ReactOS,
+                                release: release.clone(),
+                                edition: Some(edition.to_string()),
+                                iso: Some(vec![Source::Web(WebSource::new(
+                                    format!("{mirror}{iso}/download"),
+                                    Some(checksum.to_string()),
+                                    Some(ArchiveFormat::Zip),
+                                    Some(iso.to_string()),
+                                ))]),
+                                ..Default::default()
+                            }
+                        })
+                        .collect::<Vec<Config>>(),
+                )
+            }
+        });
+
+        Ok(join_futures!(futures, 2))
+    }
+}
+
+const NINEFRONT_MIRROR: &str = "https://9front.org/iso/";
+
+pub struct NineFront;
+impl Distro for NineFront {
+    const NAME: &'static str = "9front";
+    const PRETTY_NAME: &'static str = "9front";
+    const HOMEPAGE: Option<&'static str> = Some("https://9front.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Fork of the Plan 9 from Bell Labs operating system, adding hardware support, a new boot process and many other improvements.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(NINEFRONT_MIRROR).await.or_network_error(NINEFRONT_MIRROR)?;
+        // Releases are numbered after the revision they were built from, only the latest is kept on the mirror
+        let image_regex = Regex::new(r#"href="(9front-(\d+)\.(amd64|arm64)\.(iso|qcow2)\.gz)""#).unwrap();
+        let images = image_regex
+            .captures_iter(&page)
+            .map(|c| c.extract())
+            .filter_map(|(_, [image, release, arch, kind])| Some((release.parse::<u32>().ok()?, image, arch_from_str(arch)?, kind)))
+            .collect::<Vec<_>>();
+        let latest = images
+            .iter()
+            .map(|(release, ..)| *release)
+            .max()
+            .ok_or(GenerateError::NoReleasesFound)?;
+
+        // There is no GuestOS for Plan 9, so the default is used
+        Ok(images
+            .into_iter()
+            .filter(|(release, ..)| *release == latest)
+            .map(|(release, image, arch, kind)| {
+                let source = Source::Web(WebSource::new(
+                    format!("{NINEFRONT_MIRROR}{image}"),
+                    None,
+                    Some(ArchiveFormat::Gz),
+                    None,
+                ));
+                let mut config = Config {
+                    release: release.to_string(),
+                    arch,
+                    ..Default::default()
+                };
+                if kind == "iso" {
+                    config.iso = Some(vec![source]);
+                } else {
+                    config.disk_images = Some(vec![Disk {
+                        source,
+                        format: DiskFormat::Qcow2,
+                        ..Default::default()
+                    }]);
+                }
+                config
+            })
+            .collect::<Vec<Config>>())
+    }
+}
+
+const MENUETOS_DOWNLOADS: &str = "https://www.menuetos.net/download.htm";
+const MENUETOS_MIRROR: &str = "https://www.menuetos.net/";
+
+pub struct MenuetOS;
+impl Distro for MenuetOS {
+    const NAME: &'static str = "menuetos";
+    const PRETTY_NAME: &'static str = "MenuetOS";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.menuetos.net/");
+    const DESCRIPTION: Option<&'static str> = Some("Operating system written entirely in assembly language, fitting a graphical desktop, networking and applications on a single floppy disk.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let page = capture_page(MENUETOS_DOWNLOADS).await.or_network_error(MENUETOS_DOWNLOADS)?;
+        // Only the 64-bit release is maintained. It's published as a zipped floppy image, with no checksums
+        let image_regex = Regex::new(r#"href="((?:https?://[^"]+/)?M64-?(\d+(?:\.\d+)+)\.zip)""#).unwrap();
+        let (_, release, image) = image_regex
+            .captures_iter(&page)
+            .map(|c| {
+                let version = c[2].split('.').filter_map(|n| n.parse::<u32>().ok()).collect::<Vec<u32>>();
+                (version, c[2].to_string(), c[1].to_string())
+            })
+            .max()
+            .ok_or(GenerateError::NoReleasesFound)?;
+        let url = if image.starts_with("http") { image } else { format!("{MENUETOS_MIRROR}{image}") };
+
+        // There is no GuestOS for MenuetOS, so the default is used
+        Ok(vec![Config {
+            release,
+            floppy: Some(vec![Source::Web(WebSource::new(url, None, Some(ArchiveFormat::Zip), None))]),
+            ..Default::default()
+        }])
+    }
+    fn hardware_hints(_config: &Config) -> Option<HardwareHints> {
+        // The whole system is loaded from the floppy into a ramdisk, so it needs far less than quickemu's default
+        Some(HardwareHints {
+            ram: Some(256 * MIB),
+            ..Default::default()
+        })
+    }
+}
+
+const REDOX_MIRROR: &str = "https://static.redox-os.org/releases/";
+
+pub struct RedoxOS;
+impl Distro for RedoxOS {
+    const NAME: &'static str = "redox";
+    const PRETTY_NAME: &'static str = "Redox OS";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.redox-os.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Unix-like operating system written in Rust, built around a microkernel with drivers and filesystems running in userspace.");
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let release_regex = Regex::new(r"^(\d+\.\d+\.\d+)/$").unwrap();
+        let mut releases = list_links_matching(REDOX_MIRROR, &release_regex)
+            .await
+            .or_network_error(REDOX_MIRROR)?
+            .into_iter()
+            .map(|link| link.trim_end_matches('/').to_string())
+            .collect::<Vec<String>>();
+        releases.sort_unstable_by_key(|r| r.split('.').filter_map(|n| n.parse::<u32>().ok()).collect::<Vec<u32>>());
+        // Each image is also published compressed with zstd, which quickget is unable to extract
+        let image_regex = Arc::new(Regex::new(r"^redox_([a-z]+)_(?:x86_64|aarch64)_\d{4}-\d{2}-\d{2}_\d+_(harddrive\.img|livedisk\.iso)$").unwrap());
+
+        let targets = releases
+            .into_iter()
+            .rev()
+            .take(Self::release_limit())
+            .flat_map(|release| [Arch::x86_64, Arch::aarch64].map(|arch| (release.clone(), arch)))
+            .collect::<Vec<_>>();
+        let futures = targets.into_iter().map(|(release, arch)| {
+            let image_regex = image_regex.clone();
+            async move {
+                let mirror = format!("{REDOX_MIRROR}{release}/{arch}/");
+                let links = list_links(&mirror).await?;
+                let mut checksums = Whitespace.fetch(&format!("{mirror}SHA256SUM")).await;
+                Some(
+                    links
+                        .iter()
+                        .filter_map(|link| image_regex.captures(link))
+                        .map(|c| {
+                            let checksum = checksums.as_mut().and_then(|cs| cs.remove(&c[0]));
+                            let source = Source::Web(WebSource::new(format!("{mirror}{}", &c[0]), checksum, None, None));
+                            // There is no GuestOS for Redox, so the default is used
+                            let mut config = Config {
+                                release: release.clone(),
+                                arch: arch.clone(),
+                                ..Default::default()
+                            };
+                            if &c[2] == "livedisk.iso" {
+                                config.edition = Some(c[1].to_string());
+                                config.iso = Some(vec![source]);
+                            } else {
+                                config.edition = Some(format!("{}-harddrive", &c[1]));
+                                config.disk_images = Some(vec![Disk {
+                                    source,
+                                    format: DiskFormat::Raw,
+                                    ..Default::default()
+                                }]);
+                            }
+                            config
+                        })
+                        .collect::<Vec<Config>>(),
+                )
+            }
+        });
+
+        Ok(join_futures!(futures, 2))
     }
 }
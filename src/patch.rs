@@ -0,0 +1,156 @@
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+
+/// Writes an RFC 6902 JSON Patch updating the published dataset to the fresh output, in every compression,
+/// along with a manifest mapping the hash of the dataset it applies to onto the patch and the hash of the result.
+/// Clients holding the published dataset can then update by downloading only the patch
+pub fn write_patch(published: &str, output: &Value, name: &str, manifest: &str) {
+    let Ok(base) = serde_json::from_str::<Value>(published) else {
+        tracing::warn!("Unable to parse the published dataset, no patch will be written");
+        return;
+    };
+    // Hashed from the written file rather than reserialized, so clients can compare it to the bytes they hold
+    let target = match std::fs::read(format!("{name}.json")) {
+        Ok(data) => format!("{:x}", Sha256::digest(data)),
+        Err(e) => {
+            tracing::error!("Unable to read {name}.json to hash it, no patch will be written: {e}");
+            return;
+        }
+    };
+
+    let mut operations = Vec::new();
+    diff("", &base, output, &mut operations);
+    let patch_name = format!("{name}.patch");
+    crate::write_all_compressions(&operations, &patch_name);
+
+    let manifest_data = json!({
+        "target": target,
+        "patches": {
+            format!("{:x}", Sha256::digest(published.as_bytes())): {
+                "patch": patch_name,
+                "operations": operations.len(),
+            },
+        },
+    });
+    let result = serde_json::to_string_pretty(&manifest_data)
+        .map_err(std::io::Error::from)
+        .and_then(|data| std::fs::write(manifest, data));
+    if let Err(e) = result {
+        tracing::error!("Failed to write {manifest}: {e}");
+    }
+}
+
+/// Appends the operations turning `old` into `new` at the given JSON pointer
+fn diff(path: &str, old: &Value, new: &Value, operations: &mut Vec<Value>) {
+    match (old, new) {
+        _ if old == new => {}
+        (Value::Object(old), Value::Object(new)) => diff_objects(path, old, new, operations),
+        (Value::Array(old), Value::Array(new)) => diff_arrays(path, old, new, operations),
+        _ => operations.push(json!({ "op": "replace", "path": path, "value": new })),
+    }
+}
+
+fn diff_objects(path: &str, old: &Map<String, Value>, new: &Map<String, Value>, operations: &mut Vec<Value>) {
+    for (key, old_value) in old {
+        let child = format!("{path}/{}", escape(key));
+        match new.get(key) {
+            Some(new_value) => diff(&child, old_value, new_value, operations),
+            None => operations.push(json!({ "op": "remove", "path": child })),
+        }
+    }
+    for (key, new_value) in new.iter().filter(|(key, _)| !old.contains_key(*key)) {
+        operations.push(json!({ "op": "add", "path": format!("{path}/{}", escape(key)), "value": new_value }));
+    }
+}
+
+/// Distros and releases are sorted, so an addition or removal usually only shifts the entries after it.
+/// Entries matching at either end are skipped, so the shift doesn't turn into a replacement of everything following
+fn diff_arrays(path: &str, old: &[Value], new: &[Value], operations: &mut Vec<Value>) {
+    let prefix = old.iter().zip(new).take_while(|(old, new)| old == new).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+    let old = &old[prefix..old.len() - suffix];
+    let new = &new[prefix..new.len() - suffix];
+
+    let common = old.len().min(new.len());
+    for (index, (old_value, new_value)) in old.iter().zip(new).enumerate() {
+        diff(&format!("{path}/{}", prefix + index), old_value, new_value, operations);
+    }
+    // Each removal shifts the following entries down, so the same index is removed repeatedly
+    for _ in common..old.len() {
+        operations.push(json!({ "op": "remove", "path": format!("{path}/{}", prefix + common) }));
+    }
+    for (index, new_value) in new.iter().enumerate().skip(common) {
+        operations.push(json!({ "op": "add", "path": format!("{path}/{}", prefix + index), "value": new_value }));
+    }
+}
+
+/// Escapes a key for use within a JSON pointer, as described in RFC 6901
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Applies the generated operations to the base, which must then equal the output
+    fn assert_patches(base: Value, output: Value) {
+        let mut operations = Vec::new();
+        diff("", &base, &output, &mut operations);
+        let patch: json_patch::Patch = serde_json::from_value(Value::Array(operations)).unwrap();
+        let mut patched = base;
+        json_patch::patch(&mut patched, &patch).unwrap();
+        assert_eq!(patched, output);
+    }
+
+    #[test]
+    fn patches_objects() {
+        assert_patches(
+            json!({ "name": "debian", "homepage": "https://www.debian.org", "removed": true }),
+            json!({ "name": "debian", "homepage": "https://debian.org", "description": "Universal operating system" }),
+        );
+    }
+
+    #[test]
+    fn patches_shifted_arrays() {
+        assert_patches(json!([1, 2, 3, 4, 5]), json!([1, 2, 6, 4, 5]));
+        assert_patches(json!([1, 2, 3, 4, 5]), json!([1, 5]));
+        assert_patches(json!([1, 5]), json!([1, 2, 3, 4, 5]));
+        assert_patches(json!([1, 2, 3]), json!([]));
+        assert_patches(json!([]), json!([1, 2, 3]));
+    }
+
+    #[test]
+    fn patches_escaped_keys() {
+        assert_patches(
+            json!({ "a/b": 1, "c~d": [1] }),
+            json!({ "a/b": 2, "c~d": [1, 2], "e/~f": null }),
+        );
+    }
+
+    #[test]
+    fn patches_nested_releases() {
+        assert_patches(
+            json!([
+                { "name": "alpine", "releases": [{ "release": "3.19", "arch": "x86_64" }, { "release": "3.20", "arch": "x86_64" }] },
+                { "name": "debian", "releases": [{ "release": "12", "edition": "standard" }] },
+            ]),
+            json!([
+                { "name": "alpine", "releases": [{ "release": "3.20", "arch": "x86_64" }, { "release": "3.21", "arch": "aarch64" }] },
+                { "name": "arch", "releases": [{ "release": "latest" }] },
+                { "name": "debian", "releases": [{ "release": "12", "edition": "standard", "checksum": "abc" }] },
+            ]),
+        );
+    }
+
+    #[test]
+    fn replaces_mismatched_types() {
+        assert_patches(json!({ "value": [1] }), json!({ "value": { "a": 1 } }));
+        assert_patches(json!(1), json!("1"));
+    }
+}
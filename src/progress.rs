@@ -0,0 +1,106 @@
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use once_cell::sync::{Lazy, OnceCell};
+use std::{
+    collections::HashMap,
+    io::{IsTerminal, Write},
+    sync::Mutex,
+    time::Duration,
+};
+
+static PROGRESS: OnceCell<MultiProgress> = OnceCell::new();
+/// Counts the distros which have finished, out of those started
+static OVERALL: OnceCell<ProgressBar> = OnceCell::new();
+/// Spinners of the distros currently being generated, keyed by distro name
+static BARS: Lazy<Mutex<HashMap<&'static str, ProgressBar>>> = Lazy::new(Default::default);
+
+/// Draws the progress of each distro on stderr. Does nothing unless stderr is a terminal, so CI logs are unaffected.
+/// Must be called before any configs are generated
+pub fn enable() {
+    if !std::io::stderr().is_terminal() {
+        return;
+    }
+    let progress = MultiProgress::new();
+    let overall = progress.add(ProgressBar::new(0));
+    if let Ok(style) = ProgressStyle::with_template("{prefix:.bold} [{elapsed_precise}] {pos}/{len} distros finished") {
+        overall.set_style(style);
+    }
+    overall.set_prefix("quickget");
+    if PROGRESS.set(progress).is_err() || OVERALL.set(overall).is_err() {
+        tracing::warn!("Progress display was already enabled");
+    }
+}
+
+fn bar(name: &str) -> Option<ProgressBar> {
+    BARS.lock().unwrap().get(name).cloned()
+}
+
+/// Adds a spinner for a distro whose releases are being fetched
+pub(crate) fn start(name: &'static str) {
+    let (Some(progress), Some(overall)) = (PROGRESS.get(), OVERALL.get()) else {
+        return;
+    };
+    overall.inc_length(1);
+    let bar = progress.add(ProgressBar::new_spinner());
+    if let Ok(style) = ProgressStyle::with_template("{spinner} {prefix:.bold} {elapsed:>4} {msg}") {
+        bar.set_style(style);
+    }
+    bar.set_prefix(name);
+    bar.set_message("fetching");
+    bar.enable_steady_tick(Duration::from_millis(120));
+    BARS.lock().unwrap().insert(name, bar);
+}
+
+/// Marks a distro whose configs have been generated, waiting for every other distro before its URLs are validated
+pub(crate) fn waiting(name: &str) {
+    if let Some(bar) = bar(name) {
+        bar.set_message("waiting for validation");
+    }
+}
+
+/// Switches a distro to validating its configs' URLs
+pub(crate) fn validating(name: &str, configs: usize) {
+    let Some(bar) = bar(name) else {
+        return;
+    };
+    if let Ok(style) = ProgressStyle::with_template("{spinner} {prefix:.bold} {elapsed:>4} {msg} {pos}/{len}") {
+        bar.set_style(style);
+    }
+    bar.set_length(configs as u64);
+    bar.set_message("validating");
+}
+
+/// Counts a config whose URLs have been checked
+pub(crate) fn validated(name: &str) {
+    if let Some(bar) = bar(name) {
+        bar.inc(1);
+    }
+}
+
+/// Removes a distro's spinner once it succeeds. Failures are left on screen, so they're visible until the run ends
+pub(crate) fn finish(name: &str, failure: Option<&str>) {
+    let Some(bar) = BARS.lock().unwrap().remove(name) else {
+        return;
+    };
+    match failure {
+        Some(reason) => bar.finish_with_message(format!("failed: {reason}")),
+        None => bar.finish_and_clear(),
+    }
+    if let Some(overall) = OVERALL.get() {
+        overall.inc(1);
+    }
+}
+
+/// Writes log lines to stdout, hiding the progress display while doing so to prevent the two from being interleaved
+pub struct LogWriter;
+
+impl Write for LogWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match PROGRESS.get() {
+            Some(progress) => progress.suspend(|| std::io::stdout().write(buf)),
+            None => std::io::stdout().write(buf),
+        }
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        std::io::stdout().flush()
+    }
+}
@@ -0,0 +1,141 @@
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::{collections::HashMap, fmt::Debug, fs::File, sync::Mutex};
+use tracing::{
+    field::{Field, Visit},
+    span::Attributes,
+    Event, Id, Level, Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+static REPORTS: Lazy<Mutex<Vec<DistroReport>>> = Lazy::new(Default::default);
+/// Warnings and errors logged while generating each distro, keyed by its name
+static WARNINGS: Lazy<Mutex<HashMap<String, Vec<String>>>> = Lazy::new(Default::default);
+
+/// The outcome of generating a single distro's configs, written to generation_report.json
+#[derive(Serialize)]
+pub struct DistroReport {
+    pub name: &'static str,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Category of the error, such as "network" or "parse", so failures can be grouped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<&'static str>,
+    /// Whether the configs were reused from the cache, as the pages they were scraped from haven't changed. Their URLs are still validated
+    pub cached: bool,
+    pub configs: usize,
+    pub dropped: Vec<DroppedConfig>,
+    /// Configs which were kept despite unresolvable URLs, as their checksums are known
+    pub unverified: Vec<DroppedConfig>,
+    /// Checksums which were removed as they couldn't be a valid digest
+    pub invalid_checksums: Vec<String>,
+    /// Warnings and errors logged by the distro's scraper
+    pub warnings: Vec<String>,
+    /// The distro's homepage has been unreachable for several consecutive runs
+    pub possibly_discontinued: bool,
+}
+
+/// A config with unresolvable URLs, and the reasons they failed
+#[derive(Serialize)]
+pub struct DroppedConfig {
+    pub release: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edition: Option<String>,
+    pub arch: String,
+    pub reasons: Vec<String>,
+}
+
+impl DistroReport {
+    pub fn failure(name: &'static str, kind: &'static str, error: impl Into<String>) -> Self {
+        Self {
+            name,
+            success: false,
+            error: Some(error.into()),
+            error_kind: Some(kind),
+            cached: false,
+            configs: 0,
+            dropped: Vec::new(),
+            unverified: Vec::new(),
+            invalid_checksums: Vec::new(),
+            warnings: Vec::new(),
+            possibly_discontinued: false,
+        }
+    }
+    pub fn record(mut self) {
+        self.warnings = WARNINGS.lock().unwrap().remove(self.name).unwrap_or_default();
+        self.possibly_discontinued = crate::liveness::possibly_discontinued(self.name);
+        REPORTS.lock().unwrap().push(self);
+    }
+}
+
+/// Provides every report recorded so far
+pub fn with_reports<T>(f: impl FnOnce(&[DistroReport]) -> T) -> T {
+    f(&REPORTS.lock().unwrap())
+}
+
+pub fn write_report(filename: &str) {
+    let mut reports = REPORTS.lock().unwrap();
+    reports.sort_unstable_by_key(|r| r.name);
+    let result = File::create(filename)
+        .map_err(|e| e.to_string())
+        .and_then(|file| serde_json::to_writer_pretty(file, &*reports).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        tracing::error!("Failed to write {filename}: {e}");
+    }
+}
+
+/// Collects warnings and errors logged within a distro's span, so they're included in its report
+pub struct ReportLayer;
+
+/// Name of the distro a span was created for
+struct DistroName(String);
+
+#[derive(Default)]
+struct FieldVisitor {
+    distro: Option<String>,
+    message: Option<String>,
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "distro" => self.distro = Some(value.to_string()),
+            "message" => self.message = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.message = Some(format!("{value:?}"));
+        }
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for ReportLayer {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+        if let (Some(distro), Some(span)) = (visitor.distro, ctx.span(id)) {
+            span.extensions_mut().insert(DistroName(distro));
+        }
+    }
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        if *event.metadata().level() > Level::WARN {
+            return;
+        }
+        let Some(distro) = ctx
+            .event_scope(event)
+            .into_iter()
+            .flatten()
+            .find_map(|span| span.extensions().get::<DistroName>().map(|d| d.0.clone()))
+        else {
+            return;
+        };
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        if let Some(message) = visitor.message {
+            WARNINGS.lock().unwrap().entry(distro).or_default().push(message);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use crate::store_data::{ConfigMetadata, SourceMetadata};
+use schemars::{schema::RootSchema, schema_for, JsonSchema};
+use std::{fs::File, path::Path};
+
+/// JSON Schema of quickget_data.json, an array of every OS, allowing consumers to validate it and generate typed bindings.
+/// quickget_core's types have no schema of their own, so they're mirrored here, alongside the metadata merged into them
+pub fn data_schema() -> RootSchema {
+    schema_for!(Vec<OS>)
+}
+
+/// JSON Schema of quickget_data_versioned.json and the per-architecture files, which wrap the same data with its format version
+pub fn versioned_schema() -> RootSchema {
+    schema_for!(Dataset)
+}
+
+/// Writes each schema to the directory, named after the file it describes
+pub fn write_schemas(directory: &Path) {
+    for (name, schema) in [("quickget_data", data_schema()), ("quickget_data_versioned", versioned_schema())] {
+        let path = directory.join(format!("{name}.schema.json"));
+        let result = File::create(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|file| serde_json::to_writer_pretty(file, &schema).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            tracing::error!("Failed to write {}: {e}", path.display());
+        }
+    }
+}
+
+// The types below mirror the published output, and are only used to describe it
+
+/// The published dataset, wrapped so consumers can detect data they're unable to parse
+#[derive(JsonSchema)]
+pub struct Dataset {
+    /// Incremented whenever a change would break existing consumers
+    pub schema_version: u32,
+    /// UNIX timestamp of the run which generated the data
+    pub generated_at: u64,
+    pub distros: Vec<OS>,
+}
+
+#[derive(JsonSchema)]
+pub struct OS {
+    /// Name used to select the OS, such as `ubuntu`
+    pub os: String,
+    pub pretty_name: String,
+    pub homepage: Option<String>,
+    pub description: Option<String>,
+    /// The homepage has been unreachable for several consecutive runs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub possibly_discontinued: Option<bool>,
+    pub releases: Vec<Config>,
+}
+
+#[derive(JsonSchema)]
+pub struct Config {
+    pub release: Option<String>,
+    pub edition: Option<String>,
+    /// Omitted for Linux
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guest_os: Option<String>,
+    /// Omitted for x86_64
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arch: Option<Arch>,
+    pub iso: Option<Vec<Source>>,
+    pub img: Option<Vec<Source>>,
+    pub fixed_iso: Option<Vec<Source>>,
+    pub floppy: Option<Vec<Source>>,
+    pub disk_images: Option<Vec<Disk>>,
+    pub boot_type: Option<BootType>,
+    pub tpm: Option<bool>,
+    /// In bytes
+    pub ram: Option<u64>,
+    #[serde(flatten)]
+    pub metadata: ConfigMetadata,
+}
+
+#[derive(JsonSchema)]
+#[allow(non_camel_case_types)]
+pub enum Arch {
+    x86_64,
+    aarch64,
+    riscv64,
+}
+
+#[derive(JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BootType {
+    Legacy,
+    Efi { secure_boot: bool },
+}
+
+#[derive(JsonSchema)]
+pub struct Disk {
+    pub source: Source,
+    /// In bytes
+    pub size: Option<u64>,
+    /// Disk image format, such as `qcow2` or `raw`
+    pub format: Option<String>,
+}
+
+#[derive(JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+    Web(Box<WebSource>),
+    FileName(String),
+    Custom,
+}
+
+#[derive(JsonSchema)]
+pub struct WebSource {
+    pub url: String,
+    pub checksum: Option<String>,
+    /// Archive or compression the file must be extracted from, such as `xz` or `zip`
+    pub archive_format: Option<String>,
+    pub file_name: Option<String>,
+    #[serde(flatten)]
+    pub metadata: SourceMetadata,
+}
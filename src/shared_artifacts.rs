@@ -0,0 +1,117 @@
+use clap::ValueEnum;
+use quickget_ci::store_data::{attach_config_metadata, Config, Source, OS};
+use reqwest::Url;
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum SharedArtifactPolicy {
+    /// Only log artifacts which appear under more than one OS
+    Warn,
+    /// Also list the operating systems sharing an artifact in the `shared_by` field of each config containing it
+    Flag,
+    /// Keep shared artifacts only under the OS whose homepage is on the same site as the artifact.
+    /// Artifacts without such an OS are flagged instead
+    Collapse,
+}
+
+/// Finds artifacts which appear under more than one OS, either by the same URL or by the same checksum, such as when a derivative's
+/// scraper picks up its parent's images through a rebranded mirror. Auxiliary media is skipped, as it's shared deliberately
+pub fn apply(distros: &mut Vec<OS>, policy: SharedArtifactPolicy) {
+    let mut owners: HashMap<String, BTreeSet<String>> = HashMap::new();
+    for os in distros.iter() {
+        for key in os.releases.iter().flat_map(artifact_keys) {
+            owners.entry(key).or_default().insert(os.name.clone());
+        }
+    }
+    owners.retain(|_, systems| systems.len() > 1);
+    if owners.is_empty() {
+        return;
+    }
+    let mut shared = owners.iter().collect::<Vec<_>>();
+    shared.sort_unstable();
+    for (key, systems) in shared {
+        let systems = systems.iter().map(String::as_str).collect::<Vec<&str>>();
+        tracing::warn!("{key} appears under several operating systems: {}", systems.join(", "));
+    }
+    if matches!(policy, SharedArtifactPolicy::Warn) {
+        return;
+    }
+
+    let sites = distros
+        .iter()
+        .filter_map(|os| Some((os.name.clone(), site(os.homepage.as_deref()?)?)))
+        .collect::<HashMap<String, String>>();
+    for os in distros.iter_mut() {
+        let before = os.releases.len();
+        os.releases.retain_mut(|config| {
+            let shared_by = artifact_keys(config)
+                .iter()
+                .filter_map(|key| owners.get(key))
+                .flatten()
+                .cloned()
+                .collect::<BTreeSet<String>>();
+            if shared_by.is_empty() {
+                return true;
+            }
+            if matches!(policy, SharedArtifactPolicy::Collapse) {
+                let config_sites = artifact_urls(config).filter_map(site).collect::<BTreeSet<String>>();
+                let owner = shared_by
+                    .iter()
+                    .filter(|name| sites.get(*name).is_some_and(|site| config_sites.contains(site)))
+                    .collect::<Vec<_>>();
+                if let [owner] = owner[..] {
+                    return *owner == os.name;
+                }
+            }
+            attach_config_metadata(config, |m| m.shared_by = shared_by.into_iter().collect());
+            true
+        });
+        let removed = before - os.releases.len();
+        if removed > 0 {
+            tracing::info!("Removed {removed} releases of {} which belong to another OS", os.name);
+        }
+    }
+    distros.retain(|os| {
+        let empty = os.releases.is_empty();
+        if empty {
+            tracing::warn!("Removing {}, every release belongs to another OS", os.name);
+        }
+        !empty
+    });
+}
+
+/// URLs of the images a config installs from, leaving out auxiliary media
+fn artifact_urls(config: &Config) -> impl Iterator<Item = &str> {
+    web_sources(config).map(|(url, _)| url)
+}
+
+fn artifact_keys(config: &Config) -> Vec<String> {
+    web_sources(config)
+        .flat_map(|(url, checksum)| {
+            let checksum = checksum
+                .filter(|c| !c.is_empty())
+                .map(|c| format!("Checksum {}", c.to_lowercase()));
+            [Some(format!("URL {url}")), checksum]
+        })
+        .flatten()
+        .collect()
+}
+
+fn web_sources(config: &Config) -> impl Iterator<Item = (&str, Option<&str>)> {
+    [&config.iso, &config.img, &config.floppy]
+        .into_iter()
+        .flatten()
+        .flatten()
+        .chain(config.disk_images.iter().flatten().map(|d| &d.source))
+        .filter_map(|source| match source {
+            Source::Web(w) => Some((w.url.as_str(), w.checksum.as_deref())),
+            _ => None,
+        })
+}
+
+/// The last two labels of a URL's host, so an OS's homepage matches the download servers under its domain
+fn site(url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    let labels = url.host_str()?.rsplit('.').take(2).collect::<Vec<&str>>();
+    Some(labels.into_iter().rev().collect::<Vec<&str>>().join("."))
+}
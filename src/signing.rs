@@ -0,0 +1,130 @@
+use clap::ValueEnum;
+use serde_json::json;
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+/// Namespace of SSH signatures, which verifiers must pass to `ssh-keygen -Y verify -n`
+const SSH_NAMESPACE: &str = "quickget";
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum SignatureFormat {
+    /// `ssh-keygen -Y sign`, producing `.sig` files
+    Ssh,
+    /// `minisign -S`, producing `.minisig` files. The key must not be password protected
+    Minisign,
+}
+
+impl SignatureFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Ssh => "sig",
+            Self::Minisign => "minisig",
+        }
+    }
+    fn sign_command(&self, key: &Path, file: &str) -> Command {
+        let mut command = match self {
+            Self::Ssh => {
+                let mut command = Command::new("ssh-keygen");
+                command.args(["-Y", "sign", "-n", SSH_NAMESPACE, "-f"]).arg(key).arg(file);
+                command
+            }
+            Self::Minisign => {
+                let mut command = Command::new("minisign");
+                command.args(["-S", "-s"]).arg(key).args(["-m", file]);
+                command
+            }
+        };
+        // Signing must never wait for a passphrase
+        command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::piped());
+        command
+    }
+    /// Identifies the key which verifiers should trust
+    fn fingerprint(&self, key: &Path) -> Option<String> {
+        match self {
+            Self::Ssh => {
+                let output = Command::new("ssh-keygen")
+                    .arg("-lf")
+                    .arg(key)
+                    .stdin(Stdio::null())
+                    .output()
+                    .ok()?;
+                // Formatted as `256 SHA256:... comment (ED25519)`
+                let output = String::from_utf8(output.stdout).ok()?;
+                output.split_whitespace().nth(1).map(ToString::to_string)
+            }
+            Self::Minisign => {
+                // The secret key is encoded, but its public key is kept beside it, such as minisign.key and minisign.pub
+                let public_key = std::fs::read_to_string(key.with_extension("pub")).ok()?;
+                public_key.lines().nth(1).map(|l| l.trim().to_string())
+            }
+        }
+    }
+}
+
+/// Produces detached signatures of the given files, signing them all in parallel, then writes a manifest listing them alongside the key's fingerprint.
+/// Runs without a key skip signing entirely
+pub fn sign_outputs(key: Option<&PathBuf>, format: SignatureFormat, files: &[String], manifest: &str) {
+    let Some(key) = key else {
+        tracing::debug!("No signing key was provided, output files won't be signed");
+        return;
+    };
+    if !key.exists() {
+        tracing::warn!("Signing key {} doesn't exist, output files won't be signed", key.display());
+        return;
+    }
+    let Some(fingerprint) = format.fingerprint(key) else {
+        tracing::warn!(
+            "Unable to determine the fingerprint of {}, output files won't be signed",
+            key.display()
+        );
+        return;
+    };
+
+    let children = files
+        .iter()
+        .filter_map(|file| match format.sign_command(key, file).spawn() {
+            Ok(child) => Some((file, child)),
+            Err(e) => {
+                tracing::warn!("Unable to sign {file}: {e}");
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    let signatures = children
+        .into_iter()
+        .filter_map(|(file, child)| match child.wait_with_output() {
+            Ok(output) if output.status.success() => Some((file.clone(), format!("{file}.{}", format.extension()))),
+            Ok(output) => {
+                tracing::warn!("Unable to sign {file}: {}", String::from_utf8_lossy(&output.stderr).trim());
+                None
+            }
+            Err(e) => {
+                tracing::warn!("Unable to sign {file}: {e}");
+                None
+            }
+        })
+        .collect::<BTreeMap<String, String>>();
+
+    let format_name = match format {
+        SignatureFormat::Ssh => "ssh",
+        SignatureFormat::Minisign => "minisign",
+    };
+    let mut manifest_data = json!({
+        "format": format_name,
+        "fingerprint": fingerprint,
+        "signatures": signatures,
+    });
+    if let SignatureFormat::Ssh = format {
+        manifest_data["namespace"] = json!(SSH_NAMESPACE);
+    }
+    let result = File::create(manifest)
+        .map_err(|e| e.to_string())
+        .and_then(|file| serde_json::to_writer_pretty(file, &manifest_data).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        tracing::error!("Failed to write {manifest}: {e}");
+    }
+}
@@ -0,0 +1,132 @@
+use crate::checksums::single_checksum;
+use crate::error::{GenerateError, OrGenerateError};
+use crate::store_data::{Config, Distro, Source, WebSource};
+use crate::utils::capture_page;
+use join_futures::join_futures;
+use quickemu::config::GuestOS;
+use regex::Regex;
+
+const OPENINDIANA_MIRROR: &str = "https://dlc.openindiana.org/isos/hipster/";
+
+pub struct OpenIndiana;
+impl Distro for OpenIndiana {
+    const NAME: &'static str = "openindiana";
+    const PRETTY_NAME: &'static str = "OpenIndiana";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.openindiana.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Community supported illumos-based operating system.");
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let release_html = capture_page(OPENINDIANA_MIRROR).await.or_network_error(OPENINDIANA_MIRROR)?;
+        let release_regex = Regex::new(r#"href="(\d{8})/""#).unwrap();
+
+        let mut releases = release_regex
+            .captures_iter(&release_html)
+            .map(|c| c[1].to_string())
+            .collect::<Vec<_>>();
+        releases.sort_unstable();
+        releases.dedup();
+
+        // Hipster is a rolling release, snapshots are published a couple of times a year
+        let futures = releases.into_iter().rev().take(Self::release_limit()).flat_map(|release| {
+            ["gui", "text", "minimal"].into_iter().map(move |edition| {
+                let release = release.clone();
+                async move {
+                    let url = format!("{OPENINDIANA_MIRROR}{release}/OI-hipster-{edition}-{release}.iso");
+                    let checksum = single_checksum(&format!("{url}.sha256sum")).await;
+                    Config {
+                        guest_os: GuestOS::Solaris,
+                        release,
+                        edition: Some(edition.to_string()),
+                        iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                        ..Default::default()
+                    }
+                }
+            })
+        });
+
+        Ok(join_futures!(futures))
+    }
+}
+
+const OMNIOS_MIRROR: &str = "https://downloads.omnios.org/media/";
+
+pub struct OmniOS;
+impl Distro for OmniOS {
+    const NAME: &'static str = "omnios";
+    const PRETTY_NAME: &'static str = "OmniOS";
+    const HOMEPAGE: Option<&'static str> = Some("https://omnios.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Stable and secure illumos-based operating system, built for servers.");
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let release_html = capture_page(OMNIOS_MIRROR).await.or_network_error(OMNIOS_MIRROR)?;
+        let release_regex = Regex::new(r#"href="r(\d+)/""#).unwrap();
+
+        let mut releases = release_regex
+            .captures_iter(&release_html)
+            .filter_map(|c| c[1].parse::<u32>().ok())
+            .collect::<Vec<_>>();
+        releases.sort_unstable();
+        releases.dedup();
+
+        let futures = releases
+            .into_iter()
+            .rev()
+            .take(Self::release_limit())
+            .map(|release| async move {
+                let release = format!("r{release}");
+                let url = format!("{OMNIOS_MIRROR}{release}/omnios-{release}.iso");
+                let checksum = single_checksum(&format!("{url}.sha256")).await;
+                Config {
+                    guest_os: GuestOS::Solaris,
+                    release,
+                    iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                    ..Default::default()
+                }
+            });
+
+        Ok(join_futures!(futures))
+    }
+}
+
+const TRIBBLIX_MIRROR: &str = "https://pkgs.tribblix.org/iso/";
+
+pub struct Tribblix;
+impl Distro for Tribblix {
+    const NAME: &'static str = "tribblix";
+    const PRETTY_NAME: &'static str = "Tribblix";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.tribblix.org/");
+    const DESCRIPTION: Option<&'static str> = Some("Retro style illumos distribution, combining the best of the past with modern tools and packages.");
+    const RELEASE_LIMIT: usize = 2;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        let iso_html = capture_page(TRIBBLIX_MIRROR).await.or_network_error(TRIBBLIX_MIRROR)?;
+        // Milestones are named 0mN, with occasional updates named 0mN.M
+        let iso_regex = Regex::new(r#"href="tribblix-(0m(\d+)(?:\.(\d+))?)\.iso""#).unwrap();
+
+        let mut releases = iso_regex
+            .captures_iter(&iso_html)
+            .map(|c| {
+                let milestone = c[2].parse::<u32>().unwrap_or_default();
+                let update = c.get(3).and_then(|u| u.as_str().parse::<u32>().ok()).unwrap_or_default();
+                ((milestone, update), c[1].to_string())
+            })
+            .collect::<Vec<_>>();
+        releases.sort_unstable();
+        releases.dedup();
+
+        let futures = releases
+            .into_iter()
+            .rev()
+            .take(Self::release_limit())
+            .map(|(_, release)| async move {
+                let url = format!("{TRIBBLIX_MIRROR}tribblix-{release}.iso");
+                let checksum = single_checksum(&format!("{url}.sha256")).await;
+                Config {
+                    guest_os: GuestOS::Solaris,
+                    release,
+                    iso: Some(vec![Source::Web(WebSource::new(url, checksum, None, None))]),
+                    ..Default::default()
+                }
+            });
+
+        Ok(join_futures!(futures))
+    }
+}
@@ -1,74 +1,706 @@
-use crate::utils::all_valid;
+use crate::{
+    checksums::{single_checksum, ChecksumFormat},
+    error::GenerateError,
+    report::{DistroReport, DroppedConfig},
+    utils::{language_tag, list_links_matching, url_failures},
+    verification::{lists_checksum, verify_checksum_file, Outcome},
+};
 use join_futures::join_futures;
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
 pub use quickemu::config::Arch;
+use quickemu::config::DiskFormat;
 pub use quickget_core::data_structures::{ArchiveFormat, Config, Disk, Source, WebSource, OS};
 use regex::Regex;
-use std::{collections::HashMap, sync::Arc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Mutex,
+    time::Duration,
+};
+use tokio::task::JoinHandle;
 
+// Config generation is always spawned onto tokio's multi-threaded runtime, which already requires the futures be Send
+#[allow(async_fn_in_trait)]
 pub trait Distro {
     const NAME: &'static str;
     const PRETTY_NAME: &'static str;
     const HOMEPAGE: Option<&'static str>;
     const DESCRIPTION: Option<&'static str>;
-    async fn generate_configs() -> Option<Vec<Config>>;
+    /// Algorithm of the checksums attached to sources, individual sources can override this through SourceMetadata
+    const CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Sha256;
+    /// Locale of every config, for distros which only publish a localized image
+    const LOCALE: Option<&'static str> = None;
+    /// Editions are locale codes or language names, each config's language is derived from its edition
+    const LOCALIZED_EDITIONS: bool = false;
+    /// Number of releases kept by default, for scrapers which have access to older releases
+    const RELEASE_LIMIT: usize = 3;
+    /// Media attached to every config as a fixed ISO, alongside the installer
+    const AUXILIARY_MEDIA: &'static [AuxiliaryMedia] = &[];
+    /// Every config installs an image based system with a read-only root, updated atomically.
+    /// Distros with only some immutable editions mark those configs through ConfigMetadata instead
+    const IMMUTABLE: bool = false;
+    /// Name of the distro on endoflife.date, for distros which publish a support schedule
+    const EOL_PRODUCT: Option<&'static str> = None;
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError>;
+    /// VM settings a config runs best with, for images which struggle with quickemu's defaults
+    fn hardware_hints(_config: &Config) -> Option<HardwareHints> {
+        None
+    }
+    /// Number of releases the scraper should keep, taking user overrides into account
+    fn release_limit() -> usize {
+        RELEASE_LIMITS
+            .get()
+            .and_then(|limits| limits.get(Self::NAME))
+            .copied()
+            .unwrap_or(Self::RELEASE_LIMIT)
+    }
+}
+
+/// Configs produced by a distro's scraper, whose URLs haven't been validated yet
+pub struct Candidates {
+    name: &'static str,
+    pretty_name: &'static str,
+    homepage: Option<&'static str>,
+    description: Option<&'static str>,
+    releases: Vec<Config>,
+    invalid_checksums: Vec<String>,
+    /// The scraper was skipped, as the pages it read in a previous run are unchanged
+    cached: bool,
 }
 
+#[allow(async_fn_in_trait)]
 pub trait ToOS {
-    #![allow(dead_code)]
-    async fn to_os() -> Option<OS>;
+    /// Generates a distro's configs without validating their URLs, which is left to `validate` so every distro's URLs are checked together
+    async fn candidates() -> Option<Candidates>;
 }
 
 impl<T: Distro + Send> ToOS for T {
-    async fn to_os() -> Option<OS> {
-        // Any entry containing a URL which isn't reachable needs to be removed
-        let Some(releases) = Self::generate_configs().await else {
-            log::error!("Failed to generate configs for {}", Self::PRETTY_NAME);
-            return None;
+    async fn candidates() -> Option<Candidates> {
+        // The homepage is checked alongside generation, as a distro which failed to generate is the most likely to have been discontinued
+        let liveness = async {
+            if let Some(liveness) = crate::liveness::get() {
+                liveness.check(Self::NAME, Self::HOMEPAGE).await;
+            }
         };
-        if releases.is_empty() {
-            log::error!("No releases found for {}", Self::PRETTY_NAME);
-            return None;
-        }
-        let futures = releases.iter().map(|r| {
-            let urls = [
-                filter_web_sources(r.iso.as_deref()),
-                filter_web_sources(r.img.as_deref()),
-                filter_web_sources(r.fixed_iso.as_deref()),
-                filter_web_sources(r.floppy.as_deref()),
-                extract_disk_urls(r.disk_images.as_deref()),
-            ]
-            .concat();
-            async move { all_valid(urls).await }
-        });
-        let results = join_futures!(futures);
-        let releases = releases
+        let generation = async {
+            let Some(cache) = crate::cache::get() else {
+                return (Self::generate_configs().await, false);
+            };
+            if let Some(configs) = cache.previous_configs(Self::NAME).await {
+                tracing::info!(
+                    "Reusing cached configs for {}, the pages they were scraped from haven't changed",
+                    Self::PRETTY_NAME
+                );
+                return (Ok(configs), true);
+            }
+            let (result, pages) = crate::cache::track_pages(Self::generate_configs()).await;
+            if let Ok(configs) = &result {
+                cache.store_configs(Self::NAME, pages, configs);
+            }
+            (result, false)
+        };
+        let ((result, cached), _) = tokio::join!(generation, liveness);
+        let result = result.and_then(|releases| if releases.is_empty() { Err(GenerateError::NoReleasesFound) } else { Ok(releases) });
+        let mut releases = match result {
+            Ok(releases) => releases,
+            Err(e) => {
+                tracing::error!(kind = e.kind(), "Failed to generate configs for {}: {e}", Self::PRETTY_NAME);
+                DistroReport::failure(Self::NAME, e.kind(), e.to_string()).record();
+                return None;
+            }
+        };
+        attach_auxiliary_media::<Self>(&mut releases).await;
+        if let Some(lockfile) = crate::lockfile::get() {
+            lockfile.apply(&mut releases, Self::CHECKSUM_ALGORITHM);
+        }
+        let invalid_checksums = check_checksums(&mut releases, Self::CHECKSUM_ALGORITHM);
+        check_file_names(&mut releases);
+        check_appliances(&mut releases);
+        let invalid_checksums = [invalid_checksums, verify_signed_checksums(&mut releases).await].concat();
+        tag_languages::<Self>(&mut releases);
+        tag_latest(&mut releases);
+        tag_support_status::<Self>(&mut releases).await;
+        apply_hardware_hints::<Self>(&mut releases);
+        if Self::IMMUTABLE {
+            releases
+                .iter_mut()
+                .for_each(|config| attach_config_metadata(config, |m| m.immutable = true));
+        }
+        Some(Candidates {
+            name: Self::NAME,
+            pretty_name: Self::PRETTY_NAME,
+            homepage: Self::HOMEPAGE,
+            description: Self::DESCRIPTION,
+            releases,
+            invalid_checksums,
+            cached,
+        })
+    }
+}
+
+/// Awaits a spawned distro's generation, ensuring a hanging mirror or a panicking scraper only loses that distro.
+/// The timeout doesn't cover URL validation, which happens once every distro has been generated
+pub async fn supervise<T: Distro>(handle: JoinHandle<Option<Candidates>>, timeout: Duration) -> Option<Candidates> {
+    let abort_handle = handle.abort_handle();
+    match tokio::time::timeout(timeout, handle).await {
+        Ok(Ok(Some(candidates))) => {
+            crate::progress::waiting(T::NAME);
+            Some(candidates)
+        }
+        Ok(Ok(None)) => {
+            crate::progress::finish(T::NAME, Some("no configs were generated"));
+            None
+        }
+        Ok(Err(e)) => {
+            crate::progress::finish(T::NAME, Some("panicked"));
+            tracing::error!("Config generation for {} panicked: {e}", T::PRETTY_NAME);
+            DistroReport::failure(T::NAME, "panic", format!("Panicked: {e}")).record();
+            None
+        }
+        Err(_) => {
+            abort_handle.abort();
+            crate::progress::finish(T::NAME, Some("timed out"));
+            tracing::error!(
+                "Config generation for {} timed out after {} seconds",
+                T::PRETTY_NAME,
+                timeout.as_secs()
+            );
+            DistroReport::failure(T::NAME, "timeout", format!("Timed out after {} seconds", timeout.as_secs())).record();
+            None
+        }
+    }
+}
+
+/// Checks the URLs of every distro's candidate configs, removing configs with unresolvable URLs.
+/// URLs are checked in a single batch, so one referenced by several configs or distros is only requested once.
+/// Every run checks every URL, as a link can die without anything changing on the pages it was scraped from
+pub async fn validate(mut candidates: Vec<Candidates>) -> Vec<OS> {
+    for c in &candidates {
+        crate::progress::validating(c.name, c.releases.len());
+    }
+
+    // Scrapers are still run in full, only the requests checking their output are skipped
+    if validation_policy().skip_urls {
+        tracing::warn!("URL validation is disabled, configs are kept without checking their URLs");
+        return candidates.into_iter().map(|c| c.into_os(&HashMap::new())).collect();
+    }
+
+    let urls = candidates
+        .iter()
+        .flat_map(|c| &c.releases)
+        .flat_map(config_urls)
+        .collect::<HashSet<String>>();
+    let mut failures = url_failures(urls.into_iter().collect())
+        .await
+        .into_iter()
+        .collect::<HashMap<String, String>>();
+    let futures = failures
+        .keys()
+        .map(|url| async move { failover(url).await.map(|mirror| (url.clone(), mirror)) });
+    let replacements = join_futures!(futures, 1, HashMap<String, String>);
+    failures.retain(|url, _| !replacements.contains_key(url));
+    for config in candidates.iter_mut().flat_map(|c| &mut c.releases) {
+        for source in web_sources_mut(config) {
+            if let Some(mirror) = replacements.get(&source.url) {
+                source.url.clone_from(mirror);
+            }
+        }
+    }
+    // Failures are often transient, such as a mirror briefly responding with 503
+    if !failures.is_empty() {
+        tokio::time::sleep(validation_policy().retry_delay).await;
+        failures = url_failures(failures.into_keys().collect()).await.into_iter().collect();
+    }
+
+    candidates.into_iter().map(|c| c.into_os(&failures)).collect()
+}
+
+impl Candidates {
+    /// Removes the configs containing any of the unresolvable URLs, then records the distro's report
+    fn into_os(self, failures: &HashMap<String, String>) -> OS {
+        // Anything logged is attributed to the distro, so it's included in its report
+        let _span = tracing::info_span!("distro", distro = self.name).entered();
+        let policy = validation_policy();
+        let mut dropped = Vec::new();
+        let mut unverified = Vec::new();
+        let mut releases = self
+            .releases
             .into_iter()
-            .zip(results)
-            .filter_map(|(config, valid)| {
-                if valid {
+            .filter_map(|mut config| {
+                crate::progress::validated(self.name);
+                let urls = config_urls(&config);
+                let failed = urls
+                    .iter()
+                    .filter_map(|url| Some((url, failures.get(url)?)))
+                    .collect::<Vec<_>>();
+                if failed.is_empty() {
+                    return Some(config);
+                }
+                // A published checksum still allows the download to be verified, even though the URL couldn't be
+                let keep_unverified = policy.keep_unverified
+                    && failed
+                        .iter()
+                        .all(|(url, _)| web_sources_mut(&mut config).any(|w| w.url == **url && w.checksum.is_some()));
+                let reasons = failed.iter().map(|(url, reason)| format!("{url}: {reason}")).collect();
+                if keep_unverified {
+                    tracing::warn!(
+                        "Keeping {} {} {} {} with an unresolvable URL, its checksum is known",
+                        self.pretty_name,
+                        config.release,
+                        config.edition.as_deref().unwrap_or_default(),
+                        config.arch
+                    );
+                    attach_config_metadata(&mut config, |m| m.unverified_url = true);
+                    unverified.push(DroppedConfig {
+                        release: config.release.clone(),
+                        edition: config.edition.clone(),
+                        arch: config.arch.to_string(),
+                        reasons,
+                    });
                     Some(config)
                 } else {
-                    log::warn!(
+                    tracing::warn!(
                         "Removing {} {} {} {} due to unresolvable URL",
-                        Self::PRETTY_NAME,
+                        self.pretty_name,
                         config.release,
-                        config.edition.unwrap_or_default(),
+                        config.edition.as_deref().unwrap_or_default(),
                         config.arch
                     );
+                    dropped.push(DroppedConfig {
+                        release: config.release,
+                        edition: config.edition,
+                        arch: config.arch.to_string(),
+                        reasons,
+                    });
                     None
                 }
             })
             .collect::<Vec<Config>>();
+        // Only artifacts which were reachable are pinned, a broken URL's checksum can't have been checked against anything
+        if let Some(lockfile) = crate::lockfile::get().filter(|_| !policy.skip_urls) {
+            lockfile.record(&mut releases);
+        }
+        if policy.skip_urls {
+            tracing::info!("Generated {} configs for {}", releases.len(), self.pretty_name);
+        }
+
+        DistroReport {
+            name: self.name,
+            success: !releases.is_empty(),
+            error: releases
+                .is_empty()
+                .then(|| "Every config was removed due to unresolvable URLs".to_string()),
+            error_kind: releases.is_empty().then_some("unresolvable_urls"),
+            cached: self.cached,
+            configs: releases.len(),
+            dropped,
+            unverified,
+            invalid_checksums: self.invalid_checksums,
+            warnings: Vec::new(),
+            possibly_discontinued: false,
+        }
+        .record();
+        crate::progress::finish(self.name, releases.is_empty().then_some("every config was removed"));
 
-        Some(OS {
-            name: Self::NAME.into(),
-            pretty_name: Self::PRETTY_NAME.into(),
-            homepage: Self::HOMEPAGE.map(Into::into),
-            description: Self::DESCRIPTION.map(Into::into),
+        OS {
+            name: self.name.into(),
+            pretty_name: self.pretty_name.into(),
+            homepage: self.homepage.map(Into::into),
+            description: self.description.map(Into::into),
             releases,
+        }
+    }
+}
+
+/// Every URL a config depends on, including those of its netboot files
+fn config_urls(config: &Config) -> Vec<String> {
+    let mut urls = [
+        filter_web_sources(config.iso.as_deref()),
+        filter_web_sources(config.img.as_deref()),
+        filter_web_sources(config.fixed_iso.as_deref()),
+        filter_web_sources(config.floppy.as_deref()),
+        extract_disk_urls(config.disk_images.as_deref()),
+    ]
+    .concat();
+    urls.extend(ConfigMetadata::urls(&urls));
+    urls
+}
+
+pub struct ValidationPolicy {
+    /// Time to wait before checking unresolvable URLs a second time
+    pub retry_delay: Duration,
+    /// Keep configs whose URLs are still unresolvable if they have checksums, marking them as unverified rather than removing them
+    pub keep_unverified: bool,
+    /// Keep every config without checking its URLs, for quickly checking that scrapers still parse their mirrors
+    pub skip_urls: bool,
+}
+
+static VALIDATION_POLICY: OnceCell<ValidationPolicy> = OnceCell::new();
+
+/// Must be called before any configs are generated
+pub fn set_validation_policy(policy: ValidationPolicy) {
+    if VALIDATION_POLICY.set(policy).is_err() {
+        tracing::warn!("Validation policy was already set");
+    }
+}
+
+/// Overrides of each distro's `RELEASE_LIMIT`, keyed by distro name
+static RELEASE_LIMITS: OnceCell<HashMap<String, usize>> = OnceCell::new();
+
+/// Must be called before any configs are generated
+pub fn set_release_limits(limits: HashMap<String, usize>) {
+    if RELEASE_LIMITS.set(limits).is_err() {
+        tracing::warn!("Release limits were already set");
+    }
+}
+
+fn validation_policy() -> &'static ValidationPolicy {
+    VALIDATION_POLICY.get_or_init(|| ValidationPolicy {
+        retry_delay: Duration::from_secs(30),
+        keep_unverified: true,
+        skip_urls: false,
+    })
+}
+
+async fn attach_auxiliary_media<T: Distro + ?Sized>(configs: &mut [Config]) {
+    for media in T::AUXILIARY_MEDIA {
+        let Some(source) = media.source().await else {
+            tracing::warn!("{}: Unable to find the latest {}", T::PRETTY_NAME, media.name());
+            continue;
+        };
+        for config in configs.iter_mut() {
+            config.fixed_iso.get_or_insert_with(Vec::new).push(Source::Web(source.clone()));
+        }
+    }
+}
+
+fn tag_languages<T: Distro + ?Sized>(configs: &mut [Config]) {
+    if T::LOCALE.is_none() && !T::LOCALIZED_EDITIONS {
+        return;
+    }
+    for config in configs {
+        let locale = match (T::LOCALE, &config.edition) {
+            (Some(locale), _) => locale,
+            (None, Some(edition)) => edition,
+            (None, None) => continue,
+        };
+        match language_tag(locale) {
+            Some(language) => attach_config_metadata(config, |m| m.language = Some(language)),
+            None => tracing::warn!("{}: Unable to determine the language of {locale}", T::PRETTY_NAME),
+        }
+    }
+}
+
+/// OVA appliances are tar archives, and are only usable once the disk within them is known.
+/// Scrapers are expected to build them through `ova_disk`, anything else ending in .ova is fixed up or reported
+fn check_appliances(configs: &mut [Config]) {
+    let source_metadata = SOURCE_METADATA.lock().unwrap();
+    for config in configs {
+        let Some(disks) = config.disk_images.as_mut() else {
+            continue;
+        };
+        for disk in disks {
+            let Source::Web(source) = &mut disk.source else {
+                continue;
+            };
+            if !source.url.ends_with(".ova") {
+                continue;
+            }
+            if source.archive_format.is_none() {
+                source.archive_format = Some(ArchiveFormat::Tar);
+            }
+            if source_metadata.get(&source.url).is_none_or(|m| m.appliance.is_none()) {
+                tracing::warn!("{}: The disk within the appliance is unknown", source.url);
+            }
+        }
+    }
+}
+
+/// Marks each release with its end of life date and support status, matching releases to the cycles of the distro's support schedule.
+/// A release such as 12.7.0 belongs to the cycle 12, the most specific matching cycle is used
+async fn tag_support_status<T: Distro + ?Sized>(configs: &mut [Config]) {
+    let Some(product) = T::EOL_PRODUCT else {
+        return;
+    };
+    let Some(cycles) = crate::lifecycle::schedule(product).await else {
+        tracing::warn!("{}: Unable to fetch the support schedule", T::PRETTY_NAME);
+        return;
+    };
+    let today = crate::lifecycle::today();
+    for config in configs {
+        let cycle = cycles
+            .iter()
+            .filter(|c| {
+                let name = c.name();
+                config.release == name || config.release.strip_prefix(&name).is_some_and(|rest| rest.starts_with('.'))
+            })
+            .max_by_key(|c| c.name().len());
+        if let Some(cycle) = cycle {
+            attach_config_metadata(config, |m| {
+                m.eol = cycle.eol_date().map(ToString::to_string);
+                m.support_status = Some(cycle.status(&today));
+            });
+        }
+    }
+}
+
+/// RAM has a place in quickget_core's schema, so it's set on the config itself unless the scraper already chose an amount
+fn apply_hardware_hints<T: Distro + ?Sized>(configs: &mut [Config]) {
+    for config in configs {
+        let Some(hints) = T::hardware_hints(config) else {
+            continue;
+        };
+        config.ram = config.ram.or(hints.ram);
+        if hints.cpu_cores.is_some() || hints.video_memory.is_some() {
+            attach_config_metadata(config, |m| m.hardware = Some(hints));
+        }
+    }
+}
+
+/// Flags the newest release of each edition and architecture, so consumers don't depend on how a scraper names its releases.
+/// A release named "latest" is always the newest, otherwise only releases which are plain version numbers or dates are compared.
+/// Prereleases and development builds are never flagged
+fn tag_latest(configs: &mut [Config]) {
+    let stable = configs.iter().map(ConfigMetadata::is_stable).collect::<Vec<bool>>();
+    for index in newest_releases(configs, |index| stable[index]) {
+        attach_config_metadata(&mut configs[index], |m| m.latest = true);
+    }
+}
+
+/// Indices of the newest release of each edition and architecture, among the configs which pass the filter
+fn newest_releases(configs: &[Config], include: impl Fn(usize) -> bool) -> Vec<usize> {
+    let mut newest: HashMap<(Option<String>, String), usize> = HashMap::new();
+    for (index, config) in configs.iter().enumerate().filter(|(index, _)| include(*index)) {
+        let Some(rank) = latest_rank(&config.release) else {
+            continue;
+        };
+        let group = (config.edition.clone(), config.arch.to_string());
+        let newer = newest
+            .get(&group)
+            .and_then(|&current| latest_rank(&configs[current].release))
+            .is_none_or(|current| rank > current);
+        if newer {
+            newest.insert(group, index);
+        }
+    }
+    newest.into_values().collect()
+}
+
+/// Adds a copy of the newest release of each edition and architecture named "latest", alongside the concrete release,
+/// for distros which have always been requested from quickget as "latest"
+pub fn add_latest_alias(configs: &mut Vec<Config>) {
+    let aliases = newest_releases(configs, |_| true)
+        .into_iter()
+        .filter(|&index| configs[index].release != "latest")
+        .map(|index| Config {
+            release: "latest".to_string(),
+            ..configs[index].clone()
+        })
+        .collect::<Vec<Config>>();
+    configs.extend(aliases);
+}
+
+/// Orders releases named "latest" above every other, followed by plain version numbers or dates, such as 24.04 or 20240901
+fn latest_rank(release: &str) -> Option<(bool, Vec<u64>)> {
+    if release == "latest" {
+        return Some((true, Vec::new()));
+    }
+    let version = release
+        .split('.')
+        .map(|n| n.parse::<u64>().ok())
+        .collect::<Option<Vec<u64>>>()?;
+    Some((false, version))
+}
+
+/// Config metadata is looked up by the first of a config's URLs which has any,
+/// so new fields must join existing metadata rather than being attached to another URL
+pub fn attach_config_metadata(config: &mut Config, f: impl FnOnce(&mut ConfigMetadata)) {
+    let urls = web_sources_mut(config).map(|w| w.url.clone()).collect::<Vec<_>>();
+    let mut metadata = CONFIG_METADATA.lock().unwrap();
+    let url = urls.iter().find(|url| metadata.contains_key(*url)).or(urls.first());
+    if let Some(url) = url {
+        f(metadata.entry(url.clone()).or_default());
+    }
+}
+
+/// Finds the first of an unresolvable URL's fallback mirrors which resolves, moving the URL's metadata to it
+async fn failover(url: &str) -> Option<String> {
+    let mirrors = SOURCE_METADATA
+        .lock()
+        .unwrap()
+        .get(url)
+        .map(|m| m.mirrors.clone())
+        .unwrap_or_default();
+    for mirror in mirrors {
+        if !url_failures(vec![mirror.url.clone()]).await.is_empty() {
+            continue;
+        }
+        tracing::info!("Replacing unresolvable URL {url} with mirror {}", mirror.url);
+        // Metadata follows the source, with the failed URL kept as the last resort
+        let mut source_metadata = SOURCE_METADATA.lock().unwrap();
+        if let Some(mut metadata) = source_metadata.remove(url) {
+            metadata.mirrors.retain(|m| m.url != mirror.url);
+            metadata.mirrors.push(Mirror {
+                url: url.to_string(),
+                region: metadata.region,
+            });
+            metadata.region = mirror.region;
+            source_metadata.insert(mirror.url.clone(), metadata);
+        }
+        let mut config_metadata = CONFIG_METADATA.lock().unwrap();
+        if let Some(metadata) = config_metadata.remove(url) {
+            config_metadata.insert(mirror.url.clone(), metadata);
+        }
+        return Some(mirror.url);
+    }
+    None
+}
+
+pub(crate) fn web_sources_mut(config: &mut Config) -> impl Iterator<Item = &mut WebSource> {
+    [&mut config.iso, &mut config.img, &mut config.fixed_iso, &mut config.floppy]
+        .into_iter()
+        .flatten()
+        .flatten()
+        .chain(config.disk_images.iter_mut().flatten().map(|d| &mut d.source))
+        .filter_map(|source| match source {
+            Source::Web(w) => Some(w),
+            _ => None,
+        })
+}
+
+/// Tags each checksum with its algorithm, removing any which can't be a hex encoded digest of that algorithm, e.g. when a scraper picks up the wrong text.
+/// Returns a description of each checksum which was removed.
+fn check_checksums(configs: &mut [Config], default_algorithm: ChecksumAlgorithm) -> Vec<String> {
+    let mut source_metadata = SOURCE_METADATA.lock().unwrap();
+    configs
+        .iter_mut()
+        .flat_map(web_sources_mut)
+        .filter_map(|w| {
+            let checksum = w.checksum.as_deref()?;
+            let metadata = source_metadata.entry(w.url.clone()).or_default();
+            let algorithm = *metadata.checksum_algorithm.get_or_insert(default_algorithm);
+            if checksum.len() == algorithm.digest_length() && checksum.chars().all(|c| c.is_ascii_hexdigit()) {
+                return None;
+            }
+            tracing::warn!("Removing invalid {algorithm:?} checksum {checksum} from {}", w.url);
+            let incident = format!("{}: {checksum}", w.url);
+            metadata.checksum_algorithm = None;
+            w.checksum = None;
+            Some(incident)
         })
+        .collect()
+}
+
+/// Verifies the signatures of signed checksum files, then confirms each checksum taken from one is the checksum it lists for that file.
+/// Checksums which can't be confirmed are removed, since they may have been tampered with. Returns a description of each.
+async fn verify_signed_checksums(configs: &mut [Config]) -> Vec<String> {
+    let signed = {
+        let source_metadata = SOURCE_METADATA.lock().unwrap();
+        configs
+            .iter_mut()
+            .flat_map(web_sources_mut)
+            .filter(|w| w.checksum.is_some())
+            .filter_map(|w| {
+                let signature = source_metadata.get(&w.url)?.signature.as_ref()?;
+                Some((signature.checksum_file.clone()?, signature.clone()))
+            })
+            .collect::<HashMap<String, Signature>>()
+    };
+    if signed.is_empty() {
+        return Vec::new();
+    }
+    let futures = signed.into_iter().map(|(checksum_file, signature)| async move {
+        let outcome = verify_checksum_file(&signature).await;
+        (checksum_file, outcome)
+    });
+    let outcomes = join_futures!(futures).into_iter().collect::<HashMap<String, Outcome>>();
+
+    let mut removed = Vec::new();
+    for config in configs.iter_mut() {
+        let mut status = None;
+        for w in web_sources_mut(config) {
+            let signature = SOURCE_METADATA
+                .lock()
+                .unwrap()
+                .get(&w.url)
+                .and_then(|m| m.signature.clone())
+                .filter(|s| s.checksum_file.is_some());
+            let (Some(checksum), Some(signature)) = (&w.checksum, signature) else {
+                continue;
+            };
+            let Some(outcome) = signature.checksum_file.as_ref().and_then(|f| outcomes.get(f)) else {
+                continue;
+            };
+            let source_status = match outcome {
+                Outcome::Verified(contents) if lists_checksum(&signature, contents, &w.url, checksum) => ChecksumVerification::Verified,
+                Outcome::Unchecked => ChecksumVerification::Unchecked,
+                _ => {
+                    tracing::warn!(
+                        "Removing checksum {checksum} from {}, it isn't the one listed for it in a verified checksum file",
+                        w.url
+                    );
+                    removed.push(format!("{}: {checksum}", w.url));
+                    w.checksum = None;
+                    ChecksumVerification::Failed
+                }
+            };
+            // A config is only as verified as its least verified source
+            status = status.max(Some(source_status));
+        }
+        if let Some(status) = status {
+            attach_config_metadata(config, |m| m.checksum_verification = Some(status));
+        }
     }
+    removed
+}
+
+/// Name quickget saves a source as, which is its file name override or otherwise the last segment of its URL
+fn local_file_name(source: &WebSource) -> &str {
+    match &source.file_name {
+        Some(file_name) => file_name,
+        None => source.url.rsplit('/').next().unwrap_or_default(),
+    }
+}
+
+/// Warns about configs whose sources would be saved under unhelpful names, such as SourceForge's `download` or a query string,
+/// or under the same name as another of the config's sources. Scrapers should pass a file name to `WebSource::new` for these URLs
+fn check_file_names(configs: &mut [Config]) {
+    for config in configs {
+        let (release, edition, arch) = (
+            config.release.clone(),
+            config.edition.clone().unwrap_or_default(),
+            config.arch.to_string(),
+        );
+        let mut names = HashSet::new();
+        for source in web_sources_mut(config) {
+            let name = local_file_name(source);
+            if name.is_empty() || name == "download" || !name.contains('.') || name.contains(['?', '&', '=']) {
+                tracing::warn!("{release} {edition} {arch}: {} would be saved as {name:?}", source.url);
+            } else if !names.insert(name.to_string()) {
+                tracing::warn!("{release} {edition} {arch}: More than one source would be saved as {name}");
+            }
+        }
+    }
+}
+
+/// Builds the URL of a file which is served by several mirrors, in order of preference.
+/// The remaining mirrors are recorded as fallbacks, in case the first becomes unresolvable.
+pub fn mirrored_url(mirrors: &[(&str, Region)], path: &str) -> String {
+    let (primary, region) = mirrors[0];
+    let url = format!("{primary}{path}");
+    SourceMetadata::attach(&url, |m| {
+        m.region = Some(region);
+        m.mirrors = mirrors[1..]
+            .iter()
+            .map(|(mirror, region)| Mirror {
+                url: format!("{mirror}{path}"),
+                region: Some(*region),
+            })
+            .collect();
+    });
+    url
 }
 
 pub fn filter_web_sources(sources: Option<&[Source]>) -> Vec<String> {
@@ -94,42 +726,623 @@ pub fn extract_disk_urls(sources: Option<&[Disk]>) -> Vec<String> {
         .collect()
 }
 
-pub static DEFAULT_SHA256_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"SHA256 \(([^)]+)\) = ([0-9a-f]+)"#).unwrap());
-pub static DEFAULT_MD5_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"MD5 \(([^)]+)\) = ([0-9a-f]+)"#).unwrap());
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecksumAlgorithm {
+    Md5,
+    Sha256,
+    Sha512,
+}
 
-pub enum ChecksumSeparation {
-    Whitespace,
-    Sha256Regex,
-    Md5Regex,
-    CustomRegex(Arc<Regex>, usize, usize),
+impl ChecksumAlgorithm {
+    /// Length of the hex encoded digest
+    pub fn digest_length(&self) -> usize {
+        match self {
+            Self::Md5 => 32,
+            Self::Sha256 => 64,
+            Self::Sha512 => 128,
+        }
+    }
+}
+
+enum ChecksumStrategy {
+    /// A file listing the checksums of many files, such as SHA256SUMS. It's only fetched once, however many files are looked up
+    Aggregate {
+        url: String,
+        format: Box<dyn ChecksumFormat + Send + Sync>,
+        checksums: tokio::sync::OnceCell<Option<HashMap<String, String>>>,
+    },
+    /// A file published alongside the source, named after it with a suffix such as .sha256
+    Suffix(&'static str),
+    /// A page such as release notes, whose regex captures the checksum as its first group
+    Page(String, Regex),
+}
+
+/// Distros publish checksums in different ways, sometimes inconsistently across mirrors and releases.
+/// Each strategy is tried in the order it was added, until one of them has the file's checksum.
+#[derive(Default)]
+pub struct ChecksumSource {
+    strategies: Vec<(ChecksumStrategy, Option<ChecksumAlgorithm>)>,
+}
+
+impl ChecksumSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn aggregate(mut self, url: impl Into<String>, format: impl ChecksumFormat + Send + Sync + 'static) -> Self {
+        let strategy = ChecksumStrategy::Aggregate {
+            url: url.into(),
+            format: Box::new(format),
+            checksums: tokio::sync::OnceCell::new(),
+        };
+        self.strategies.push((strategy, None));
+        self
+    }
+    pub fn suffix(mut self, suffix: &'static str) -> Self {
+        self.strategies.push((ChecksumStrategy::Suffix(suffix), None));
+        self
+    }
+    pub fn page(mut self, url: impl Into<String>, regex: Regex) -> Self {
+        self.strategies.push((ChecksumStrategy::Page(url.into(), regex), None));
+        self
+    }
+    /// Checksums found through the previously added strategy use this algorithm, rather than the distro's
+    pub fn algorithm(mut self, algorithm: ChecksumAlgorithm) -> Self {
+        if let Some((_, previous)) = self.strategies.last_mut() {
+            *previous = Some(algorithm);
+        }
+        self
+    }
+    /// Finds the checksum of the file at `url`, which is named by the last segment of its path
+    pub async fn find(&self, url: &str) -> Option<String> {
+        let file = url.rsplit('/').next()?;
+        for (strategy, algorithm) in &self.strategies {
+            let checksum = match strategy {
+                ChecksumStrategy::Aggregate { url, format, checksums } => checksums
+                    .get_or_init(|| async { crate::utils::capture_page(url).await.map(|data| format.parse(&data)) })
+                    .await
+                    .as_ref()
+                    .and_then(|cs| cs.get(file).cloned()),
+                ChecksumStrategy::Suffix(suffix) => single_checksum(&format!("{url}{suffix}")).await,
+                ChecksumStrategy::Page(url, regex) => crate::utils::capture_page(url)
+                    .await
+                    .and_then(|page| Some(regex.captures(&page)?[1].to_string())),
+            };
+            if let Some(checksum) = checksum {
+                if let Some(algorithm) = algorithm {
+                    SourceMetadata::attach(url, |m| m.checksum_algorithm = Some(*algorithm));
+                }
+                return Some(checksum);
+            }
+        }
+        None
+    }
+}
+
+/// Data about a config which quickget_core's schema has no place for.
+/// It's keyed by the URL of one of the config's sources, and merged into the config's JSON object on output.
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ConfigMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cloud_init: Option<CloudInit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub netboot: Option<NetBoot>,
+    /// BCP 47 language tag, present on localized images
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// One of the config's URLs couldn't be resolved during generation, but its checksum is known
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub unverified_url: bool,
+    /// Present on prereleases and development builds, configs without a channel are stable releases
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<Channel>,
+    /// The config is the newest release of its edition and architecture
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub latest: bool,
+    /// The installed system is image based, with a read-only root which is updated atomically
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub immutable: bool,
+    /// Whether the config's checksums were confirmed against a checksum file with a valid signature, present when one is published
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum_verification: Option<ChecksumVerification>,
+    /// Date the release stops receiving security updates, YYYY-MM-DD
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub support_status: Option<SupportStatus>,
+    /// Recommended VM settings which quickget_core's schema has no place for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hardware: Option<HardwareHints>,
+    /// Every OS whose configs contain one of this config's images, when more than one does
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub shared_by: Vec<String>,
+}
+
+pub const MIB: u64 = 1024 * 1024;
+pub const GIB: u64 = 1024 * MIB;
+
+/// VM settings an image runs best with, such as the small amount of RAM legacy systems expect
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct HardwareHints {
+    /// In bytes, written to the config's own `ram` field
+    #[serde(skip)]
+    pub ram: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_cores: Option<u32>,
+    /// Memory of the emulated display adapter, in bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video_memory: Option<u64>,
+}
+
+/// Whether a release is still supported, according to its distro's published schedule
+#[derive(Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SupportStatus {
+    Supported,
+    /// Active support has ended, only security updates are still published
+    Security,
+    Eol,
+}
+
+/// Outcome of verifying the signed checksum files a config's checksums were taken from
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecksumVerification {
+    Verified,
+    /// The signature couldn't be checked, such as when its key isn't embedded, so the checksums were kept
+    Unchecked,
+    /// The signature was invalid or didn't list the checksum, so it was removed
+    Failed,
 }
 
-impl ChecksumSeparation {
-    pub async fn build(self, url: &str) -> Option<HashMap<String, String>> {
-        let data = crate::utils::capture_page(url).await?;
-        Some(self.build_with_data(&data))
+/// How finished a release is, allowing consumers to leave out builds which aren't meant for general use.
+/// Stable releases have no channel
+#[derive(Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Channel {
+    /// Alphas, betas and release candidates of an upcoming release
+    Testing,
+    /// Automated builds of the latest development code, which are replaced frequently
+    Nightly,
+}
+
+/// The image ships without usable credentials, a cloud-init seed must be attached to log in
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CloudInit {
+    pub default_user: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_password: Option<String>,
+}
+
+const VIRTIO_WIN_MIRROR: &str = "https://fedorapeople.org/groups/virt/virtio-win/direct-downloads/stable-virtio/";
+static VIRTIO_WIN: tokio::sync::OnceCell<Option<WebSource>> = tokio::sync::OnceCell::const_new();
+
+/// Media which helps guests run under QEMU, without being part of the operating system itself
+pub enum AuxiliaryMedia {
+    /// Fedora's build of the VirtIO drivers and guest agent for Windows
+    VirtioWin,
+}
+
+impl AuxiliaryMedia {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::VirtioWin => "virtio-win ISO",
+        }
     }
-    pub fn build_with_data(self, data: &str) -> HashMap<String, String> {
+    /// Resolved once per run, every distro which attaches the media shares it
+    async fn source(&self) -> Option<WebSource> {
         match self {
-            Self::Whitespace => data
-                .lines()
-                .filter_map(|l| {
-                    l.split_once(' ')
-                        .map(|(hash, file)| (file.trim().to_string(), hash.trim().to_string()))
-                })
-                .collect(),
-            Self::Md5Regex => DEFAULT_MD5_REGEX
-                .captures_iter(data)
-                .map(|c| (c[1].to_string(), c[2].to_string()))
-                .collect(),
-            Self::Sha256Regex => DEFAULT_SHA256_REGEX
-                .captures_iter(data)
-                .map(|c| (c[1].to_string(), c[2].to_string()))
-                .collect(),
-            Self::CustomRegex(regex, keyindex, valueindex) => regex
-                .captures_iter(data)
-                .map(|c| (c[keyindex].to_string(), c[valueindex].to_string()))
-                .collect(),
+            Self::VirtioWin => VIRTIO_WIN.get_or_init(latest_virtio_win).await.clone(),
+        }
+    }
+}
+
+/// The stable directory links virtio-win.iso to the newest versioned ISO, which is listed beside it.
+/// The versioned URL is used, since its contents never change
+async fn latest_virtio_win() -> Option<WebSource> {
+    let iso_regex = Regex::new(r"^virtio-win-0\.1\.(\d+)\.iso$").unwrap();
+    let links = list_links_matching(VIRTIO_WIN_MIRROR, &iso_regex).await?;
+    let iso = links
+        .into_iter()
+        .max_by_key(|link| iso_regex.captures(link).and_then(|c| c[1].parse::<u32>().ok()))?;
+    // Fedora doesn't publish checksums of the ISO
+    Some(WebSource::url_only(format!("{VIRTIO_WIN_MIRROR}{iso}")))
+}
+
+/// A disk image which is only published as an OVA appliance, such as those made for VirtualBox.
+/// The disk is extracted from the appliance and converted to qcow2
+pub fn ova_disk(url: String, checksum: Option<String>, disk: impl Into<String>) -> Disk {
+    let disk = disk.into();
+    SourceMetadata::attach(&url, |m| m.appliance = Some(Appliance { disk }));
+    Disk {
+        source: Source::Web(WebSource::new(url, checksum, Some(ArchiveFormat::Tar), None)),
+        format: DiskFormat::Qcow2,
+        ..Default::default()
+    }
+}
+
+/// Builds a config for a cloud image, a qcow2 disk image under the "cloud" edition which boots straight into an installed system.
+/// Cloud images ship without usable credentials, so the user which a cloud-init seed should configure is attached
+pub fn cloud_image(release: String, arch: Arch, source: WebSource, default_user: &str) -> Config {
+    ConfigMetadata::attach(&source.url, |m| {
+        m.cloud_init = Some(CloudInit {
+            default_user: default_user.to_string(),
+            default_password: None,
+        })
+    });
+    Config {
+        release,
+        edition: Some("cloud".to_string()),
+        arch,
+        disk_images: Some(vec![Disk {
+            source: Source::Web(source),
+            format: DiskFormat::Qcow2,
+            ..Default::default()
+        }]),
+        ..Default::default()
+    }
+}
+
+/// A kernel and initrd which can be booted directly, allowing a network install without an ISO
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NetBoot {
+    #[schemars(with = "crate::schema::WebSource")]
+    pub kernel: WebSource,
+    #[schemars(with = "crate::schema::WebSource")]
+    pub initrd: WebSource,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cmdline: Option<String>,
+    /// An archive of every netboot file, for distros which only publish a checksum of the archive rather than of each file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<crate::schema::WebSource>")]
+    pub archive: Option<WebSource>,
+}
+
+/// Data about a web source which quickget_core's schema has no place for.
+/// It's keyed by the source's URL, and merged into the source's JSON object on output.
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct SourceMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<Signature>,
+    /// Unix timestamp after which the URL stops working
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum_algorithm: Option<ChecksumAlgorithm>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<Region>,
+    /// A .torrent file or magnet link which downloads the same file over BitTorrent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub torrent: Option<String>,
+    /// Alternative URLs for the same file, in order of preference
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub mirrors: Vec<Mirror>,
+    /// Size of the file in bytes, as reported by the server when its URL was validated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// Whether the server accepts range requests, allowing an interrupted download to be resumed.
+    /// Absent when the server didn't say either way
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resumable: Option<bool>,
+    /// Present on OVA appliances, naming the disk to extract from the archive
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appliance: Option<Appliance>,
+    /// Headers which must be sent when downloading the file, such as the cookie holding an access token.
+    /// Usually paired with `expires`, as the token stops working
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+}
+
+/// A disk within an OVA appliance. OVAs are tar archives holding an OVF descriptor alongside VMDK disks,
+/// the disk is extracted then converted to the format of the config's disk image
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Appliance {
+    /// Name of the disk within the archive, such as `Whonix-Xfce-disk001.vmdk`
+    pub disk: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Mirror {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<Region>,
+}
+
+/// Where a mirror is located, allowing consumers to pick one near them
+#[derive(Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Region {
+    /// Served through a CDN, or otherwise distributed worldwide
+    Global,
+    Us,
+    Eu,
+}
+
+/// A signature published alongside a source, or alongside the checksum file its checksum was taken from
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Signature {
+    pub url: String,
+    /// Set when the signature covers a checksum file rather than the source itself. It matches the signature's URL when the
+    /// signature is embedded within the checksum file, such as a clearsigned file or OpenBSD's SHA256.sig
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum_file: Option<String>,
+    /// How the checksum file lists each file's checksum
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum_format: Option<ChecksumFileFormat>,
+    #[serde(default)]
+    pub scheme: SignatureScheme,
+    /// Fingerprint of the PGP key which made the signature. signify keys are identified by name instead, such as `openbsd-76-base`
+    pub key_fingerprint: String,
+}
+
+impl Signature {
+    pub fn detached(url: String, key_fingerprint: &'static str) -> Self {
+        Self {
+            url,
+            checksum_file: None,
+            checksum_format: None,
+            scheme: SignatureScheme::Pgp,
+            key_fingerprint: key_fingerprint.to_string(),
+        }
+    }
+    pub fn checksum_file(url: String, checksum_file: String, checksum_format: ChecksumFileFormat, key_fingerprint: &'static str) -> Self {
+        Self {
+            url,
+            checksum_file: Some(checksum_file),
+            checksum_format: Some(checksum_format),
+            scheme: SignatureScheme::Pgp,
+            key_fingerprint: key_fingerprint.to_string(),
+        }
+    }
+    /// A checksum file signed with signify, which embeds the signature at the top of the file
+    pub fn signify(url: String, checksum_format: ChecksumFileFormat, key_name: String) -> Self {
+        Self {
+            checksum_file: Some(url.clone()),
+            url,
+            checksum_format: Some(checksum_format),
+            scheme: SignatureScheme::Signify,
+            key_fingerprint: key_name,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureScheme {
+    #[default]
+    Pgp,
+    /// OpenBSD's signify, which signs with Ed25519 keys
+    Signify,
+}
+
+/// Layout of a signed checksum file, allowing the checksums within it to be compared once its signature is verified
+#[derive(Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecksumFileFormat {
+    /// One checksum per line, followed by the file name, as written by sha256sum
+    Whitespace,
+    /// Lines formatted as `SHA256 (file) = checksum`, as written by BSD tools
+    Bsd,
+    /// A list of mappings naming a `file` alongside its checksum, keyed by algorithm, such as Alpine's latest-releases.yaml
+    Yaml,
+}
+
+static CONFIG_METADATA: Lazy<Mutex<HashMap<String, ConfigMetadata>>> = Lazy::new(Default::default);
+static SOURCE_METADATA: Lazy<Mutex<HashMap<String, SourceMetadata>>> = Lazy::new(Default::default);
+
+impl ConfigMetadata {
+    pub fn attach(url: &str, f: impl FnOnce(&mut Self)) {
+        let mut metadata = CONFIG_METADATA.lock().unwrap();
+        f(metadata.entry(url.to_string()).or_default());
+    }
+    /// Whether the config was flagged as the newest release of its edition and architecture, and isn't a prerelease or development build
+    pub fn is_latest_stable(config: &Config) -> bool {
+        let urls = [
+            filter_web_sources(config.iso.as_deref()),
+            filter_web_sources(config.img.as_deref()),
+            filter_web_sources(config.fixed_iso.as_deref()),
+            filter_web_sources(config.floppy.as_deref()),
+            extract_disk_urls(config.disk_images.as_deref()),
+        ]
+        .concat();
+        let metadata = CONFIG_METADATA.lock().unwrap();
+        urls.iter()
+            .find_map(|url| metadata.get(url))
+            .is_some_and(|m| m.latest && m.channel.is_none())
+    }
+    /// Whether the config isn't a prerelease or development build
+    fn is_stable(config: &Config) -> bool {
+        let urls = config_urls(config);
+        let metadata = CONFIG_METADATA.lock().unwrap();
+        urls.iter()
+            .find_map(|url| metadata.get(url))
+            .is_none_or(|m| m.channel.is_none())
+    }
+    /// URLs contained within metadata attached to any of the given source URLs, these must be validated alongside the sources
+    pub fn urls(source_urls: &[String]) -> Vec<String> {
+        let metadata = CONFIG_METADATA.lock().unwrap();
+        source_urls
+            .iter()
+            .filter_map(|url| metadata.get(url)?.netboot.as_ref())
+            .flat_map(|netboot| {
+                [Some(&netboot.kernel), Some(&netboot.initrd), netboot.archive.as_ref()]
+                    .into_iter()
+                    .flatten()
+                    .map(|source| source.url.clone())
+            })
+            .collect()
+    }
+}
+
+/// The config and source metadata attached to any of the configs' URLs, so it can be restored when the configs are reused
+pub(crate) fn metadata_of(configs: &[Config]) -> (HashMap<String, ConfigMetadata>, HashMap<String, SourceMetadata>) {
+    let urls = configs.iter().flat_map(config_urls).collect::<HashSet<String>>();
+    let config_metadata = CONFIG_METADATA.lock().unwrap();
+    let source_metadata = SOURCE_METADATA.lock().unwrap();
+    let configs = urls
+        .iter()
+        .filter_map(|url| Some((url.clone(), config_metadata.get(url)?.clone())))
+        .collect();
+    let sources = urls
+        .iter()
+        .filter_map(|url| Some((url.clone(), source_metadata.get(url)?.clone())))
+        .collect();
+    (configs, sources)
+}
+
+pub(crate) fn restore_metadata(config_metadata: HashMap<String, ConfigMetadata>, source_metadata: HashMap<String, SourceMetadata>) {
+    CONFIG_METADATA.lock().unwrap().extend(config_metadata);
+    SOURCE_METADATA.lock().unwrap().extend(source_metadata);
+}
+
+impl SourceMetadata {
+    pub fn attach(url: &str, f: impl FnOnce(&mut Self)) {
+        let mut metadata = SOURCE_METADATA.lock().unwrap();
+        f(metadata.entry(url.to_string()).or_default());
+    }
+    /// Headers attached to the source at `url`, which any request for it must send
+    pub fn headers(url: &str) -> BTreeMap<String, String> {
+        let metadata = SOURCE_METADATA.lock().unwrap();
+        metadata.get(url).map(|m| m.headers.clone()).unwrap_or_default()
+    }
+    /// Algorithm of the source's checksum, as chosen by its scraper, or otherwise the distro's default
+    pub fn checksum_algorithm(url: &str, default: ChecksumAlgorithm) -> ChecksumAlgorithm {
+        let metadata = SOURCE_METADATA.lock().unwrap();
+        metadata.get(url).and_then(|m| m.checksum_algorithm).unwrap_or(default)
+    }
+}
+
+pub fn insert_metadata(distros: &mut Value) {
+    insert_os_metadata(distros);
+    insert_config_metadata(distros);
+    // Source metadata must come second, signatures contain URLs which would otherwise be collected from configs
+    insert_source_metadata(distros, &SOURCE_METADATA.lock().unwrap());
+}
+
+fn insert_source_metadata(value: &mut Value, source_metadata: &HashMap<String, SourceMetadata>) {
+    match value {
+        Value::Object(object) => {
+            let metadata = match object.get("url") {
+                Some(Value::String(url)) => source_metadata.get(url).map(serde_json::to_value),
+                _ => None,
+            };
+            if let Some(Ok(Value::Object(fields))) = metadata {
+                object.extend(fields);
+            } else {
+                object.values_mut().for_each(|v| insert_source_metadata(v, source_metadata));
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(|v| insert_source_metadata(v, source_metadata)),
+        _ => {}
+    }
+}
+
+fn insert_os_metadata(distros: &mut Value) {
+    let systems = distros.as_array_mut().into_iter().flatten().filter_map(Value::as_object_mut);
+    for os in systems {
+        let discontinued = os
+            .get("os")
+            .and_then(Value::as_str)
+            .is_some_and(crate::liveness::possibly_discontinued);
+        if discontinued {
+            os.insert("possibly_discontinued".into(), Value::Bool(true));
+        }
+    }
+}
+
+fn insert_config_metadata(distros: &mut Value) {
+    let config_metadata = CONFIG_METADATA.lock().unwrap();
+    let configs = distros
+        .as_array_mut()
+        .into_iter()
+        .flatten()
+        .filter_map(|os| os.get_mut("releases")?.as_array_mut())
+        .flatten()
+        .filter_map(Value::as_object_mut);
+
+    for config in configs {
+        let metadata = {
+            let mut urls = Vec::new();
+            collect_urls(config, &mut urls);
+            urls.iter().find_map(|url| config_metadata.get(*url)).map(serde_json::to_value)
+        };
+        if let Some(Ok(Value::Object(fields))) = metadata {
+            config.extend(fields);
+        }
+    }
+}
+
+fn collect_urls<'a>(object: &'a Map<String, Value>, urls: &mut Vec<&'a str>) {
+    for (key, value) in object {
+        match value {
+            Value::String(url) if key == "url" => urls.push(url),
+            Value::Object(object) => collect_urls(object, urls),
+            Value::Array(values) => values.iter().filter_map(Value::as_object).for_each(|o| collect_urls(o, urls)),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn appliance_config(disk: Disk) -> Config {
+        Config {
+            release: "1.0".to_string(),
+            disk_images: Some(vec![disk]),
+            ..Default::default()
         }
     }
+
+    #[test]
+    fn check_appliances_extracts_ova() {
+        let url = "https://example.com/unknown-appliance.ova".to_string();
+        let mut configs = vec![appliance_config(Disk {
+            source: Source::Web(WebSource::url_only(url.clone())),
+            format: DiskFormat::Qcow2,
+            ..Default::default()
+        })];
+        check_appliances(&mut configs);
+        let Source::Web(source) = &configs[0].disk_images.as_ref().unwrap()[0].source else {
+            unreachable!()
+        };
+        assert_eq!(source.archive_format, Some(ArchiveFormat::Tar));
+        assert!(SOURCE_METADATA
+            .lock()
+            .unwrap()
+            .get(&url)
+            .and_then(|m| m.appliance.as_ref())
+            .is_none());
+    }
+
+    #[test]
+    fn check_appliances_keeps_known_disk() {
+        let url = "https://example.com/known-appliance.ova".to_string();
+        let mut configs = vec![appliance_config(ova_disk(url.clone(), None, "known-disk1.vmdk"))];
+        check_appliances(&mut configs);
+        let Source::Web(source) = &configs[0].disk_images.as_ref().unwrap()[0].source else {
+            unreachable!()
+        };
+        assert_eq!(source.archive_format, Some(ArchiveFormat::Tar));
+        let metadata = SOURCE_METADATA.lock().unwrap();
+        let appliance = metadata.get(&url).and_then(|m| m.appliance.as_ref());
+        assert_eq!(appliance.map(|a| a.disk.as_str()), Some("known-disk1.vmdk"));
+    }
+
+    #[test]
+    fn check_appliances_ignores_other_disks() {
+        let mut configs = vec![appliance_config(Disk {
+            source: Source::Web(WebSource::url_only("https://example.com/disk.qcow2".to_string())),
+            format: DiskFormat::Qcow2,
+            ..Default::default()
+        })];
+        check_appliances(&mut configs);
+        let Source::Web(source) = &configs[0].disk_images.as_ref().unwrap()[0].source else {
+            unreachable!()
+        };
+        assert_eq!(source.archive_format, None);
+    }
 }
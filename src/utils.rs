@@ -1,30 +1,222 @@
 #![allow(dead_code)]
+use crate::{cache::CachedPage, fixtures, store_data::SourceMetadata};
+use isolang::Language;
 use join_futures::join_futures;
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
 use quickemu::config::Arch;
 use quickget_core::data_structures::ArchiveFormat;
-use reqwest::{StatusCode, Url};
+use regex::Regex;
+use reqwest::{
+    header::{
+        HeaderMap, HeaderName, HeaderValue, ACCEPT, ACCEPT_RANGES, AUTHORIZATION, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, RANGE,
+        RETRY_AFTER,
+    },
+    Response, StatusCode, Url,
+};
 use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
-use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
+use reqwest_retry::{policies::ExponentialBackoff, DefaultRetryableStrategy, RetryTransientMiddleware, Retryable, RetryableStrategy};
+use scraper::{Html, Selector};
 use serde::Deserialize;
-use std::collections::HashMap;
-use tokio::sync::Semaphore;
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::{Semaphore, SemaphorePermit},
+    task::JoinHandle,
+};
+use tracing::Instrument;
+
+// Index pages, checksum files and API responses are far smaller than this, anything larger is most likely a misdirected image
+const MAX_PAGE_SIZE: usize = 8 * 1024 * 1024;
+const MAX_LARGE_PAGE_SIZE: usize = 128 * 1024 * 1024;
+// Media types which can never be a page, regardless of what the body contains
+const BINARY_CONTENT_TYPES: [&str; 12] = [
+    "image/",
+    "audio/",
+    "video/",
+    "application/zip",
+    "application/gzip",
+    "application/x-gzip",
+    "application/x-xz",
+    "application/x-bzip2",
+    "application/x-7z-compressed",
+    "application/x-iso9660-image",
+    "application/x-tar",
+    "application/vnd.efi.img",
+];
 
 pub async fn capture_page(input: &str) -> Option<String> {
+    capture_page_with_headers(input, HeaderMap::new()).await
+}
+
+pub async fn capture_page_with_headers(input: &str, headers: HeaderMap) -> Option<String> {
+    capture(input, headers, MAX_PAGE_SIZE).await
+}
+
+/// For endpoints which are expected to respond with large bodies, such as paginated APIs or bucket listings
+pub async fn capture_large_page(input: &str) -> Option<String> {
+    capture(input, HeaderMap::new(), MAX_LARGE_PAGE_SIZE).await
+}
+
+/// Targets of the links within a page, such as the files and directories of a mirror's directory index
+pub async fn list_links(url: &str) -> Option<Vec<String>> {
+    capture_page(url).await.map(|page| links(&page))
+}
+
+/// Targets of the links within a page which match `pattern`. Patterns should be anchored, since targets are otherwise matched partially
+pub async fn list_links_matching(url: &str, pattern: &Regex) -> Option<Vec<String>> {
+    let mut links = list_links(url).await?;
+    links.retain(|link| pattern.is_match(link));
+    Some(links)
+}
+
+static ANCHOR_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("a[href]").unwrap());
+
+/// Extracts the target of every anchor within an HTML page. The page is parsed as a browser would, so attribute order, quoting,
+/// comments and entities don't matter. Directory indexes often link to an entry twice, so targets are only listed the first time they appear
+pub fn links(html: &str) -> Vec<String> {
+    let document = Html::parse_document(html);
+    let mut seen = HashSet::new();
+    document
+        .select(&ANCHOR_SELECTOR)
+        .filter_map(|anchor| anchor.value().attr("href"))
+        .map(ToString::to_string)
+        .filter(|target| seen.insert(target.clone()))
+        .collect()
+}
+
+#[tracing::instrument(level = "debug", skip_all, fields(url = input))]
+async fn capture(input: &str, headers: HeaderMap, max_size: usize) -> Option<String> {
+    if fixtures::replaying() {
+        return fixtures::replay(input);
+    }
+    // Only plain GET requests can be repeated to check whether a distro's pages have changed since the previous run
+    let plain = headers.is_empty();
+    let output = fetch_page(input, headers, max_size).await;
+    if plain {
+        crate::cache::record_page(input, output.as_deref());
+    } else {
+        crate::cache::record_opaque_request();
+    }
+    output
+}
+
+async fn fetch_page(input: &str, headers: HeaderMap, max_size: usize) -> Option<String> {
     let url: Url = input.parse().ok()?;
-    let url_permit = match CLIENT.url_permits.get(url.host_str()?) {
-        Some(semaphore) => Some(semaphore.acquire().await.ok()?),
-        None => None,
+    let url_permit = CLIENT.host_permit(&url).await?;
+
+    // Conditional requests allow mirrors to skip sending pages which haven't changed since the previous run
+    let cache = crate::cache::get();
+    let cached = cache.and_then(|c| c.page(input));
+    let mut headers = headers;
+    if let Some(cached) = &cached {
+        if let Some(etag) = cached.etag.as_deref().and_then(|e| HeaderValue::from_str(e).ok()) {
+            headers.insert(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = cached.last_modified.as_deref().and_then(|l| HeaderValue::from_str(l).ok()) {
+            headers.insert(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    let permit = CLIENT.semaphore.acquire().await.ok()?;
+    let headers = CLIENT.headers(&url, headers);
+    let response = CLIENT.client.get(url).headers(headers).send().await.ok()?;
+
+    let status = response.status();
+    let output = if status == StatusCode::NOT_MODIFIED && cached.is_some() {
+        cached.map(|c| c.body)
+    } else if status.is_success() {
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|h: &HeaderValue| h.to_str().ok())
+                .map(ToString::to_string)
+        };
+        let (etag, last_modified) = (header(ETAG), header(LAST_MODIFIED));
+        let text = read_text(input, response, max_size).await.filter(|text| !text.is_empty());
+        if let (Some(cache), Some(body)) = (cache, &text) {
+            cache.store_page(
+                input,
+                CachedPage {
+                    etag,
+                    last_modified,
+                    body: body.clone(),
+                    used_at: 0,
+                },
+            );
+        }
+        text
+    } else {
+        tracing::warn!("Failed to capture page: {}, {}", input, status);
+        None
     };
 
+    drop(permit);
+    if let Some(url_permit) = url_permit {
+        drop(url_permit);
+    }
+    if let Some(output) = &output {
+        fixtures::record(input, output);
+    }
+    output
+}
+
+/// Reads the body as text, giving up as soon as it turns out to be binary or larger than `max_size`
+async fn read_text(input: &str, mut response: Response, max_size: usize) -> Option<String> {
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or_default()
+        .to_lowercase();
+    if BINARY_CONTENT_TYPES.iter().any(|binary| content_type.starts_with(binary)) {
+        tracing::warn!("Refusing to capture page {input}, its content type is {content_type}");
+        return None;
+    }
+    if response.content_length().is_some_and(|length| length > max_size as u64) {
+        tracing::warn!("Refusing to capture page {input}, its length exceeds {max_size} bytes");
+        return None;
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.ok()? {
+        // Checksum files are often served as application/octet-stream, so the body itself has to be checked
+        if body.is_empty() && chunk.contains(&0) {
+            tracing::warn!("Refusing to capture page {input}, its body is binary");
+            return None;
+        }
+        if body.len() + chunk.len() > max_size {
+            tracing::warn!("Stopped capturing page {input}, its body exceeds {max_size} bytes");
+            return None;
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Some(String::from_utf8_lossy(&body).into_owned())
+}
+
+#[tracing::instrument(level = "debug", skip_all, fields(url = input))]
+pub async fn capture_headers(input: &str) -> Option<HeaderMap> {
+    crate::cache::record_opaque_request();
+    let fixture_key = format!("HEAD {input}");
+    if fixtures::replaying() {
+        return fixtures::replay_headers(&fixture_key);
+    }
+    let url: Url = input.parse().ok()?;
+    let url_permit = CLIENT.host_permit(&url).await?;
+
     let permit = CLIENT.semaphore.acquire().await.ok()?;
-    let response = CLIENT.client.get(url).send().await.ok()?;
+    let headers = CLIENT.headers(&url, HeaderMap::new());
+    let response = CLIENT.client.get(url).headers(headers).send().await.ok()?;
 
     let status = response.status();
     let output = if status.is_success() {
-        response.text().await.ok().filter(|text| !text.is_empty())
+        Some(response.headers().clone())
     } else {
-        log::warn!("Failed to capture page: {}, {}", input, status);
+        tracing::warn!("Failed to capture headers: {}, {}", input, status);
         None
     };
 
@@ -32,40 +224,204 @@ pub async fn capture_page(input: &str) -> Option<String> {
     if let Some(url_permit) = url_permit {
         drop(url_permit);
     }
+    if let Some(output) = &output {
+        fixtures::record_headers(&fixture_key, output);
+    }
     output
 }
 
-pub async fn all_valid(urls: Vec<String>) -> bool {
-    let futures = urls.into_iter().map(|input| async move {
-        let url: Url = input.parse().ok()?;
-        let url_permit = match CLIENT.url_permits.get(url.host_str()?) {
-            Some(semaphore) => Some(semaphore.acquire().await.ok()?),
-            None => None,
-        };
-        let permit = CLIENT.semaphore.acquire().await.ok()?;
-
-        let response = CLIENT
-            .client
-            .get(url)
-            .send()
-            .await
-            .inspect_err(|e| {
-                log::error!("Failed to make request to URL {}: {}", input, e);
-            })
-            .ok()?;
-        let status = response.status();
-        let successful = status.is_success() || status == StatusCode::TOO_MANY_REQUESTS;
+#[tracing::instrument(level = "debug", skip_all, fields(url = input))]
+pub async fn post_page(input: &str, headers: HeaderMap, body: String) -> Option<String> {
+    crate::cache::record_opaque_request();
+    let fixture_key = format!("POST {input}\n{body}");
+    if fixtures::replaying() {
+        return fixtures::replay(&fixture_key);
+    }
+    let url: Url = input.parse().ok()?;
+    let url_permit = CLIENT.host_permit(&url).await?;
+
+    let permit = CLIENT.semaphore.acquire().await.ok()?;
+    let headers = CLIENT.headers(&url, headers);
+    let response = CLIENT.client.post(url).headers(headers).body(body).send().await.ok()?;
+
+    let status = response.status();
+    let output = if status.is_success() {
+        read_text(input, response, MAX_PAGE_SIZE).await.filter(|text| !text.is_empty())
+    } else {
+        tracing::warn!("Failed to post to page: {}, {}", input, status);
+        None
+    };
 
-        if !successful {
-            log::warn!("Failed to resolve URL {}: {}", input, status);
+    drop(permit);
+    if let Some(url_permit) = url_permit {
+        drop(url_permit);
+    }
+    if let Some(output) = &output {
+        fixtures::record(&fixture_key, output);
+    }
+    output
+}
+
+/// Returns the file name of the first disk within an OVA appliance. OVAs are tar archives which start with their OVF descriptor,
+/// so only the start of the appliance is downloaded rather than all of it
+#[tracing::instrument(level = "debug", skip_all, fields(url = input))]
+pub async fn ova_disk_name(input: &str) -> Option<String> {
+    crate::cache::record_opaque_request();
+    let fixture_key = format!("OVF {input}");
+    let descriptor = if fixtures::replaying() {
+        fixtures::replay(&fixture_key)?
+    } else {
+        let descriptor = ovf_descriptor(input).await?;
+        fixtures::record(&fixture_key, &descriptor);
+        descriptor
+    };
+    static DISK_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"<(?:ovf:)?File\b[^>]*?\bovf:href="([^"]+\.vmdk)""#).unwrap());
+    DISK_REGEX.captures(&descriptor).map(|c| c[1].to_string())
+}
+
+async fn ovf_descriptor(input: &str) -> Option<String> {
+    const TAR_HEADER_SIZE: usize = 512;
+    const MAX_DESCRIPTOR_SIZE: usize = 64 * 1024;
+    let url: Url = input.parse().ok()?;
+    let url_permit = CLIENT.host_permit(&url).await?;
+
+    let permit = CLIENT.semaphore.acquire().await.ok()?;
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        RANGE,
+        HeaderValue::from_str(&format!("bytes=0-{}", TAR_HEADER_SIZE + MAX_DESCRIPTOR_SIZE - 1)).unwrap(),
+    );
+    let headers = CLIENT.headers(&url, headers);
+    let mut response = CLIENT.client.get(url).headers(headers).send().await.ok()?;
+
+    let status = response.status();
+    let mut body = Vec::new();
+    if status.is_success() {
+        // Servers which ignore the range send the whole appliance, which is cut off once the descriptor has arrived
+        while body.len() < TAR_HEADER_SIZE + MAX_DESCRIPTOR_SIZE {
+            let Some(chunk) = response.chunk().await.ok()? else {
+                break;
+            };
+            body.extend_from_slice(&chunk);
         }
-        drop(permit);
-        if let Some(url_permit) = url_permit {
-            drop(url_permit);
+    } else {
+        tracing::warn!("Failed to capture the OVF descriptor: {}, {}", input, status);
+    }
+    drop(permit);
+    if let Some(url_permit) = url_permit {
+        drop(url_permit);
+    }
+
+    // The size of the first entry is stored as octal within its tar header
+    let header = body.get(..TAR_HEADER_SIZE)?;
+    let name = String::from_utf8_lossy(&header[..100]);
+    if !name.trim_end_matches('\0').ends_with(".ovf") {
+        tracing::warn!("{input}: The appliance doesn't start with an OVF descriptor");
+        return None;
+    }
+    let size = std::str::from_utf8(&header[124..136])
+        .ok()?
+        .trim_matches(|c: char| c == '\0' || c == ' ');
+    let size = usize::from_str_radix(size, 8).ok()?;
+    let descriptor = body.get(TAR_HEADER_SIZE..TAR_HEADER_SIZE + size.min(MAX_DESCRIPTOR_SIZE))?;
+    Some(String::from_utf8_lossy(descriptor).into_owned())
+}
+
+/// Returns each unresolvable URL alongside the reason it failed, an empty list means every URL is valid
+pub async fn url_failures(urls: Vec<String>) -> Vec<(String, String)> {
+    check_urls(urls, false).await
+}
+
+/// Like `url_failures`, but URLs which resolve to something that can't be the file are also failures,
+/// such as download pages which fail softly by serving an HTML error page
+pub async fn url_failures_checking_content(urls: Vec<String>) -> Vec<(String, String)> {
+    check_urls(urls, true).await
+}
+
+async fn check_urls(urls: Vec<String>, check_content: bool) -> Vec<(String, String)> {
+    // Replayed runs have no network access, so every URL is assumed to be valid
+    if fixtures::replaying() {
+        return Vec::new();
+    }
+    let futures = urls.into_iter().map(|input| {
+        let span = tracing::debug_span!("validate", url = input);
+        async move {
+            let url: Url = input.parse().ok()?;
+            let url_permit = CLIENT.host_permit(&url).await?;
+            let permit = CLIENT.semaphore.acquire().await.ok()?;
+
+            // Sources such as macOS recovery images are only served alongside a token
+            let required = SourceMetadata::headers(&input)
+                .into_iter()
+                .filter_map(|(name, value)| Some((HeaderName::try_from(name).ok()?, HeaderValue::try_from(value).ok()?)))
+                .collect();
+            // Only the headers are needed, but some servers refuse HEAD requests or answer them with an error, such as SourceForge.
+            // Those URLs are requested again for their first byte before they're counted as a failure
+            let headers = CLIENT.headers(&url, required);
+            let mut response = CLIENT.client.head(url.clone()).headers(headers.clone()).send().await;
+            if response
+                .as_ref()
+                .is_ok_and(|head| !(head.status().is_success() || head.status() == StatusCode::TOO_MANY_REQUESTS))
+            {
+                let mut headers = headers;
+                headers.insert(RANGE, HeaderValue::from_static("bytes=0-0"));
+                response = CLIENT.client.get(url).headers(headers).send().await;
+            }
+            // DNS, TLS and connection errors mean the URL is just as dead as an error status
+            let response = match response {
+                Ok(response) => response,
+                Err(e) => {
+                    tracing::warn!("Failed to make request to URL {}: {}", input, e);
+                    return Some((input, e.to_string()));
+                }
+            };
+            let status = response.status();
+            let header = |name| response.headers().get(name).and_then(|h: &HeaderValue| h.to_str().ok());
+            let failure = if !(status.is_success() || status == StatusCode::TOO_MANY_REQUESTS) {
+                tracing::warn!("Failed to resolve URL {}: {}", input, status);
+                Some(status.to_string())
+            } else if check_content && header(CONTENT_TYPE).is_some_and(|t| t.starts_with("text/html")) {
+                Some("Served an HTML page".to_string())
+            } else if check_content && header(CONTENT_LENGTH) == Some("0") {
+                Some("Empty response".to_string())
+            } else {
+                // The response is only used for its status, so its headers are recorded here rather than making another request.
+                // A ranged response only covers the first byte, the size of the whole file follows the slash of its Content-Range
+                let partial = status == StatusCode::PARTIAL_CONTENT;
+                let size = match partial {
+                    true => header(CONTENT_RANGE).and_then(|r| r.rsplit_once('/')).map(|(_, size)| size),
+                    false => header(CONTENT_LENGTH),
+                }
+                .and_then(|l| l.parse::<u64>().ok())
+                .filter(|size| *size > 0);
+                // Servers which don't advertise either way may still support ranges, so nothing is recorded for them
+                let resumable = header(ACCEPT_RANGES)
+                    .map(|r| r.split(',').any(|unit| unit.trim().eq_ignore_ascii_case("bytes")))
+                    .or(partial.then_some(true));
+                SourceMetadata::attach(&input, |m| {
+                    m.size = size.or(m.size);
+                    m.resumable = resumable.or(m.resumable);
+                });
+                None
+            };
+            drop(permit);
+            if let Some(url_permit) = url_permit {
+                drop(url_permit);
+            }
+            failure.map(|reason| (input, reason))
         }
-        Some(successful)
+        .instrument(span)
     });
-    join_futures!(futures).into_iter().all(|r| r.unwrap_or(true))
+    join_futures!(futures).into_iter().flatten().collect()
+}
+
+/// Spawns a task within the current span, so anything it logs and any pages it reads are still attributed to the distro being generated
+pub fn spawn<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::spawn(crate::cache::inherit_pages(future.in_current_span()))
 }
 
 pub fn arch_from_str(arch: &str) -> Option<Arch> {
@@ -77,36 +433,394 @@ pub fn arch_from_str(arch: &str) -> Option<Arch> {
     }
 }
 
+// Language names which carry a region or script, and would otherwise lose it by only matching the language
+const LANGUAGE_NAME_TAGS: [(&str, &str); 6] = [
+    ("brazilian portuguese", "pt-BR"),
+    ("chinese simplified", "zh-Hans"),
+    ("chinese traditional", "zh-Hant"),
+    ("english international", "en-GB"),
+    ("french canadian", "fr-CA"),
+    ("spanish mexico", "es-MX"),
+];
+
+/// Normalizes a locale code or language name, e.g. "pt_BR", "deu" or "Brazilian Portuguese", into a BCP 47 language tag such as "pt-BR"
+pub fn language_tag(locale: &str) -> Option<String> {
+    // Punctuation varies between sources, such as "Chinese (Simplified)" and "Chinese Simplified"
+    let name = locale
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase();
+    if let Some((_, tag)) = LANGUAGE_NAME_TAGS.iter().find(|(n, _)| *n == name) {
+        return Some(tag.to_string());
+    }
+    let (language, region) = match locale.split_once(['_', '-']) {
+        Some((language, region)) if region.len() == 2 && region.chars().all(|c| c.is_ascii_alphabetic()) => (language, Some(region.to_uppercase())),
+        _ => (locale, None),
+    };
+    let lowercase = language.to_lowercase();
+    let language = Language::from_639_1(&lowercase)
+        .or_else(|| Language::from_639_3(&lowercase))
+        .or_else(|| Language::from_name(language))
+        // Names are often qualified, such as "English International" or "Chinese Simplified"
+        .or_else(|| language.split_whitespace().find_map(Language::from_name))?;
+    let code = language.to_639_1().unwrap_or(language.to_639_3());
+    Some(match region {
+        Some(region) => format!("{code}-{region}"),
+        None => code.to_string(),
+    })
+}
+
+// Mirrors which rate limit or drop connections when too many requests are made at once
+// ibiblio.org hosts EasyOS, FreeDOS and the Puppy family, which all share its limit
+const DEFAULT_HOST_LIMITS: [(&str, usize); 6] = [
+    ("sourceforge.net", 5),
+    ("downloads.sourceforge.net", 10),
+    ("dl.sourceforge.net", 10),
+    ("ibiblio.org", 5),
+    ("cdimage.debian.org", 10),
+    ("tinycorelinux.net", 4),
+];
+
+const BROWSER_USER_AGENT: &str = "Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0";
+
+// Hosts which respond differently depending on who's asking, subdomains receive their parent domain's headers.
+// SourceForge serves download tools a redirect straight to a mirror, rather than an HTML page counting down to the download.
+// Microsoft's download pages only hand out links to browsers
+const DEFAULT_HOST_HEADERS: [(&str, &[(&str, &str)]); 2] = [
+    ("sourceforge.net", &[("user-agent", "Wget/1.21.4")]),
+    (
+        "microsoft.com",
+        &[("user-agent", BROWSER_USER_AGENT), ("accept-language", "en-US,en;q=0.9")],
+    ),
+];
+
+pub struct ConnectionLimits {
+    pub max_connections: usize,
+    /// Added to, or overriding, the default per-host limits. Subdomains share their parent domain's limit
+    pub host_limits: Vec<(String, usize)>,
+    /// Requests to rate limited hosts are delayed by a random duration up to this
+    pub jitter: Duration,
+}
+
+static CONNECTION_LIMITS: OnceCell<ConnectionLimits> = OnceCell::new();
+
+/// Must be called before any requests are made
+pub fn set_connection_limits(limits: ConnectionLimits) {
+    if CONNECTION_LIMITS.set(limits).is_err() {
+        tracing::warn!("Connection limits were already set");
+    }
+}
+
 struct ReqwestClient {
     client: ClientWithMiddleware,
     semaphore: Semaphore,
-    url_permits: HashMap<&'static str, Semaphore>,
+    url_permits: HashMap<String, Semaphore>,
+    jitter: Duration,
+    health: HostHealth,
+    host_headers: Vec<(String, HeaderMap)>,
+}
+
+/// Whether the host is the domain or one of its subdomains
+fn within_domain(host: &str, domain: &str) -> bool {
+    host == domain || host.strip_suffix(domain).is_some_and(|s| s.ends_with('.'))
+}
+
+impl ReqwestClient {
+    /// Acquires a permit for the URL's host if it's rate limited, returning None if the URL is invalid.
+    /// Requests to a host which is currently paused wait for the pause to end first
+    async fn host_permit(&self, url: &Url) -> Option<Option<SemaphorePermit<'_>>> {
+        let host = url.host_str()?;
+        if let Some(pause) = self.health.pause(host) {
+            tracing::debug!("Waiting {}s for requests to {host} to resume", pause.as_secs());
+            tokio::time::sleep(pause).await;
+        }
+        let semaphore = self
+            .url_permits
+            .iter()
+            .filter(|(limited, _)| within_domain(host, limited))
+            .max_by_key(|(limited, _)| limited.len());
+        let Some((_, semaphore)) = semaphore else {
+            return Some(None);
+        };
+        let permit = semaphore.acquire().await.ok()?;
+        if !self.jitter.is_zero() {
+            tokio::time::sleep(self.jitter.mul_f64(fastrand::f64())).await;
+        }
+        Some(Some(permit))
+    }
+    /// Adds the headers configured for the URL's host to a request's headers. Headers passed by the scraper take precedence
+    fn headers(&self, url: &Url, mut headers: HeaderMap) -> HeaderMap {
+        let Some(host) = url.host_str() else {
+            return headers;
+        };
+        let host_headers = self
+            .host_headers
+            .iter()
+            .filter(|(domain, _)| within_domain(host, domain))
+            .max_by_key(|(domain, _)| domain.len());
+        if let Some((_, host_headers)) = host_headers {
+            for (name, value) in host_headers {
+                if !headers.contains_key(name) {
+                    headers.insert(name, value.clone());
+                }
+            }
+        }
+        headers
+    }
+}
+
+/// Consecutive failed responses from a host after which requests to it are paused
+const HOST_FAILURE_THRESHOLD: u32 = 3;
+const HOST_PAUSE: Duration = Duration::from_secs(10);
+const MAX_HOST_PAUSE: Duration = Duration::from_secs(120);
+
+/// Tracks hosts which are failing or rate limiting requests during a run. Mirrors such as SourceForge and ibiblio are shared by many distros,
+/// so every scraper backs off from a struggling host together, rather than each continuing until its own retries run out
+#[derive(Default)]
+struct HostHealth {
+    hosts: Mutex<HashMap<String, HostState>>,
+}
+
+#[derive(Default)]
+struct HostState {
+    /// Failed responses since the host last responded successfully
+    failures: u32,
+    /// Number of times the host has been paused, each pause lasts twice as long as the previous one
+    pauses: u32,
+    paused_until: Option<Instant>,
+}
+
+impl HostHealth {
+    /// Records the outcome of a response. Only rate limiting, server errors and failed connections count against a host,
+    /// a missing page says nothing about its health
+    fn record(&self, host: &str, healthy: bool, retry_after: Option<Duration>) {
+        let mut hosts = self.hosts.lock().unwrap();
+        let state = hosts.entry(host.to_string()).or_default();
+        if healthy {
+            state.failures = 0;
+            return;
+        }
+        state.failures += 1;
+        let now = Instant::now();
+        if state.failures < HOST_FAILURE_THRESHOLD || state.paused_until.is_some_and(|until| until > now) {
+            return;
+        }
+        let pause = HOST_PAUSE
+            .saturating_mul(1 << state.pauses.min(4))
+            .max(retry_after.unwrap_or_default())
+            .min(MAX_HOST_PAUSE);
+        state.failures = 0;
+        state.pauses += 1;
+        state.paused_until = Some(now + pause);
+        tracing::warn!(
+            "{host} is failing or rate limiting requests, pausing requests to it for {}s",
+            pause.as_secs()
+        );
+    }
+    /// Time remaining until requests to the host resume, if it's paused
+    fn pause(&self, host: &str) -> Option<Duration> {
+        let hosts = self.hosts.lock().unwrap();
+        let until = hosts.get(host)?.paused_until?;
+        until.checked_duration_since(Instant::now())
+    }
+}
+
+/// Retries the same responses as reqwest_retry's default strategy, counting every attempt towards the run's metrics and its host's health
+struct MeasuredRetryStrategy;
+impl RetryableStrategy for MeasuredRetryStrategy {
+    fn handle(&self, res: &Result<reqwest::Response, reqwest_middleware::Error>) -> Option<Retryable> {
+        let retryable = DefaultRetryableStrategy.handle(res);
+        let (host, failed) = match res {
+            Ok(response) => (
+                response.url().host_str(),
+                response.status().is_client_error() || response.status().is_server_error(),
+            ),
+            Err(reqwest_middleware::Error::Reqwest(e)) => (e.url().and_then(Url::host_str), true),
+            Err(_) => (None, true),
+        };
+        let retried = matches!(retryable, Some(Retryable::Transient));
+        crate::metrics::record_response(host.unwrap_or("unknown"), failed, retried);
+        if let Some(host) = host {
+            let (healthy, retry_after) = match res {
+                Ok(response) => {
+                    let status = response.status();
+                    let retry_after = response
+                        .headers()
+                        .get(RETRY_AFTER)
+                        .and_then(|h| h.to_str().ok()?.parse::<u64>().ok())
+                        .map(Duration::from_secs);
+                    (
+                        status != StatusCode::TOO_MANY_REQUESTS && !status.is_server_error(),
+                        retry_after,
+                    )
+                }
+                Err(_) => (false, None),
+            };
+            CLIENT.health.record(host, healthy, retry_after);
+        }
+        retryable
+    }
 }
 
 static CLIENT: Lazy<ReqwestClient> = Lazy::new(|| {
     let retries = ExponentialBackoff::builder().build_with_max_retries(3);
     let client = reqwest::ClientBuilder::new().user_agent("quickemu-rs/1.0").build().unwrap();
     let client = ClientBuilder::new(client)
-        .with(RetryTransientMiddleware::new_with_policy(retries))
+        .with(RetryTransientMiddleware::new_with_policy_and_strategy(
+            retries,
+            MeasuredRetryStrategy,
+        ))
         .build();
-    let semaphore = Semaphore::new(150);
-    let url_permits = HashMap::from([("sourceforge.net", Semaphore::new(5))]);
-    ReqwestClient { client, semaphore, url_permits }
+    let limits = CONNECTION_LIMITS.get_or_init(|| ConnectionLimits {
+        max_connections: 150,
+        host_limits: Vec::new(),
+        jitter: Duration::ZERO,
+    });
+    let semaphore = Semaphore::new(limits.max_connections);
+    let url_permits = DEFAULT_HOST_LIMITS
+        .iter()
+        .map(|(host, limit)| (host.to_string(), *limit))
+        .chain(limits.host_limits.iter().cloned())
+        .map(|(host, limit)| (host, Semaphore::new(limit)))
+        .collect();
+    let host_headers = DEFAULT_HOST_HEADERS
+        .iter()
+        .map(|(domain, headers)| {
+            let headers = headers
+                .iter()
+                .map(|(name, value)| (HeaderName::from_static(name), HeaderValue::from_static(value)))
+                .collect();
+            (domain.to_string(), headers)
+        })
+        .collect();
+    ReqwestClient {
+        client,
+        semaphore,
+        url_permits,
+        jitter: limits.jitter,
+        health: HostHealth::default(),
+        host_headers,
+    }
 });
 
+#[allow(async_fn_in_trait)]
 pub trait GatherData {
     type Output;
     async fn gather_data(url: &str) -> Option<Self::Output>;
 }
 
+// Scrapers only use the newest releases, so there's no need to exhaust the unauthenticated rate limit of 60 requests per hour
+const GITHUB_MAX_PAGES: usize = 3;
+static GITHUB_TOKEN: Lazy<Option<String>> = Lazy::new(|| std::env::var("GITHUB_TOKEN").ok().filter(|t| !t.is_empty()));
+
 pub struct GithubAPI;
 impl GatherData for GithubAPI {
     type Output = Vec<GithubAPIValue>;
     async fn gather_data(url: &str) -> Option<Self::Output> {
-        let data = capture_page(url).await?;
-        serde_json::from_str(&data).ok()
+        let mut releases = Vec::new();
+        let mut next = Some(url.to_string());
+        for _ in 0..GITHUB_MAX_PAGES {
+            let Some(url) = next.take() else { break };
+            let page = capture_github_page(&url).await.and_then(|(data, next_page)| {
+                let page: Vec<GithubAPIValue> = serde_json::from_str(&data)
+                    .inspect_err(|e| tracing::error!("Unable to parse GitHub API response from {url}: {e}"))
+                    .ok()?;
+                Some((page, next_page))
+            });
+            let Some((page, next_page)) = page else {
+                // Later pages only hold older releases, so those already gathered are still worth using
+                if !releases.is_empty() {
+                    tracing::error!(
+                        "Unable to fetch {url}, continuing with the {} releases gathered so far",
+                        releases.len()
+                    );
+                    break;
+                }
+                return None;
+            };
+            releases.extend(page);
+            next = next_page;
+        }
+        Some(releases)
     }
 }
+
+/// Fetches a page of a GitHub API listing, returning its body alongside the URL of the next page
+#[tracing::instrument(level = "debug", skip_all, fields(url = input))]
+async fn capture_github_page(input: &str) -> Option<(String, Option<String>)> {
+    crate::cache::record_opaque_request();
+    let link_key = format!("LINK {input}");
+    if fixtures::replaying() {
+        return Some((fixtures::replay(input)?, fixtures::replay(&link_key)));
+    }
+    let url: Url = input.parse().ok()?;
+    let url_permit = CLIENT.host_permit(&url).await?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(ACCEPT, HeaderValue::from_static("application/vnd.github+json"));
+    if let Some(token) = GITHUB_TOKEN
+        .as_deref()
+        .and_then(|t| HeaderValue::from_str(&format!("Bearer {t}")).ok())
+    {
+        headers.insert(AUTHORIZATION, token);
+    }
+
+    let permit = CLIENT.semaphore.acquire().await.ok()?;
+    let headers = CLIENT.headers(&url, headers);
+    let response = CLIENT
+        .client
+        .get(url)
+        .headers(headers)
+        .send()
+        .await
+        .inspect_err(|e| tracing::error!("Failed to make request to GitHub API {input}: {e}"))
+        .ok()?;
+
+    let status = response.status();
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|h: &HeaderValue| h.to_str().ok())
+            .map(ToString::to_string)
+    };
+    let output = if status.is_success() {
+        let next = header("link").as_deref().and_then(next_link);
+        // Release notes are included in full, so repositories with many releases have large responses
+        read_text(input, response, MAX_LARGE_PAGE_SIZE).await.map(|body| (body, next))
+    } else if header("x-ratelimit-remaining").as_deref() == Some("0") {
+        let reset = header("x-ratelimit-reset").unwrap_or_else(|| "unknown".to_string());
+        let hint = if GITHUB_TOKEN.is_none() { ", set GITHUB_TOKEN to raise the limit" } else { "" };
+        tracing::error!("GitHub API rate limit exceeded fetching {input}, it resets at UNIX time {reset}{hint}");
+        None
+    } else {
+        tracing::error!("GitHub API request {input} failed: {status}");
+        None
+    };
+
+    drop(permit);
+    if let Some(url_permit) = url_permit {
+        drop(url_permit);
+    }
+    if let Some((body, next)) = &output {
+        fixtures::record(input, body);
+        if let Some(next) = next {
+            fixtures::record(&link_key, next);
+        }
+    }
+    output
+}
+
+/// Finds the next page in a Link header, formatted as `<https://api.github.com/...?page=2>; rel="next", <...>; rel="last"`
+fn next_link(header: &str) -> Option<String> {
+    header.split(',').find_map(|link| {
+        let (url, rel) = link.split_once(';')?;
+        rel.contains(r#"rel="next""#)
+            .then(|| url.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+    })
+}
 #[derive(Deserialize)]
 pub struct GithubAPIValue {
     pub tag_name: String,
@@ -120,6 +834,125 @@ pub struct GithubAsset {
     pub browser_download_url: String,
 }
 
+const SOURCEFORGE_FEED_LIMIT: usize = 100;
+// The file feed can't be paged through, so a full feed is requested again with a larger limit, until the files run out or this many attempts
+const SOURCEFORGE_MAX_PAGES: u32 = 3;
+
+/// SourceForge's file feed, which lists every file below a directory of a project, newest first
+pub struct SourceForgeAPI;
+impl SourceForgeAPI {
+    /// URL of the feed listing the files below `path`, such as `Final/` of the `antix-linux` project
+    pub fn files_url(project: &str, path: &str) -> String {
+        format!(
+            "https://sourceforge.net/projects/{project}/rss?path=/{}",
+            path.trim_matches('/')
+        )
+    }
+    /// The file SourceForge offers as a project's main download
+    pub async fn best_release(project: &str) -> Option<SourceForgeFile> {
+        let url = format!("https://sourceforge.net/projects/{project}/best_release.json");
+        let data = capture_page(&url).await?;
+        let best_release: SourceForgeBestRelease = serde_json::from_str(&data)
+            .inspect_err(|e| tracing::error!("Unable to parse SourceForge best release {url}: {e}"))
+            .ok()?;
+        let release = best_release.release;
+        Some(SourceForgeFile {
+            download_url: format!("https://sourceforge.net/projects/{project}/files{}/download", release.filename),
+            path: release.filename,
+            md5: release.md5sum.filter(|md5| !md5.is_empty()),
+        })
+    }
+}
+impl GatherData for SourceForgeAPI {
+    type Output = Vec<SourceForgeFile>;
+    async fn gather_data(url: &str) -> Option<Self::Output> {
+        let mut limit = SOURCEFORGE_FEED_LIMIT;
+        for page in 1..=SOURCEFORGE_MAX_PAGES {
+            let feed_url = format!("{url}&limit={limit}");
+            let data = capture_page(&feed_url).await?;
+            let feed: SourceForgeFeed = quick_xml::de::from_str(&data)
+                .inspect_err(|e| tracing::error!("Unable to parse SourceForge file feed {feed_url}: {e}"))
+                .ok()?;
+            if feed.channel.items.len() < limit || page == SOURCEFORGE_MAX_PAGES {
+                return Some(feed.channel.items.into_iter().map(SourceForgeFile::from).collect());
+            }
+            limit *= 4;
+        }
+        None
+    }
+}
+
+pub struct SourceForgeFile {
+    /// Path of the file within the project, such as `/Final/antiX-23.1/antiX-23.1_x64-full.iso`
+    pub path: String,
+    pub download_url: String,
+    pub md5: Option<String>,
+}
+impl SourceForgeFile {
+    pub fn name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or_default()
+    }
+    /// Path of the directory containing the file, without a trailing slash
+    pub fn directory(&self) -> &str {
+        self.path.rsplit_once('/').map(|(directory, _)| directory).unwrap_or_default()
+    }
+    /// The file beside this one whose name has `suffix` appended, such as its checksum file
+    pub fn sibling<'a>(&self, files: &'a [SourceForgeFile], suffix: &str) -> Option<&'a SourceForgeFile> {
+        files.iter().find(|f| f.path.strip_suffix(suffix) == Some(&self.path))
+    }
+}
+impl From<SourceForgeItem> for SourceForgeFile {
+    fn from(item: SourceForgeItem) -> Self {
+        let md5 = item
+            .content
+            .and_then(|c| c.hashes.into_iter().find(|h| h.algorithm == "md5"))
+            .map(|h| h.value);
+        Self {
+            path: item.title,
+            download_url: item.link,
+            md5,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SourceForgeFeed {
+    channel: SourceForgeChannel,
+}
+#[derive(Deserialize)]
+struct SourceForgeChannel {
+    #[serde(rename = "item", default)]
+    items: Vec<SourceForgeItem>,
+}
+#[derive(Deserialize)]
+struct SourceForgeItem {
+    title: String,
+    link: String,
+    // `media:content`, the namespace prefix is dropped during deserialization
+    content: Option<SourceForgeContent>,
+}
+#[derive(Deserialize)]
+struct SourceForgeContent {
+    #[serde(rename = "hash", default)]
+    hashes: Vec<SourceForgeHash>,
+}
+#[derive(Deserialize)]
+struct SourceForgeHash {
+    #[serde(rename = "@algo")]
+    algorithm: String,
+    #[serde(rename = "$text")]
+    value: String,
+}
+#[derive(Deserialize)]
+struct SourceForgeBestRelease {
+    release: SourceForgeBestFile,
+}
+#[derive(Deserialize)]
+struct SourceForgeBestFile {
+    filename: String,
+    md5sum: Option<String>,
+}
+
 impl GatherData for FedoraRelease {
     type Output = Vec<FedoraRelease>;
     async fn gather_data(url: &str) -> Option<Self::Output> {
@@ -143,12 +976,58 @@ pub struct FedoraRelease {
 
 #[macro_export]
 macro_rules! spawn_distros {
-    ($( $distro:ty ),* $(,)? ) => {{
+    ($args:expr; $( $distro:ty ),* $(,)? ) => {{
         let mut handles = Vec::new();
         $(
-            let handle = spawn(<$distro>::to_os());
-            handles.push(handle);
+            if $args.should_generate(<$distro as $crate::store_data::Distro>::NAME) {
+                $crate::progress::start(<$distro as $crate::store_data::Distro>::NAME);
+                let span = ::tracing::info_span!("distro", distro = <$distro as $crate::store_data::Distro>::NAME);
+                let handle = spawn(::tracing::Instrument::instrument(<$distro>::candidates(), span));
+                handles.push(spawn($crate::store_data::supervise::<$distro>(handle, $args.timeout)));
+            }
         )*
         handles
     }};
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    /// Serves a file of 1024 bytes, but answers HEAD requests with 403 like SourceForge's download links
+    async fn refuse_head() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = vec![0; 4096];
+                let length = stream.read(&mut request).await.unwrap_or_default();
+                let request = String::from_utf8_lossy(&request[..length]).to_lowercase();
+                let response = if request.starts_with("head") {
+                    "HTTP/1.1 403 Forbidden\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                } else if request.contains("range: bytes=0-0") {
+                    "HTTP/1.1 206 Partial Content\r\ncontent-range: bytes 0-0/1024\r\ncontent-length: 1\r\nconnection: close\r\n\r\n0"
+                } else {
+                    "HTTP/1.1 500 Internal Server Error\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                };
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{address}/image.iso")
+    }
+
+    #[tokio::test]
+    async fn check_urls_falls_back_to_ranged_get() {
+        let url = refuse_head().await;
+        assert!(url_failures(vec![url.clone()]).await.is_empty());
+        // The size is taken from the Content-Range of the ranged response, rather than its length of a single byte
+        let (mut size, mut resumable) = (None, None);
+        SourceMetadata::attach(&url, |m| (size, resumable) = (m.size, m.resumable));
+        assert_eq!(size, Some(1024));
+        assert_eq!(resumable, Some(true));
+    }
+}
@@ -0,0 +1,58 @@
+use crate::diff;
+use quickget_ci::utils::{url_failures, url_failures_checking_content};
+use serde::Serialize;
+use std::{collections::BTreeMap, path::Path};
+
+#[derive(Serialize)]
+struct DeadLink {
+    url: String,
+    reason: String,
+    /// Every release which references the URL, formatted as "os release edition arch"
+    releases: Vec<String>,
+}
+
+/// Checks every web source in a published dataset, writing the unresolvable ones to `report`.
+/// Returns false if the dataset couldn't be loaded or any link is dead.
+pub async fn run(data: &Path, report: &Path, check_length: bool) -> bool {
+    let Some(distros) = diff::load(data) else {
+        return false;
+    };
+    // Editions and architectures often share a file, each URL only needs to be checked once
+    let mut urls: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (os, releases) in distros {
+        for (release, sources) in releases {
+            for url in sources.into_keys() {
+                urls.entry(url).or_default().push(format!("{os} {release}"));
+            }
+        }
+    }
+    tracing::info!("Validating {} URLs", urls.len());
+
+    let urls_to_check = urls.keys().cloned().collect();
+    // The content type and length are read from the same response which resolved the URL
+    let failures = if check_length {
+        url_failures_checking_content(urls_to_check).await
+    } else {
+        url_failures(urls_to_check).await
+    };
+
+    let dead_links = failures
+        .into_iter()
+        .map(|(url, reason)| {
+            let releases = urls.remove(&url).unwrap_or_default();
+            println!("{url}: {reason} ({})", releases.join(", "));
+            DeadLink { url, reason, releases }
+        })
+        .collect::<Vec<_>>();
+    println!("{} dead links found", dead_links.len());
+
+    match serde_json::to_string_pretty(&dead_links) {
+        Ok(output) => {
+            if let Err(e) = std::fs::write(report, output) {
+                tracing::error!("Failed to write {}: {e}", report.display());
+            }
+        }
+        Err(e) => tracing::error!("Failed to serialize validation report: {e}"),
+    }
+    dead_links.is_empty()
+}
@@ -0,0 +1,232 @@
+use crate::{
+    checksums::signed_checksums,
+    store_data::{Signature, SignatureScheme},
+    utils::capture_page,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ed25519_dalek::{Verifier, VerifyingKey};
+use std::{
+    path::Path,
+    process::{Command, Stdio},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Public keys embedded from `keys/` by the build script, keyed by file name. PGP keys are named after their fingerprint
+static KEYS: &[(&str, &str)] = include!(concat!(env!("OUT_DIR"), "/keys.rs"));
+
+/// Set when a signature couldn't be checked because a key or tool is missing, rather than because of the mirror
+static UNAVAILABLE: AtomicBool = AtomicBool::new(false);
+
+/// Whether any signature went unchecked because a key wasn't embedded or gpgv isn't installed.
+/// Runs which must verify every signature fail when this is set
+pub fn verification_unavailable() -> bool {
+    UNAVAILABLE.load(Ordering::Relaxed)
+}
+
+fn unavailable(reason: String) -> Outcome {
+    tracing::error!("{reason}");
+    UNAVAILABLE.store(true, Ordering::Relaxed);
+    Outcome::Unchecked
+}
+
+pub(crate) enum Outcome {
+    /// Contents of the checksum file, whose signature is valid
+    Verified(String),
+    /// The signature doesn't match the checksum file, or wasn't made by the expected key
+    Failed,
+    /// Verification couldn't be attempted, such as when gpgv isn't installed or a file couldn't be downloaded
+    Unchecked,
+}
+
+/// Verifies the signature of a checksum file against its embedded key
+pub(crate) async fn verify_checksum_file(signature: &Signature) -> Outcome {
+    let Some(checksum_file_url) = &signature.checksum_file else {
+        return Outcome::Unchecked;
+    };
+    let key_name = match signature.scheme {
+        SignatureScheme::Pgp => format!("{}.asc", signature.key_fingerprint.to_uppercase()),
+        SignatureScheme::Signify => format!("{}.pub", signature.key_fingerprint),
+    };
+    let Some(key) = KEYS.iter().find(|(name, _)| *name == key_name).map(|(_, key)| key.to_string()) else {
+        return unavailable(format!("Key {key_name} isn't embedded, {checksum_file_url} can't be verified"));
+    };
+    // Clearsigned files and signify's SHA256.sig carry the signature alongside the checksums they cover
+    let embedded = signature.url == *checksum_file_url;
+    let (Some(signature_file), Some(checksum_file)) = (
+        capture_page(&signature.url).await,
+        match embedded {
+            true => Some(String::new()),
+            false => capture_page(checksum_file_url).await,
+        },
+    ) else {
+        tracing::warn!("Unable to download everything needed to verify {checksum_file_url}");
+        return Outcome::Unchecked;
+    };
+
+    let verified = match signature.scheme {
+        SignatureScheme::Signify => signify(&key, &signature_file).map_err(Some),
+        SignatureScheme::Pgp => {
+            // Pages are read as text, so binary signatures can't be passed on intact
+            if !signature_file.contains("-----BEGIN PGP SIGNATURE-----") {
+                tracing::debug!(
+                    "Signature {} isn't armored, {checksum_file_url} can't be verified",
+                    signature.url
+                );
+                return Outcome::Unchecked;
+            }
+            let fingerprint = signature.key_fingerprint.clone();
+            tokio::task::spawn_blocking(move || {
+                let directory = std::env::temp_dir().join(format!("quickget-verify-{}", uuid::Uuid::new_v4()));
+                let result = gpgv(
+                    &directory,
+                    &key,
+                    &signature_file,
+                    (!embedded).then_some(&*checksum_file),
+                    &fingerprint,
+                );
+                if let Err(e) = std::fs::remove_dir_all(&directory) {
+                    tracing::debug!("Unable to remove {}: {e}", directory.display());
+                }
+                result
+            })
+            .await
+            .map_err(|e| Some(e.to_string()))
+            .and_then(|result| result)
+        }
+    };
+
+    match verified {
+        Ok(Some(contents)) => Outcome::Verified(contents),
+        Ok(None) => {
+            tracing::error!(
+                "Signature {} of {checksum_file_url} wasn't made by {}, or doesn't match",
+                signature.url,
+                signature.key_fingerprint
+            );
+            Outcome::Failed
+        }
+        Err(Some(e)) => {
+            tracing::warn!("Unable to verify {checksum_file_url}: {e}");
+            Outcome::Unchecked
+        }
+        Err(None) => Outcome::Unchecked,
+    }
+}
+
+/// Whether the verified checksum file lists the checksum for the source at this URL. Entries are matched by their path,
+/// relative to the checksum file, rather than by searching for the checksum anywhere within it
+pub(crate) fn lists_checksum(signature: &Signature, contents: &str, url: &str, checksum: &str) -> bool {
+    let Some(format) = signature.checksum_format else {
+        return false;
+    };
+    signed_checksums(format, contents).into_iter().any(|(file, listed)| {
+        let file = file.trim_start_matches("./");
+        !file.is_empty() && url.ends_with(&format!("/{file}")) && listed.eq_ignore_ascii_case(checksum)
+    })
+}
+
+/// Verifies a file signed by signify with an embedded signature, returning the message it covers.
+/// Both the key and the signature are preceded by an untrusted comment, and start with the algorithm and key number
+fn signify(key: &str, signed: &str) -> Result<Option<String>, String> {
+    let decode = |contents: &str| {
+        let line = contents.lines().nth(1).ok_or("Missing signify data")?;
+        STANDARD.decode(line.trim()).map_err(|e| e.to_string())
+    };
+    let key = decode(key)?;
+    let signature = decode(signed)?;
+    let (Ok(public_key), Ok(signature_bytes)) = (
+        <[u8; 32]>::try_from(key.get(10..).unwrap_or_default()),
+        <[u8; 64]>::try_from(signature.get(10..).unwrap_or_default()),
+    ) else {
+        return Err("Malformed signify key or signature".to_string());
+    };
+    if key[..2] != *b"Ed" || signature[..2] != *b"Ed" {
+        return Err("Unsupported signify algorithm".to_string());
+    }
+    // The key number identifies which key made the signature, a mismatch means it was signed with another key
+    if key[2..10] != signature[2..10] {
+        return Ok(None);
+    }
+    let message = signed.splitn(3, '\n').nth(2).unwrap_or_default();
+    let public_key = VerifyingKey::from_bytes(&public_key).map_err(|e| e.to_string())?;
+    let signature = ed25519_dalek::Signature::from_bytes(&signature_bytes);
+    Ok(public_key
+        .verify(message.as_bytes(), &signature)
+        .is_ok()
+        .then(|| message.to_string()))
+}
+
+/// Returns the signed data if the signature is valid and was made by the key with the given fingerprint, or one of its subkeys.
+/// Without separate data, the signature is expected to be clearsigned. The error is empty when gpg isn't installed, which has already been reported
+fn gpgv(directory: &Path, key: &str, signature: &str, data: Option<&str>, fingerprint: &str) -> Result<Option<String>, Option<String>> {
+    std::fs::create_dir_all(directory).map_err(|e| Some(e.to_string()))?;
+    let write = |name: &str, contents: &str| std::fs::write(directory.join(name), contents).map_err(|e| Some(e.to_string()));
+    write("key.asc", key)?;
+    write("data.sig", signature)?;
+    if let Some(data) = data {
+        write("data", data)?;
+    }
+
+    // gpgv only reads binary keyrings
+    let dearmor = Command::new("gpg")
+        .args(["--batch", "--yes", "--dearmor", "--output"])
+        .arg(directory.join("keyring.gpg"))
+        .arg(directory.join("key.asc"))
+        .stdin(Stdio::null())
+        .output();
+    let dearmor = match dearmor {
+        Ok(output) => output,
+        Err(e) => {
+            unavailable(format!("Unable to run gpg, signatures can't be verified: {e}"));
+            return Err(None);
+        }
+    };
+    if !dearmor.status.success() {
+        return Err(Some(format!(
+            "Unable to read key {fingerprint}: {}",
+            String::from_utf8_lossy(&dearmor.stderr).trim()
+        )));
+    }
+    let mut command = Command::new("gpgv");
+    command
+        .args(["--status-fd", "1", "--output"])
+        .arg(directory.join("data.out"))
+        .arg("--keyring")
+        .arg(directory.join("keyring.gpg"))
+        .arg(directory.join("data.sig"));
+    if data.is_some() {
+        command.arg(directory.join("data"));
+    }
+    let output = match command.stdin(Stdio::null()).output() {
+        Ok(output) => output,
+        Err(e) => {
+            unavailable(format!("Unable to run gpgv, signatures can't be verified: {e}"));
+            return Err(None);
+        }
+    };
+
+    // VALIDSIG lists the fingerprint of the signing key, followed by that of its primary key last
+    let status = String::from_utf8_lossy(&output.stdout);
+    let valid = status
+        .lines()
+        .filter_map(|line| line.strip_prefix("[GNUPG:] VALIDSIG "))
+        .any(|line| {
+            let mut fields = line.split_whitespace();
+            let signing_key = fields.next();
+            let primary_key = fields.last();
+            [signing_key, primary_key]
+                .into_iter()
+                .flatten()
+                .any(|f| f.eq_ignore_ascii_case(fingerprint))
+        });
+    if !(output.status.success() && valid) {
+        return Ok(None);
+    }
+    match data {
+        Some(data) => Ok(Some(data.to_string())),
+        // Only the signed portion of a clearsigned file is trusted, not any text surrounding it
+        None => std::fs::read_to_string(directory.join("data.out"))
+            .map(Some)
+            .map_err(|e| Some(e.to_string())),
+    }
+}
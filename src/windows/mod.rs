@@ -0,0 +1,148 @@
+use crate::{
+    error::GenerateError,
+    fixtures,
+    store_data::{Arch, AuxiliaryMedia, Config, ConfigMetadata, Distro, Source, SourceMetadata, WebSource},
+    utils::{capture_page, capture_page_with_headers, language_tag},
+};
+use join_futures::join_futures;
+use quickemu::config::GuestOS;
+use regex::Regex;
+use reqwest::{
+    header::{HeaderMap, HeaderValue, REFERER},
+    Url,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+const WINDOWS_DOWNLOAD_PAGE: &str = "https://www.microsoft.com/en-us/software-download/";
+const WINDOWS_SESSION_URL: &str = "https://vlscppe.microsoft.com/tags?org_id=y6jn8c31&session_id=";
+const WINDOWS_API_URL: &str = "https://www.microsoft.com/en-US/api/controls/contentinclude/html";
+const WINDOWS_SKU_PAGE_ID: &str = "a8f8f489-4c7f-463a-9ca6-5cff94d8d041";
+const WINDOWS_LINKS_PAGE_ID: &str = "6e2a1789-ef16-4f27-a296-74ef7ef5d96b";
+
+pub struct Windows;
+impl Distro for Windows {
+    const NAME: &'static str = "windows";
+    const PRETTY_NAME: &'static str = "Windows";
+    const HOMEPAGE: Option<&'static str> = Some("https://www.microsoft.com/windows/");
+    const DESCRIPTION: Option<&'static str> = Some("Whether you’re gaming, studying, running a business, or running a household, Windows helps you get it done.");
+    const AUXILIARY_MEDIA: &'static [AuxiliaryMedia] = &[AuxiliaryMedia::VirtioWin];
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        // Products are named like "Windows 11 (multi-edition ISO for x64 devices)", the edition being the part before "ISO"
+        let product_regex = Arc::new(Regex::new(r#"<option value="(\d+)">Windows [^<(]*\(([^<)]+?) ISO"#).unwrap());
+        let sku_regex = Arc::new(Regex::new(r#"<option value="(\{[^"]+\})">"#).unwrap());
+        let link_regex = Arc::new(Regex::new(r#"href="(https://software\.download\.prss\.microsoft\.com/[^"]+)""#).unwrap());
+
+        let futures = [("11", "windows11", Arch::x86_64), ("11", "windows11arm64", Arch::aarch64), ("10", "windows10ISO", Arch::x86_64)]
+            .into_iter()
+            .map(|(release, segment, arch)| {
+                let product_regex = product_regex.clone();
+                let sku_regex = sku_regex.clone();
+                let link_regex = link_regex.clone();
+                async move {
+                    let download_page = format!("{WINDOWS_DOWNLOAD_PAGE}{segment}");
+                    let page = capture_page(&download_page).await?;
+                    let product = product_regex.captures(&page)?;
+                    let (product_edition_id, edition) = (&product[1], product[2].to_string());
+
+                    // Download links are only handed out to sessions which have been registered with Microsoft
+                    let session_id = fixtures::recorded_value(&format!("windows session {segment}"), || uuid::Uuid::new_v4().to_string());
+                    capture_page(&format!("{WINDOWS_SESSION_URL}{session_id}")).await?;
+
+                    let segments = format!("software-download,{segment}");
+                    let sku_url = Url::parse_with_params(
+                        WINDOWS_API_URL,
+                        [
+                            ("pageId", WINDOWS_SKU_PAGE_ID),
+                            ("host", "www.microsoft.com"),
+                            ("segments", &segments),
+                            ("query", ""),
+                            ("action", "getskuinformationbyproductedition"),
+                            ("sessionId", &session_id),
+                            ("productEditionId", product_edition_id),
+                            ("sdVersion", "2"),
+                        ],
+                    )
+                    .ok()?;
+                    let sku_page = capture_page(sku_url.as_str()).await?;
+                    let skus = sku_regex
+                        .captures_iter(&sku_page)
+                        .filter_map(|c| serde_json::from_str::<WindowsSku>(&c[1].replace("&quot;", "\"")).ok())
+                        .collect::<Vec<_>>();
+
+                    let futures = skus.into_iter().map(|sku| {
+                        let link_url = Url::parse_with_params(
+                            WINDOWS_API_URL,
+                            [
+                                ("pageId", WINDOWS_LINKS_PAGE_ID),
+                                ("host", "www.microsoft.com"),
+                                ("segments", &segments),
+                                ("query", ""),
+                                ("action", "GetProductDownloadLinksBySku"),
+                                ("sessionId", &session_id),
+                                ("skuId", &sku.id),
+                                ("language", &sku.language),
+                                ("sdVersion", "2"),
+                            ],
+                        );
+                        let download_page = download_page.clone();
+                        let link_regex = link_regex.clone();
+                        let arch = arch.clone();
+                        let edition = edition.clone();
+                        async move {
+                            let mut headers = HeaderMap::new();
+                            headers.insert(REFERER, HeaderValue::from_str(&download_page).ok()?);
+                            let page = capture_page_with_headers(link_url.ok()?.as_str(), headers).await?;
+                            // Pages offer every architecture the product is built for, such as Windows 10's 32-bit and 64-bit ISOs
+                            let marker = match arch {
+                                Arch::x86_64 => "x64",
+                                Arch::aarch64 => "arm64",
+                                _ => return None,
+                            };
+                            let url = link_regex
+                                .captures_iter(&page)
+                                .map(|c| c[1].replace("&amp;", "&"))
+                                .find(|url| {
+                                    let path = url.split('?').next().unwrap_or_default();
+                                    path.rsplit('/').next().unwrap_or_default().to_lowercase().contains(marker)
+                                })?;
+
+                            // Links are generated per session, and expire after 24 hours
+                            let expires = url
+                                .split(['?', '&'])
+                                .find_map(|p| p.strip_prefix("e="))
+                                .and_then(|e| e.parse::<u64>().ok());
+                            SourceMetadata::attach(&url, |m| m.expires = expires);
+                            match language_tag(&sku.language) {
+                                Some(language) => ConfigMetadata::attach(&url, |m| m.language = Some(language)),
+                                None => tracing::warn!("Windows: Unable to determine the language of {}", sku.language),
+                            }
+                            let file_name = url
+                                .split('?')
+                                .next()
+                                .and_then(|path| path.rsplit('/').next())
+                                .map(ToString::to_string);
+
+                            Some(Config {
+                                release: release.to_string(),
+                                edition: Some(edition),
+                                guest_os: GuestOS::Windows,
+                                arch,
+                                iso: Some(vec![Source::Web(WebSource::new(url, None, None, file_name))]),
+                                ..Default::default()
+                            })
+                        }
+                    });
+                    Some(join_futures!(futures, 1))
+                }
+            });
+
+        Ok(join_futures!(futures, 2))
+    }
+}
+
+#[derive(Deserialize)]
+struct WindowsSku {
+    id: String,
+    language: String,
+}
@@ -0,0 +1,165 @@
+//! Replays recorded mirror responses through scrapers, failing when the configs they generate drift from the golden files.
+//! Fixtures live in tests/fixtures/<distro>/, each file holding the key it was requested by (usually its URL) on the first line,
+//! followed by the response. Goldens in tests/golden/<distro>.json summarise the generated configs.
+//! Set UPDATE_GOLDEN=1 to rewrite the goldens after an intentional change, and review the diff.
+
+use quickget_ci::{
+    bsd::FreeBSD,
+    error::GenerateError,
+    fixtures,
+    linux::{Antix, ArcoLinux, CachyOS, Debian, Devuan, EasyOS, EndlessOS, NixOS, PhotonOS},
+    store_data::{self, AuxiliaryMedia, Config, Distro, Source, ToOS, ValidationPolicy, WebSource},
+};
+use serde_json::{json, Value};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Once,
+    time::Duration,
+};
+
+static IMPORT_FIXTURES: Once = Once::new();
+
+/// Every scraper's fixtures are replayed from a single directory, since the fixture mode can only be set once per process
+fn replay_fixtures() {
+    IMPORT_FIXTURES.call_once(|| {
+        let directory = Path::new(env!("CARGO_TARGET_TMPDIR")).join("golden_fixtures");
+        let _ = fs::remove_dir_all(&directory);
+        for distro in fs::read_dir(test_data("fixtures")).unwrap() {
+            for fixture in fs::read_dir(distro.unwrap().path()).unwrap() {
+                let path = fixture.unwrap().path();
+                let data = fs::read_to_string(&path).unwrap();
+                let (key, response) = data
+                    .split_once('\n')
+                    .unwrap_or_else(|| panic!("{} has no key on its first line", path.display()));
+                fixtures::import(&directory, key.trim_end(), response).unwrap();
+            }
+        }
+        fixtures::init(fixtures::FixtureMode::Replay(directory));
+    });
+}
+
+fn test_data(path: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join(path)
+}
+
+/// Only what scrapers parse from mirrors is compared, so the goldens don't depend on how quickget_core serializes configs
+fn summarise(config: &Config) -> Value {
+    let web = |kind: &str, source: &Source| match source {
+        Source::Web(WebSource { url, checksum, file_name, .. }) => Some(json!({ "kind": kind, "url": url, "checksum": checksum, "file_name": file_name })),
+        _ => None,
+    };
+    let sources = [("iso", &config.iso), ("img", &config.img), ("fixed_iso", &config.fixed_iso), ("floppy", &config.floppy)]
+        .into_iter()
+        .flat_map(|(kind, sources)| sources.iter().flatten().filter_map(move |source| web(kind, source)))
+        .chain(
+            config
+                .disk_images
+                .iter()
+                .flatten()
+                .filter_map(|disk| web("disk_image", &disk.source)),
+        )
+        .collect::<Vec<Value>>();
+    json!({
+        "release": config.release,
+        "edition": config.edition,
+        "arch": config.arch.to_string(),
+        "sources": sources,
+    })
+}
+
+async fn check<T: Distro>() {
+    replay_fixtures();
+    let configs = T::generate_configs()
+        .await
+        .unwrap_or_else(|e| panic!("{} failed to generate from its fixtures: {e}", T::PRETTY_NAME));
+    let mut summaries = configs.iter().map(summarise).collect::<Vec<Value>>();
+    // Scrapers run their requests concurrently, their order isn't part of what's being tested
+    summaries.sort_by_key(|summary| summary.to_string());
+    let generated = serde_json::to_string_pretty(&summaries).unwrap() + "\n";
+
+    let golden = test_data("golden").join(format!("{}.json", T::NAME));
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&golden, &generated).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&golden).unwrap_or_else(|e| {
+        panic!(
+            "Unable to read {}, run with UPDATE_GOLDEN=1 to create it: {e}",
+            golden.display()
+        )
+    });
+    assert!(
+        expected == generated,
+        "{} drifted from {}, run with UPDATE_GOLDEN=1 if the change is intended.\nGenerated:\n{generated}",
+        T::PRETTY_NAME,
+        golden.display()
+    );
+}
+
+macro_rules! golden_tests {
+    ($($test:ident: $distro:ty),* $(,)?) => {
+        $(
+            #[tokio::test]
+            async fn $test() {
+                check::<$distro>().await;
+            }
+        )*
+    };
+}
+
+golden_tests! {
+    antix: Antix,
+    arcolinux: ArcoLinux,
+    cachyos: CachyOS,
+    debian: Debian,
+    devuan: Devuan,
+    easyos: EasyOS,
+    endless: EndlessOS,
+    freebsd: FreeBSD,
+    nixos: NixOS,
+    photon: PhotonOS,
+}
+
+/// A guest which opts into the virtio-win ISO, without any media of its own to scrape
+struct VirtioGuest;
+impl Distro for VirtioGuest {
+    const NAME: &'static str = "virtio-guest";
+    const PRETTY_NAME: &'static str = "VirtIO Guest";
+    const HOMEPAGE: Option<&'static str> = None;
+    const DESCRIPTION: Option<&'static str> = None;
+    const AUXILIARY_MEDIA: &'static [AuxiliaryMedia] = &[AuxiliaryMedia::VirtioWin];
+    async fn generate_configs() -> Result<Vec<Config>, GenerateError> {
+        Ok(vec![Config {
+            release: "1".to_string(),
+            iso: Some(vec![Source::Web(WebSource::url_only("https://example.com/guest.iso"))]),
+            ..Default::default()
+        }])
+    }
+}
+
+#[tokio::test]
+async fn auxiliary_media() {
+    replay_fixtures();
+    // The example URL can't be resolved, and only the attached media is being tested
+    store_data::set_validation_policy(ValidationPolicy {
+        retry_delay: Duration::ZERO,
+        keep_unverified: true,
+        skip_urls: true,
+    });
+    let candidates = VirtioGuest::candidates().await.expect("VirtIO Guest failed to generate");
+    let os = store_data::validate(vec![candidates]).await.remove(0);
+    let fixed_isos = os.releases[0]
+        .fixed_iso
+        .iter()
+        .flatten()
+        .filter_map(|source| match source {
+            Source::Web(web) => Some(web.url.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<&str>>();
+    assert_eq!(
+        fixed_isos,
+        ["https://fedorapeople.org/groups/virt/virtio-win/direct-downloads/stable-virtio/virtio-win-0.1.271.iso"]
+    );
+}